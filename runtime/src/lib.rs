@@ -0,0 +1,42 @@
+//! Runtime support for the compact mode of autoerror, enabled through
+//!  `#[auto_error(compact)]`. In this mode the derive passes the format
+//!  string and fields of a variant to the functions in this crate, rather
+//!  than generating the code formatting each variant, which keeps the
+//!  generated code small for error types with many variants.
+
+use std::fmt;
+
+/// Write a message to the formatter, substituting the arguments for the
+///  placeholders in the template. Placeholders are either `{}`, taking the
+///  next argument, or `{N}`, taking argument N, and braces are escaped by
+///  doubling them, as in format strings.
+pub fn write_message(f: &mut fmt::Formatter<'_>, template: &str, args: &[&dyn fmt::Display]) -> fmt::Result {
+    let mut next = 0;
+    let mut rest = template;
+    while let Some(i) = rest.find(['{', '}']) {
+        f.write_str(&rest[..i])?;
+        let brace = &rest[i..i + 1];
+        rest = &rest[i + 1..];
+        if rest.starts_with(brace) {
+            f.write_str(brace)?;
+            rest = &rest[1..];
+            continue;
+        }
+        if brace == "}" {
+            return Err(fmt::Error);
+        }
+
+        let end = rest.find('}').ok_or(fmt::Error)?;
+        let index = match rest[..end].trim() {
+            "" => {
+                next += 1;
+                next - 1
+            }
+            index => index.parse().map_err(|_| fmt::Error)?,
+        };
+        let arg = args.get(index).ok_or(fmt::Error)?;
+        write!(f, "{}", arg)?;
+        rest = &rest[end + 1..];
+    }
+    f.write_str(rest)
+}
@@ -4,24 +4,488 @@
 //!  to derive `std::fmt::Display` and `std::error:Error` for the error type,
 //!  as well as `std::from::From<T>` for any unnamed variant with one parameter
 //!  inferred to be an error type (currently determined by whether it's type
-//!  name is Error).
+//!  name is Error, looking through `Box`, `Arc` and `Rc`). Boxed trait objects
+//!  such as `Box<dyn std::error::Error + Send + Sync>` are also recognized.
 //!
 //! Default behaviour can be overridden with the auto_error attribute
 //!  - format_str takes a string which becomes the format string for that
-//!    variant
-//!  - make_from forces derivation of std::from::From when set to true
+//!    variant. Fields not mentioned in the format string are left out of
+//!    the message. Placeholders refer to the fields by position, and are
+//!    checked to refer to existing fields.
+//!    Variants whose format string only displays a single field pass the
+//!    formatter on to it, so flags such as `{:#}`, width and precision apply
+//!    to the field.
+//!  - make_from forces derivation of std::from::From when set to true. When
+//!    set to "tuple", std::from::From is instead derived from a tuple of all
+//!    the variant's fields. Two variants converted from the same type are
+//!    reported, as their implementations would conflict, and need make_from
+//!    set to false on one of them.
 //!  - err forces the std::error::Error implementation to return the inner
 //!    type during calls to source, or in other words to treat the inner
-//!    type as an error type.
+//!    type as an error type. The inner type can be an `Option`, in which
+//!    case source only returns the inner error when present. When set to
+//!    "as_ref", the inner type is turned into an error through
+//!    `AsRef<dyn std::error::Error>`, which is needed for types such as
+//!    `anyhow::Error` that don't implement `std::error::Error` themselves.
+//!    `anyhow::Error` is detected automatically, as is `eyre::Report` when
+//!    the eyre feature is enabled.
+//!  - separator sets the string placed between the elements of `Vec` fields
+//!    when displaying them. Aggregates are joined with ", " by default,
+//!    other `Vec` fields only when the variant sets a separator, and are
+//!    otherwise displayed as a whole, e.g. with `{:?}`.
+//!  - chain_fmt appends the message of the source to the message of the variant,
+//!    as in "failed to save user: disk full", without repeating it in the format
+//!    string. Set to false, it turns off chain_fmt given on the enum for the
+//!    variant.
+//!  - whatever marks the variant as the catch-all for ad-hoc errors. It holds a
+//!    message, optionally followed by a source, and gets `whatever` and
+//!    `whatever_with_source` constructors taking anything implementing
+//!    `std::fmt::Display` as message, such as `format_args!(...)`.
+//!  - code takes a string with a stable code for the variant, returned by a
+//!    generated `code` method as `Option<&'static str>`. When any variant has a
+//!    code all of them need one, except hidden variants, for which `code` returns
+//!    `None`, and codes need to be unique.
+//!    Enums with an integer `#[repr(..)]` and explicit discriminants instead get a
+//!    `code` method returning the discriminant as that integer type, and their
+//!    kind enum gets the same discriminants and a `TryFrom` implementation from
+//!    the integer type. Integrations report whichever kind of code is generated.
+//!  - status takes the HTTP status code used for the variant by the web framework
+//!    integrations, defaulting to 500.
+//!  - body takes the response body used for the variant by the actix and rocket
+//!    integrations instead of the error message, such as to keep internal details
+//!    out of responses.
+//!  - grpc takes the name of the gRPC status code used for the variant, such as
+//!    "not_found", defaulting to "internal".
+//!  - problem_type and title take the type URI and title used for the variant in
+//!    RFC 7807 problem details, defaulting to "about:blank" and the variant name
+//!    written out as words.
+//!  - jsonrpc_code takes the JSON-RPC error code of the variant, defaulting to
+//!    -32603 (internal error).
+//!  - exit_code takes the process exit code of the variant, from 1 to 255,
+//!    returned by a generated `exit_code` method. Variants without one exit with 1.
+//!  - errno takes the raw OS error code of the variant, as an integer or a
+//!    constant such as `libc::ENOENT`. The generated `raw_os_error` method returns
+//!    it, and `from_raw_os_error` and `from_io_error` turn a code back into the
+//!    variant. Such variants can't have payload fields, and codes need to be
+//!    unique.
+//!  - py_exc takes the Python exception raised for the variant by the pyo3
+//!    integration, such as "ValueError", or the path to a custom exception type,
+//!    defaulting to "RuntimeError".
+//!  - napi_status takes the name of the `napi::Status` used for the variant by the
+//!    napi integration, such as "InvalidArg", defaulting to "GenericFailure".
+//!  - sqlx routes a class of `sqlx::Error` to the variant, one of "row_not_found",
+//!    "pool_timed_out", "unique_violation", "foreign_key_violation",
+//!    "not_null_violation" or "check_violation", when the sqlx feature is
+//!    enabled. The generated `From<sqlx::Error>` picks the variant by the class of
+//!    the error, and otherwise falls back on the variant converted from
+//!    `sqlx::Error`, or the one marked "other". Routed variants have no fields or
+//!    hold the `sqlx::Error`.
+//!  - diesel routes a class of `diesel::result::Error` to the variant in the same
+//!    way when the diesel feature is enabled, one of "not_found",
+//!    "unique_violation", "foreign_key_violation", "not_null_violation",
+//!    "check_violation", "serialization_failure" or "read_only_transaction",
+//!    with "other" marking the variant for the remaining errors.
+//!  - reqwest routes `reqwest::Error` to the variant by its classification when
+//!    the reqwest feature is enabled, one of "timeout", "connect", "status",
+//!    "client_error", "server_error", "redirect", "body", "decode", "request"
+//!    or "builder", with "other" marking the variant for the remaining errors.
+//!    As errors can fall into several classes, such as a timeout while
+//!    connecting, the classes are checked in the order of the variants.
+//!  - retryable, or transient set to true, marks the variant as retryable in the
+//!    generated `is_retryable` method. Variants wrapping the error type itself
+//!    return whether the wrapped error is retryable, unless transient is given
+//!    explicitly.
+//!  - severity takes the severity of the variant, one of "debug", "info", "warn",
+//!    "error" or "critical", defaulting to "error". When any variant has one, a
+//!    `severity` method returns it as a generated enum named after the error type
+//!    with Severity appended, ordered from least to most severe.
+//!  - log takes the `log::Level` of the variant, one of "error", "warn", "info",
+//!    "debug" or "trace", defaulting to "error". The tracing integration uses the
+//!    same level.
+//!  - report makes the sentry integration report every error of the variant
+//!    created through the generated `From` implementations and constructors.
+//!  - help takes a help text suggesting how to resolve the error, returned by a
+//!    generated `help` method and shown by display_chain, termination and the
+//!    miette integration.
+//!  - url takes a documentation link for the variant, returned by a generated
+//!    `url` method and used by the miette integration. Variants without one use
+//!    the url template given on the enum, if any.
+//!  - msg_key takes a stable key for the message of the variant, for localizing
+//!    errors through a translation catalog such as Fluent. When any variant has
+//!    one, a generated `message_key` method returns it, defaulting to the
+//!    variant name in kebab-case, and `message_args` returns the fields used in
+//!    the format string as named values. Arguments are named arg0, arg1, ...
+//!    after their position, unless the field is marked with
+//!    `#[auto_error(arg = "...")]`.
+//!  - into takes the conversion of the variant into the error type given by into
+//!    on the enum. A path to a variant or function is called with the fields of
+//!    the variant, as in `into = "ApiError::NotFound"`, and any other expression
+//!    is evaluated with the error in scope as `error`, as in
+//!    `into = "ApiError::Internal(error.to_string())"`.
+//!  - deserialize set to false leaves the variant out of deserialization, for
+//!    variants holding data that can't be deserialized. Such errors deserialize
+//!    into the variant marked catch_all, which should have a single field
+//!    constructible from the message, or into the whatever variant.
+//!  - from_poison marks the variant that poisoned locks are converted into, with a
+//!    generated `From<std::sync::PoisonError<T>>` implementation for any guard,
+//!    so `lock()?` works. The guard is dropped, and the variant should have no
+//!    fields or a single field constructible from the message of the error.
+//!  - from_panic marks the variant that panic payloads caught by
+//!    `std::panic::catch_unwind` are converted into, with a generated
+//!    `From<Box<dyn Any + Send>>` implementation. The variant holds the message of
+//!    the panic, taken from the payload when it is a string, optionally followed
+//!    by a `Box<dyn Any + Send>` field keeping the payload itself.
+//!  - context marks the variant holding a message and a source describing it,
+//!    such as `Context(String, Box<dyn Error + Send + Sync>)`, and generates an
+//!    extension trait (the error type name with Context appended) with `context`
+//!    and `with_context` methods on `Result`, wrapping any error convertible into
+//!    the source together with a message, as in
+//!    `fs::read(path).context("loading config")?`. When the source is an
+//!    `Option`, the trait is implemented for `Option` as well.
 //!
-//! From derivation and source returning work only for variants with a single field.
+//! Source returning works for variants with a single field, or for variants where
+//!  one field is marked with `#[auto_error(source)]`. Fields referring to the
+//!  error type itself (such as `Box<Self>`) are treated as sources, allowing
+//!  errors to wrap other errors of the same type. Otherwise the only field whose
+//!  type name ends in Error, such as the `std::io::Error` in
+//!  `Read(PathBuf, std::io::Error)`, is the source, without deriving From for it.
+//!  From derivation works for variants with a single field, or for variants where
+//!  one field is marked with `#[auto_error(from)]`. The remaining fields are then
+//!  filled in using `Default::default()`.
+//!
+//! Types listed in `error_types(sqlx::Error, MyFailure)` on the enum are
+//!  treated as errors regardless of their name, getting source and From just
+//!  like types named Error. Listed paths match the end of the field type, so
+//!  `MyFailure` also covers `crate::MyFailure`. `error_suffixes("Error", "Err",
+//!  "Failure")` replaces the name check, treating types whose name ends in
+//!  one of the suffixes as errors, and `heuristic = false` turns off looking
+//!  at type names altogether, leaving marked sources, listed types and
+//!  trait objects of Error.
+//!
+//! Picking the source among several fields by type name is a breaking change
+//!  for variants that had no source before. A field whose type is named like an
+//!  error without implementing Error, such as a plain `struct ValidationError`,
+//!  no longer compiles next to other fields. Such variants take `err = false`, or
+//!  the enum takes `heuristic = false` to turn the name check off entirely.
+//!
+//! Variants holding a `Vec` of errors are aggregates. Their elements are joined
+//!  when displayed, the first element is returned from source, and a generated
+//!  `sources` method iterates over all of them.
+//!
+//! Source fields marked with `#[auto_error(arc)]` hold their error in an `Arc`,
+//!  so they can be cloned. The generated `From` implementations then take the
+//!  error itself (or a `Box` for trait objects) and wrap it.
+//!
+//! Fields marked with `#[auto_error(boxed)]`, or the field a variant marked with
+//!  it is converted from, hold a large value in a `Box` to keep the error type
+//!  small. The generated `From` implementations take the value itself and box
+//!  it, so callers convert from the unboxed type as before.
+//!
+//! Variants with a single `Cow<'static, str>` field hold a message that is only
+//!  allocated when needed. They get `From` implementations for both `&'static str`
+//!  and `String`, borrowing static messages and taking ownership of the others.
+//!
+//! Fields marked with `#[auto_error(backtrace)]` or `#[auto_error(location)]`
+//!  capture a `std::backtrace::Backtrace`, or the `&'static std::panic::Location`
+//!  where the error was created. The generated `From` implementations, constructors
+//!  and context selectors fill them in rather than taking them, they are left out
+//!  of the message, and generated `backtrace` and `location` methods return them.
+//!  Fields marked with `#[auto_error(timestamp)]` likewise capture the time the
+//!  error was created, calling `now()` on the field type, such as
+//!  `std::time::SystemTime` or a type of your own, and are returned by a generated
+//!  `timestamp` method.
+//!  Fields marked with `#[auto_error(instance_id)]` get a unique ID generated for
+//!  every error created, by calling `new()` on the field type, or the function given
+//!  as in `#[auto_error(instance_id = "uuid::Uuid::new_v4")]`. A generated
+//!  `instance_id` method returns it, so users can report the ID of the error they saw.
+//!
+//! Fields marked with `#[auto_error(config_path)]` and `#[auto_error(config_key)]`
+//!  hold the configuration file and key an error occurred in, and are appended to
+//!  the message when set, as in "invalid port 70000 in config.toml at key
+//!  server.port". They start out empty (or `None` when optional), and are set
+//!  through the generated `in_config` and `at_key` methods, as in
+//!  `err.in_config("config.toml").at_key("server.port")`. The path needs to
+//!  implement `AsRef<Path>`, and the key `AsRef<str>`.
+//!
+//! The derive also generates `chain` and `root_cause` methods, which respectively
+//!  iterate over the error and its chain of sources, and return the last error in
+//!  that chain, as well as a `variant_name` method returning the name of the variant.
+//!
+//! Variants marked `#[doc(hidden)]`, such as a `__NonExhaustive` variant kept for
+//!  forward compatibility, are displayed as "unknown error", and are left out of
+//!  the generated accessors, constructors, context selectors, catalog and list
+//!  of kinds.
+//!
+//! Any `cfg` and `cfg_attr` attributes on a variant are carried over to the code
+//!  generated for it, so feature gated variants work as expected.
+//!
+//! A `#[deprecated]` attribute on a variant is carried over to the generated
+//!  accessors, constructors, builders, context selectors and context extension
+//!  trait, so code creating or inspecting the variant is warned about it. The
+//!  generated implementations themselves allow its use, and trait implementations
+//!  such as `From` can't be deprecated in Rust. Marking the variant with
+//!  `#[auto_error(superseded_by = "NewVariant")]` names its replacement, which
+//!  deprecates the generated items with a note pointing to the new variant when it
+//!  isn't deprecated itself.
+//!
+//! Fields disabled through a `cfg` attribute are removed before the derive sees
+//!  them, and fields with a `cfg` attribute are not considered when inferring the
+//!  source and `std::from::From`, so both are the same in every configuration. A
+//!  `cfg_attr` can select a different format_str for each configuration.
+//!
+//! The enum itself can also carry an auto_error attribute, enabling optional
+//!  functionality
+//!  - display_chain generates a `display_chain` method, rendering the error
+//!    followed by its chain of sources as "error: ..." and "caused by: ..." lines,
+//!    and a "help: ..." line when the variant has a help text.
+//!  - chain_fmt appends the message of the source to the message of every variant
+//!    with a source, as chain_fmt does for a single variant. Leave it off when a
+//!    reporter walks the chain of sources itself, or its messages appear twice.
+//!  - accessors generates `is_*`, `as_*` and `into_*` methods for every variant,
+//!    named after the variant in snake case. Unit variants only get an `is_*`
+//!    method.
+//!  - constructors generates a constructor function for every variant, named after
+//!    the variant in snake case and taking `impl Into<T>` for each field. Names
+//!    that are keywords become raw identifiers, such as `r#type` for a Type variant.
+//!  - track_caller marks the generated constructors with `#[track_caller]`.
+//!  - builders generates a builder for every variant with three or more fields,
+//!    named after the variant with Builder appended. The variant's snake case
+//!    function returns an empty builder with a setter per field, named by the
+//!    field's `arg` or `arg0`, `arg1`, ... otherwise, and `build()` is only
+//!    available once every field is set: `Error::parse_error().line(12).column(4)
+//!    .message(m).build()`. These variants get no positional constructor, and
+//!    builders are not supported for generic enums.
+//!  - context_selectors generates a context selector struct for every variant,
+//!    named after the variant with Context appended, holding all fields but the
+//!    source. Together with the generated `IntoError`, `ResultExt` and `OptionExt`
+//!    traits (prefixed with the error type name) these allow adding context at
+//!    the call site, as in `fs::read(path).context(ReadConfigContext(path))?`, or
+//!    through `with_context` taking a closure returning the selector, which is
+//!    only called on errors, so any context is only computed when needed.
+//!  - debug generates a `std::fmt::Debug` implementation showing the error message
+//!    followed by its chain of sources and, when captured, its backtrace, which
+//!    is what gets printed when main returns the error. The enum should then not
+//!    derive Debug itself.
+//!  - kind generates a fieldless companion enum mirroring the variants, named
+//!    after the error type with Kind appended (or the given name when set to a
+//!    string), together with a `kind` method and a `From<&Error>` implementation.
+//!    A `KINDS` constant lists the kinds of all variants, for enumerating every
+//!    error in documentation or exhaustive tests.
+//!    Kinds are parsed back from their variant name or code with `FromStr` and
+//!    `TryFrom<&str>`, returning the unrecognized string as error.
+//!  - eq implements `PartialEq` and `Eq` comparing only the variants of errors,
+//!    ignoring their fields, so tests can check for a kind of error as in
+//!    `assert_eq!(e, Error::NotFound)`. The same comparison is available as a
+//!    `same_kind` method.
+//!  - test_helpers generates `expect_kind` and `expect_err_kind` methods for tests,
+//!    asserting that an error or result is of the given kind, as in
+//!    `Error::expect_err_kind(parse(s), ErrorKind::NotFound)`. On failure they panic
+//!    with the actual kind, message and chain of sources, or the value when the
+//!    result is `Ok`. Requires kind to be enabled as well.
+//!  - hash implements `Hash` using only the variant of errors and their code,
+//!    ignoring their fields. Together with eq this allows keeping errors in a
+//!    `HashSet` or `HashMap`, such as for deduplicating alerts.
+//!  - macros generates `bail_*!` and `ensure_*!` macros, named after the error
+//!    type in snake case, that return early with the given variant, as in
+//!    `ensure_error!(line.len() < 80, TooLong(line.len()))`. The error type needs
+//!    to be in scope where the macros are used.
+//!  - result_alias takes a name, and generates a result type of that name with
+//!    the error type as default error, as in
+//!    `pub type Result<T, E = Error> = core::result::Result<T, E>`.
+//!  - into takes the name of another error type, and generates a From
+//!    implementation converting errors into it, as set on every variant by into.
+//!    This saves writing large conversions by hand between the layers of an
+//!    application, such as from domain errors to API errors.
+//!  - into_variants does the same as into, but variants without into of their own
+//!    convert into the variant of the same name of the other error type, passing
+//!    their fields along. This keeps module level errors in sync with the crate
+//!    level error containing their variants, as variants missing there fail to
+//!    compile.
+//!  - url takes a template for the documentation links of the variants, in which
+//!    {code} is replaced by the code of the variant, as in
+//!    `url = "https://docs.example.com/errors/{code}"`.
+//!  - localized displays errors through a lookup function registered with the
+//!    generated `set_localizer` method, which is given the message key and
+//!    arguments of the error, as returned by `message_key` and `message_args`.
+//!    Errors are displayed using their format string when no function is
+//!    registered or it returns None, so one error type can serve both logs and
+//!    translated messages.
+//!  - compact formats the messages of variants with plain format strings, whose
+//!    placeholders have no format specs, through the autoerror-runtime crate
+//!    rather than generating the formatting code for each variant, which keeps
+//!    the generated code small for error types with many variants. Requires the
+//!    runtime feature, and autoerror-runtime as dependency.
+//!  - format_table does the same as compact, but keeps the format strings in a
+//!    static table indexed by the position of the variant, so only that index is
+//!    passed to the autoerror-runtime crate for each variant. Requires the runtime
+//!    feature, and autoerror-runtime as dependency. It can't be combined with
+//!    compact.
+//!  - no_alloc generates a `write_message` method writing the message into any
+//!    `std::fmt::Write` without allocating, so errors can be displayed in panic
+//!    handlers and allocators. The generated Display never allocates itself, but
+//!    the fields it displays need to do the same. Localized error types look up
+//!    their messages as strings, and can't be no_alloc.
+//!  - heapless does the same as no_alloc, and generates a `to_heapless` method
+//!    returning the message in a `heapless::String<N>`, failing when it doesn't
+//!    fit. Requires the heapless feature, and heapless as dependency.
+//!  - msrv takes the minimum supported Rust version of the crate, as in
+//!    `msrv = "1.56"`. Parameters generating code that needs a newer compiler
+//!    are then rejected: exit codes need 1.61, localized 1.63 and backtrace
+//!    fields 1.65. As autoerror itself requires Rust 1.56, older versions are
+//!    rejected, and on such compilers proc-macro2 and quote may need to be held
+//!    back to releases that still support them. These rules are checked against
+//!    the msrv, but the generated code is not built with that compiler.
+//!  - generate_tests emits a test module next to the error type, with a test for
+//!    every variant constructing it from placeholder values, checking that its
+//!    message matches its format string and that `source` returns a source exactly
+//!    when the variant has one. Variants with fields no placeholder value can be
+//!    made up for are skipped. Messages of localized error types and format
+//!    strings with format specs are only checked to display without failing. The
+//!    tests, such as `error_autoerror_tests::io_message_and_source`, are visible
+//!    to the module of the error type. Not supported for generic enums.
+//!  - catalog generates an associated `CATALOG` constant describing every variant
+//!    by name, code, format string and doc comment, using a generated struct named
+//!    after the error type with Info appended (or the given name when set to a
+//!    string).
+//!  - problem_details generates a `to_problem_details` method returning the RFC
+//!    7807 problem details of the error, with the status, type and title of the
+//!    variant and the error message as detail, using a generated struct named
+//!    after the error type with ProblemDetails appended (or the given name when
+//!    set to a string).
+//!  - jsonrpc generates `jsonrpc_code` and `to_jsonrpc_error` methods, the latter
+//!    returning a JSON-RPC 2.0 error object with the code of the variant, the
+//!    error message, and the message of the source as data. The object uses a
+//!    generated struct named after the error type with JsonRpcError appended (or
+//!    the given name when set to a string), which can also be converted into
+//!    from the error.
+//!  - termination generates a wrapper around `Result<(), Error>` implementing
+//!    `std::process::Termination`, named after the error type with Termination
+//!    appended (or the given name when set to a string). Returned from `main`, it
+//!    prints the error and its chain of sources and exits with the exit code of
+//!    the variant.
+//!  - ffi generates a fieldless `#[repr(C)]` companion enum mirroring the variants,
+//!    numbered from 1 in declaration order, named after the error type with Code
+//!    appended (or the given name when set to a string), together with an
+//!    `ffi_code` method.
+//!  - last_error, used together with ffi, keeps a thread local last error set
+//!    through `set_last_error`, and read through `take_last_error` and
+//!    `with_last_error`. It also exports the C functions `*_last_error_code`,
+//!    `*_error_message` and `*_error_message_free`, prefixed with the error type
+//!    name in snake case (or the given prefix when set to a string).
+//!  - integrations takes a list of the ecosystem integrations below, as in
+//!    `integrations(serde, tracing, metrics = "app_errors")`, which is the same as
+//!    giving them one by one but rejects anything else. Each of them needs the
+//!    cargo feature of autoerror it is named after, reporting which one is missing
+//!    otherwise (log_kv and emit_log need the log feature, deserialize the serde
+//!    feature).
+//!  - actix generates an `actix_web::ResponseError` implementation responding
+//!    with the status of the variant and the error message as plain text body.
+//!    Requires the actix feature.
+//!  - warp generates a `warp::reject::Reject` implementation, together with an
+//!    `into_rejection` method. Requires the warp feature.
+//!  - rocket generates a `rocket::response::Responder` implementation responding
+//!    with the status of the variant and the error message as body. Requires the
+//!    rocket feature.
+//!  - tonic generates a conversion into `tonic::Status`, with the gRPC code of
+//!    the variant and the error message. Requires the tonic feature.
+//!  - pyo3 generates a conversion into `pyo3::PyErr`, raising the Python exception
+//!    of the variant with the error message. Requires the pyo3 feature.
+//!  - napi generates a conversion into `napi::Error`, with the N-API status of
+//!    the variant and the error message as reason. Requires the napi feature.
+//!  - log generates a `log_level` method returning the log level of the variant.
+//!    emit_log does the same, and additionally logs every error created through
+//!    the generated `From` implementations and constructors at that level.
+//!    log_kv also does the same, and additionally implements `log::kv::ToValue`
+//!    and `log::kv::Source`, so structured loggers get the error with its chain of
+//!    sources, or as kind, code, message and source pairs. Requires the log
+//!    feature, and log with its kv_std feature as dependency for log_kv.
+//!  - slog implements `slog::Value` and `slog::KV`, the latter giving the error as
+//!    kind, code, message and source pairs, as in `error!(log, "failed"; e)`.
+//!    Requires the slog feature.
+//!  - valuable implements `valuable::Valuable` and `valuable::Structable`, giving
+//!    the error as a struct of its kind, code and message, which tracing records
+//!    as structured value through `tracing::field::valuable`. Requires the
+//!    valuable feature, and valuable as dependency.
+//!  - tracing generates a `record` method emitting a tracing event at the log
+//!    level of the variant, with the variant name as kind, the code when given,
+//!    and the error message, as well as an `as_value` method for recording the
+//!    error in the fields of other events. Requires the tracing feature.
+//!  - otel generates a `record_exception` method recording the error as an
+//!    exception event on the current OpenTelemetry span, with the variant name as
+//!    type, the error message and the code when given, and setting the status of
+//!    the span to error. Requires the otel feature, and opentelemetry as
+//!    dependency.
+//!  - metrics increments a counter from the metrics crate for every error created
+//!    through the generated `From` implementations and constructors, labeled with
+//!    the variant name as kind and the code when given. The counter is named after
+//!    the error type in snake case with _total appended, or the given name when set
+//!    to a string. Requires the metrics feature.
+//!  - sentry generates a `capture` method reporting the error and its chain of
+//!    sources to Sentry, with a fingerprint made of the error type name and the
+//!    code of the variant, or its name when it has no code. Requires the sentry
+//!    feature.
+//!  - miette implements `miette::Diagnostic` for the error, with the code,
+//!    severity, help and url of the variant. A field marked with
+//!    `#[auto_error(source_code)]` provides the source code, and fields marked
+//!    with `#[auto_error(label)]` or `#[auto_error(label = "...")]` the labeled
+//!    spans within it. Requires the miette feature.
+//!  - async_graphql generates an `async_graphql::ErrorExtensions` implementation,
+//!    adding the variant name, and the code and status when given, to the
+//!    extensions of the GraphQL error. Requires the async_graphql feature.
+//!  - serde implements `serde::Serialize` for the error, as an object with the
+//!    variant name as kind, the code when given, the error message, and the
+//!    messages of its chain of sources as source. The generated problem details
+//!    and JSON-RPC error objects derive `serde::Serialize` as well. Requires the
+//!    serde feature, and serde with its derive feature as dependency.
+//!  - deserialize does the same as serde, but additionally includes the fields of
+//!    the variant when serializing, and implements `serde::Deserialize` turning
+//!    that back into the error. Errors of a known kind without fields are
+//!    rejected.
+//!  - schemars implements `schemars::JsonSchema` describing the serialized form of
+//!    the error, so it can be included in OpenAPI documents. The generated problem
+//!    details and JSON-RPC error objects derive it as well. Requires serde to be
+//!    enabled, the schemars feature, and schemars as dependency.
+//!  - arbitrary implements `arbitrary::Arbitrary`, picking a variant and filling
+//!    in its fields from the unstructured data, so fuzzers and property tests can
+//!    exercise every variant. Captured fields are filled in as when the error is
+//!    created. Requires the arbitrary feature, and arbitrary as dependency, and all
+//!    other fields to implement `Arbitrary`.
+//!  - validator displays fields holding `validator::ValidationErrors` as a summary
+//!    of the fields that failed, as in "email: must be a valid email; name:
+//!    length", using the message of each validation or else its code. The
+//!    generated `validation_errors` method returns these messages by field path,
+//!    which the serde integration includes as `validation`, and the problem
+//!    details as `errors`. Requires the validator feature.
+//!
+//! The `autoerror` attribute macro is an alternative to the derive that can also
+//!  modify the enum itself, adding the derive unless already present. Given clone,
+//!  as in `#[autoerror(clone)]`, it makes the error type `Clone` by wrapping the
+//!  errors held by source fields in an `Arc` and marking those fields with
+//!  `#[auto_error(arc)]`, for errors that need to be cached or broadcast. Given
+//!  backtrace or location, it appends a field capturing a backtrace or location
+//!  to every variant with fields, marked as described above.
+//!  Given the type of an instance ID, as in `#[autoerror(instance_id = "ErrorId")]`,
+//!  it likewise appends a field holding an instance ID.
+//!
+//! The `error_enum!` macro declares an error enum tersely, one line per variant,
+//!  as its name and fields optionally followed by `=> "message"` giving its
+//!  format string and further auto_error parameters in brackets, with from short
+//!  for `make_from=true`. It expands to the enum deriving AutoError, as in
+//!  `NotFound(String) => "User {} not found" [status=404],`.
+//!
+//! Generic enums are supported. Fields whose type mentions a type parameter
+//!  are required to implement `std::fmt::Display`, and `std::error::Error`
+//!  when they are returned from source. Type parameters only used in fields
+//!  left out of the message can be listed in `no_bound(T, U)` on the enum to
+//!  skip these bounds, and `bound = "T: MyTrait"` replaces the inferred bounds
+//!  with the given ones.
 //!
 //! # Example
 //!
 //! ```
-//! #[derive(AutoError)]
 //! use autoerror::AutoError;
 //!
+//! #[derive(Debug, AutoError)]
 //! enum Error {
 //!     #[auto_error(format_str="Document not found")]
 //!     NotFound,
@@ -33,241 +497,6795 @@
 
 use proc_macro::TokenStream;
 use syn::{parse_macro_input, DeriveInput};
-use quote::{quote, format_ident};
+use syn::ext::IdentExt;
+use quote::{quote, quote_spanned, format_ident, ToTokens};
 
-// Infer whether wrapped type is an error
-//  by applying a name based heuristic (type path
-//  last segment is Error)
-fn infer_is_error(variant: &syn::Variant) -> bool {
-    if let syn::Fields::Named(_) = variant.fields {
-        return false;
+// Strip a single parameter generic wrapper type (such as Box) from a type, if present
+fn strip_wrapper<'a>(ty: &'a syn::Type, wrappers: &[&str]) -> Option<&'a syn::Type> {
+    if let syn::Type::Path(path) = ty {
+        let segment = path.path.segments.last()?;
+        if !wrappers.iter().any(|wrapper| segment.ident == wrapper) {
+            return None;
+        }
+        if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+            if args.args.len() == 1 {
+                if let syn::GenericArgument::Type(inner) = &args.args[0] {
+                    return Some(inner);
+                }
+            }
+        }
     }
 
-    if variant.fields.len() != 1 {
-        return false;
-    }
-    let field = variant.fields.iter().next().unwrap();
+    None
+}
+
+// Strip a Box, Arc or Rc wrapper from a type, if present
+fn strip_pointer(ty: &syn::Type) -> Option<&syn::Type> {
+    strip_wrapper(ty, &["Box", "Arc", "Rc"])
+}
+
+// Strip an Option wrapper from a type, if present
+fn strip_option(ty: &syn::Type) -> Option<&syn::Type> {
+    strip_wrapper(ty, &["Option"])
+}
+
+// Strip a Vec wrapper from a type, if present
+fn strip_vec(ty: &syn::Type) -> Option<&syn::Type> {
+    strip_wrapper(ty, &["Vec"])
+}
 
-    if let syn::Type::Path(path) = &field.ty {
-        if path.path.segments.len() == 0 {
+// Detect Cow<'static, str> messages, which can be taken from static strings
+//  without allocating
+fn is_cow_str(ty: &syn::Type) -> bool {
+    if let syn::Type::Path(path) = ty {
+        let last = path.path.segments.last().unwrap();
+        if last.ident != "Cow" {
             return false;
         }
-        if path.path.segments.last().unwrap().ident == "Error" {
-            return true;
+        if let syn::PathArguments::AngleBracketed(args) = &last.arguments {
+            let lifetime = args.args.iter().any(|arg| matches!(arg, syn::GenericArgument::Lifetime(l) if l.ident == "static"));
+            let str_type = args.args.iter().any(|arg| matches!(arg, syn::GenericArgument::Type(syn::Type::Path(p)) if p.path.is_ident("str")));
+            return lifetime && str_type;
         }
     }
 
     false
 }
 
-// Auto-generate a basic format string for a variant.
-fn infer_format_str(variant: &syn::Variant) -> String {
-    let mut result = "".to_string();
-    let mut first = true;
-    for _var in variant.fields.iter() {
-        if first {
-            result += "{}";
-            first = false;
-        } else {
-            result += " {}";
+// Detect the ValidationErrors of the validator crate, possibly boxed
+fn is_validation_errors(ty: &syn::Type) -> bool {
+    let ty = strip_pointer(ty).unwrap_or(ty);
+    match ty {
+        syn::Type::Path(path) => path.path.segments.last().map_or(false, |s| s.ident == "ValidationErrors"),
+        _ => false,
+    }
+}
+
+// Function collecting the messages of failed validations by the path of
+//  the field they failed on, such as items[0].name, giving the code of the
+//  validation when it has no message
+fn validation_fields_fn() -> proc_macro2::TokenStream {
+    quote!{
+        fn autoerror_validation_fields(
+            errors: &::validator::ValidationErrors,
+            prefix: &str,
+            fields: &mut ::std::collections::BTreeMap<::std::string::String, ::std::vec::Vec<::std::string::String>>,
+        ) {
+            for (name, kind) in errors.errors() {
+                let path = if prefix.is_empty() {
+                    ::std::string::ToString::to_string(name)
+                } else {
+                    ::std::format!("{}.{}", prefix, name)
+                };
+                match kind {
+                    ::validator::ValidationErrorsKind::Field(errors) => {
+                        let messages = fields.entry(path).or_default();
+                        for error in errors {
+                            messages.push(::std::string::ToString::to_string(error.message.as_ref().unwrap_or(&error.code)));
+                        }
+                    }
+                    ::validator::ValidationErrorsKind::Struct(errors) => autoerror_validation_fields(errors, &path, fields),
+                    ::validator::ValidationErrorsKind::List(items) => {
+                        for (i, errors) in items {
+                            autoerror_validation_fields(errors, &::std::format!("{}[{}]", path, i), fields);
+                        }
+                    }
+                }
+            }
         }
     }
-    result
 }
 
-struct ErrorVariant<'a> {
-    err: bool,
-    make_from: bool,
-    format_str: String,
-    variant: &'a syn::Variant,
+// The type that is ultimately returned from source for a source field
+fn source_type(ty: &syn::Type) -> &syn::Type {
+    let ty = strip_option(ty).unwrap_or(ty);
+    let ty = strip_vec(ty).unwrap_or(ty);
+    strip_pointer(ty).unwrap_or(ty)
 }
 
-// Parse a single variant in the enum
-fn parse_variant(variant: &syn::Variant) -> Result<ErrorVariant, TokenStream> {
-    // validate fields are unnamed (but present!)
-    match variant.fields {
-        syn::Fields::Named(_) => { return Err(TokenStream::from(syn::Error::new_spanned(variant, "Named fields not supported").to_compile_error())); }
-        syn::Fields::Unnamed(_) => {}
-        syn::Fields::Unit => { }
+// Generate an expression turning a reference e to a source field
+//  into an Option<&(dyn Error + 'static)>
+fn source_expr(ty: &syn::Type, as_ref: bool) -> proc_macro2::TokenStream {
+    if let Some(inner) = strip_option(ty) {
+        let inner = source_expr(inner, as_ref);
+        quote!{ e.as_ref().and_then(|e| #inner) }
+    } else if let Some(inner) = strip_vec(ty) {
+        let inner = source_expr(inner, as_ref);
+        quote!{ e.first().and_then(|e| #inner) }
+    } else if as_ref && strip_pointer(ty).is_some() {
+        quote!{ ::std::option::Option::Some(::std::convert::AsRef::<dyn ::std::error::Error + 'static>::as_ref(&**e)) }
+    } else if as_ref {
+        quote!{ ::std::option::Option::Some(::std::convert::AsRef::<dyn ::std::error::Error + 'static>::as_ref(e)) }
+    } else if strip_pointer(ty).is_some() {
+        quote!{ ::std::option::Option::Some(&**e as &(dyn ::std::error::Error + 'static)) }
+    } else {
+        quote!{ ::std::option::Option::Some(e as &(dyn ::std::error::Error + 'static)) }
     }
+}
 
-    let mut attr: Option<_> = None;
-    for attr_cand in variant.attrs.iter() {
-        if attr_cand.path.is_ident("auto_error") {
-            if attr != None {
-                return Err(TokenStream::from(syn::Error::new_spanned(&attr_cand, "Duplicate occurence of auto_error attribute").to_compile_error()));
-            }
-            attr = Some(attr_cand);
+// Detect error report types that don't implement Error themselves, but
+//  can be turned into one through AsRef (anyhow::Error, and eyre::Report
+//  when the eyre feature is enabled)
+fn is_report_type(ty: &syn::Type) -> bool {
+    if let syn::Type::Path(path) = ty {
+        let segments: Vec<_> = path.path.segments.iter().map(|s| s.ident.to_string()).collect();
+        if segments.ends_with(&["anyhow".to_string(), "Error".to_string()]) {
+            return true;
+        }
+        if cfg!(feature = "eyre") && segments.ends_with(&["eyre".to_string(), "Report".to_string()]) {
+            return true;
         }
     }
 
-    let mut result = ErrorVariant {
-        err: infer_is_error(variant),
-        make_from: infer_is_error(variant),
-        format_str: infer_format_str(variant),
-        variant,
-    };
+    false
+}
 
-    if let Some(attr) = attr {
-        let meta = attr.parse_meta().map_err(|e| e.to_compile_error())?;
-        let meta = match meta {
-            syn::Meta::List(list) => list,
-            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&meta, "Incorrect auto_error arguments").to_compile_error())); },
-        };
+// Infer whether the source of a variant should be obtained through AsRef
+fn infer_source_as_ref(ty: &syn::Type) -> bool {
+    let ty = strip_option(ty).unwrap_or(ty);
+    let ty = strip_vec(ty).unwrap_or(ty);
+    is_report_type(strip_pointer(ty).unwrap_or(ty))
+}
 
-        for arg in meta.nested.iter() {
-            let arg = match arg {
-                syn::NestedMeta::Meta(arg) => arg,
-                _ => { return Err(TokenStream::from(syn::Error::new_spanned(arg, "Incorrect auto_error arguments").to_compile_error())); },
-            };
-            let arg = match arg {
-                syn::Meta::NameValue(arg) => arg,
-                _ => { return Err(TokenStream::from(syn::Error::new_spanned(arg, "Incorrect auto_error arguments").to_compile_error())); },
-            };
-            if arg.path.is_ident("err") {
-                result.err = match &arg.lit {
-                    syn::Lit::Bool(v) => v.value,
-                    _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for err, expected bool").to_compile_error())); },
-                };
-            } else if arg.path.is_ident("format_str") {
-                result.format_str = match &arg.lit {
-                    syn::Lit::Str(v) => v.value(),
-                    _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for format_str, expected string").to_compile_error())); },
-                };
-            } else if arg.path.is_ident("make_from") {
-                result.make_from = match &arg.lit {
-                    syn::Lit::Bool(v) => v.value,
-                    _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for make_from, expected bool").to_compile_error())); },
-                };
-            } else {
-                return Err(TokenStream::from(syn::Error::new_spanned(variant, "Unknown parameter").to_compile_error()));
+// Wrap the error held by a source field in an Arc, looking through Option
+//  and Vec, and replacing a Box. Returns None for fields that are already
+//  shared through an Arc or Rc.
+fn share_type(ty: &syn::Type) -> Option<syn::Type> {
+    if let Some(inner) = strip_option(ty) {
+        let inner = share_type(inner)?;
+        return Some(syn::parse_quote!{ ::std::option::Option<#inner> });
+    }
+    if let Some(inner) = strip_vec(ty) {
+        let inner = share_type(inner)?;
+        return Some(syn::parse_quote!{ ::std::vec::Vec<#inner> });
+    }
+    if strip_wrapper(ty, &["Arc", "Rc"]).is_some() {
+        return None;
+    }
+    let inner = strip_wrapper(ty, &["Box"]).unwrap_or(ty);
+    Some(syn::parse_quote!{ ::std::sync::Arc<#inner> })
+}
+
+// The type a field marked arc is converted from, which is the field type
+//  without the Arc, looking through Option and Vec. Trait objects are
+//  taken boxed, as they can't be passed by value.
+fn unshared_type(ty: &syn::Type) -> syn::Type {
+    if let Some(inner) = strip_option(ty) {
+        let inner = unshared_type(inner);
+        return syn::parse_quote!{ ::std::option::Option<#inner> };
+    }
+    if let Some(inner) = strip_vec(ty) {
+        let inner = unshared_type(inner);
+        return syn::parse_quote!{ ::std::vec::Vec<#inner> };
+    }
+    match strip_wrapper(ty, &["Arc"]) {
+        Some(inner @ syn::Type::TraitObject(_)) => syn::parse_quote!{ ::std::boxed::Box<#inner> },
+        Some(inner) => inner.clone(),
+        None => ty.clone(),
+    }
+}
+
+// The type a field marked boxed is converted from, which is the field type
+//  without the Box, looking through Option and Vec. Trait objects stay
+//  boxed, as they can't be passed by value.
+fn unboxed_type(ty: &syn::Type) -> syn::Type {
+    if let Some(inner) = strip_option(ty) {
+        let inner = unboxed_type(inner);
+        return syn::parse_quote!{ ::std::option::Option<#inner> };
+    }
+    if let Some(inner) = strip_vec(ty) {
+        let inner = unboxed_type(inner);
+        return syn::parse_quote!{ ::std::vec::Vec<#inner> };
+    }
+    match strip_wrapper(ty, &["Box"]) {
+        Some(syn::Type::TraitObject(_)) | None => ty.clone(),
+        Some(inner) => inner.clone(),
+    }
+}
+
+// Generate an expression converting a value of the unshared type of a
+//  field marked arc into the field type, which also converts the unboxed
+//  type of a field marked boxed
+fn share_expr(ty: &syn::Type, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    if let Some(inner) = strip_option(ty) {
+        let inner = share_expr(inner, quote!{ e });
+        quote!{ #value.map(|e| #inner) }
+    } else if let Some(inner) = strip_vec(ty) {
+        let inner = share_expr(inner, quote!{ e });
+        quote!{ #value.into_iter().map(|e| #inner).collect() }
+    } else {
+        quote!{ ::std::convert::From::from(#value) }
+    }
+}
+
+// Detect a (boxed) reference to the error type itself
+fn is_self_type(ty: &syn::Type, error_ident: &syn::Ident) -> bool {
+    let ty = strip_pointer(ty).unwrap_or(ty);
+    if let syn::Type::Path(path) = ty {
+        return path.qself.is_none() && path.path.leading_colon.is_none() && path.path.segments.len() == 1
+            && (path.path.segments[0].ident == "Self" || path.path.segments[0].ident == *error_ident);
+    }
+
+    false
+}
+
+// Configuration of which types are treated as errors, as set on the enum
+#[derive(Clone, Default)]
+struct ErrorHeuristic {
+    // Types treated as errors regardless of their name
+    error_types: Vec<syn::Path>,
+    // Name suffixes marking a type as an error, replacing the default
+    //  of types named Error
+    suffixes: Option<Vec<String>>,
+    // Whether to look at type names at all
+    disabled: bool,
+}
+
+impl ErrorHeuristic {
+    // Whether a type name marks the type as an error, so that it is
+    //  returned from source and gets From derived
+    fn is_error_name(&self, ident: &syn::Ident) -> bool {
+        match &self.suffixes {
+            _ if self.disabled => false,
+            Some(suffixes) => suffixes.iter().any(|suffix| ident.to_string().ends_with(suffix.as_str())),
+            None => ident == "Error",
+        }
+    }
+
+    // Whether a type name looks like an error, such as ParseIntError
+    fn is_error_like_name(&self, ident: &syn::Ident) -> bool {
+        match &self.suffixes {
+            _ if self.disabled => false,
+            Some(suffixes) => suffixes.iter().any(|suffix| ident.to_string().ends_with(suffix.as_str())),
+            None => ident.to_string().ends_with("Error"),
+        }
+    }
+
+    // Whether a type is one of the listed error types. Listed paths match
+    //  the trailing segments of the type, so MyFailure matches crate::MyFailure
+    fn is_listed(&self, path: &syn::Path) -> bool {
+        self.error_types.iter().any(|listed| {
+            listed.segments.len() <= path.segments.len()
+                && listed.segments.iter().rev().zip(path.segments.iter().rev()).all(|(a, b)| a.ident == b.ident)
+        })
+    }
+}
+
+// Infer whether wrapped type is an error
+//  by applying a name based heuristic (type path
+//  last segment is Error, looking through Vec, Box,
+//  Arc and Rc, or a trait object of Error), or
+//  by it being listed in error_types
+fn infer_is_error(ty: &syn::Type, heuristic: &ErrorHeuristic) -> bool {
+    let ty = strip_vec(ty).unwrap_or(ty);
+    let ty = strip_pointer(ty).unwrap_or(ty);
+
+    if is_report_type(ty) {
+        return true;
+    }
+
+    if let syn::Type::Path(path) = ty {
+        if path.path.segments.is_empty() {
+            return false;
+        }
+        if path.qself.is_none() && heuristic.is_listed(&path.path) {
+            return true;
+        }
+        if heuristic.is_error_name(&path.path.segments.last().unwrap().ident) {
+            return true;
+        }
+    }
+
+    if let syn::Type::TraitObject(object) = ty {
+        return object.bounds.iter().any(|bound| match bound {
+            syn::TypeParamBound::Trait(bound) => bound.path.segments.last().map_or(false, |s| s.ident == "Error"),
+            _ => false,
+        });
+    }
+
+    false
+}
+
+// Determine whether a type is named like an error, such as ParseIntError,
+//  which is used to pick the source among multiple fields
+fn infer_is_error_named(ty: &syn::Type, heuristic: &ErrorHeuristic) -> bool {
+    let ty = strip_pointer(ty).unwrap_or(ty);
+    let name_is_error = |path: &syn::Path| path.segments.last().map_or(false, |s| heuristic.is_error_like_name(&s.ident));
+    match ty {
+        syn::Type::Path(path) => name_is_error(&path.path),
+        syn::Type::TraitObject(object) => object.bounds.iter().any(|bound| match bound {
+            syn::TypeParamBound::Trait(bound) => name_is_error(&bound.path),
+            _ => false,
+        }),
+        _ => false,
+    }
+}
+
+// Determine whether a type mentions any of the enum's type parameters
+fn uses_type_params(ty: &syn::Type, generics: &syn::Generics) -> bool {
+    fn walk(tokens: proc_macro2::TokenStream, params: &[&syn::Ident]) -> bool {
+        tokens.into_iter().any(|token| match token {
+            proc_macro2::TokenTree::Ident(ident) => params.contains(&&ident),
+            proc_macro2::TokenTree::Group(group) => walk(group.stream(), params),
+            _ => false,
+        })
+    }
+
+    let params: Vec<_> = generics.type_params().map(|param| &param.ident).collect();
+    !params.is_empty() && walk(ty.to_token_stream(), &params)
+}
+
+// The name binding field i of a variant in generated code. Patterns resolve
+//  names of constants in scope, so these are prefixed to avoid picking up
+//  any the user has
+fn field_binding(i: usize) -> syn::Ident {
+    format_ident!("autoerror_f{}", i)
+}
+
+// Convert a variant name to snake case, for use in generated method names
+fn to_snake_case(ident: &syn::Ident) -> String {
+    let name = ident.unraw().to_string();
+    let chars: Vec<_> = name.chars().collect();
+    let mut result = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            // Start a new word on a lower to upper transition, or at the
+            //  last capital of an acronym (HTTPError -> http_error)
+            let prev_lower = i > 0 && (chars[i-1].is_lowercase() || chars[i-1].is_numeric());
+            let next_lower = i > 0 && chars[i-1].is_uppercase() && chars.get(i+1).map_or(false, |c| c.is_lowercase());
+            if !result.is_empty() && !result.ends_with('_') && (prev_lower || next_lower) {
+                result.push('_');
             }
+            result.extend(c.to_lowercase());
+        } else {
+            result.push(*c);
         }
     }
+    result
+}
+
+// The keywords that need to be written as raw identifiers, in any edition
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if",
+    "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override",
+    "priv", "pub", "ref", "return", "static", "struct", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
 
-    if result.err && result.variant.fields.len() != 1 {
-        return Err(TokenStream::from(syn::Error::new_spanned(variant, "Wrapped errors should have exactly 1 argument").to_compile_error()));
+// Convert a variant name to a snake case identifier, which is made raw when
+//  it is a keyword (Type -> r#type), or suffixed with an underscore when it
+//  is one that can't be raw (Crate -> crate_)
+fn to_snake_ident(ident: &syn::Ident) -> syn::Ident {
+    let name = to_snake_case(ident);
+    match name.as_str() {
+        "self" | "super" | "crate" => syn::Ident::new(&format!("{}_", name), ident.span()),
+        _ if KEYWORDS.contains(&name.as_str()) => syn::Ident::new_raw(&name, ident.span()),
+        _ => syn::Ident::new(&name, ident.span()),
     }
+}
 
-    if result.make_from && result.variant.fields.len() != 1 {
-        return Err(TokenStream::from(syn::Error::new_spanned(variant, "Can only derive from for variants with 1 field").to_compile_error()));
+// Turn a variant name into a human readable title (UserNotFound -> User not found)
+fn to_title(ident: &syn::Ident) -> String {
+    let name = to_snake_case(ident).replace('_', " ");
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => name,
     }
+}
 
-    Ok(result)
+// Auto-generate a basic format string for a variant, leaving out the
+//  fields capturing a backtrace or location
+fn infer_format_str(variant: &syn::Variant, captured: &[usize]) -> String {
+    let mut result = "".to_string();
+    let mut first = true;
+    for _ in (0..variant.fields.len()).filter(|i| !captured.contains(i)) {
+        if !first {
+            result += " ";
+        }
+        first = false;
+        result += "{}";
+    }
+    result
 }
 
-/// Derive basic error type infrastruture for enum types.
-///
-/// Supports unnamed and unit enum variants, and uses the type definition
-///  to derive `std::fmt::Display` and `std::error:Error` for the error type,
-///  as well as `std::from::From<T>` for any unnamed variant with one parameter
-///  inferred to be an error type (currently determined by whether it's type
-///  name is Error).
-///
-/// Default behaviour can be overridden with the auto_error attribute
-///  - format_str takes a string which becomes the format string for that
-///    variant
-///  - make_from forces derivation of std::from::From when set to true
-///  - err forces the std::error::Error implementation to return the inner
-///    type during calls to source, or in other words to treat the inner
-///    type as an error type.
-///
-/// From derivation and source returning work only for variants with a single field.
-///
-/// # Example
-///
-/// ```
-/// #[derive(AutoError)]
-/// use autoerror::AutoError;
-///
-/// enum Error {
-///     #[auto_error(format_str="Document not found")]
-///     NotFound,
-///     IO(std::io::Error),
-///     #[auto_error(make_from=true)]
-///     Other(String),
-/// }
-/// ```
-#[proc_macro_derive(AutoError, attributes(auto_error))]
-pub fn derive(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
+// Rewrite the positional placeholders in a format string to refer to the
+//  bindings of the variant's fields by name, so fields not mentioned in the
+//  format string need not be passed to it. Returns the rewritten format
+//  string and which fields it uses, or None for format strings that can't
+//  be rewritten (those taking precision from the arguments through .*).
+//  Positions only count the payload fields, skipping captured ones.
+fn rewrite_format_str(format_str: &str, payload: &[usize], field_count: usize) -> Option<(String, Vec<bool>)> {
+    let mut result = String::new();
+    let mut used = vec![false; field_count];
+    let mut next_positional = 0;
+    let mut chars = format_str.chars().peekable();
 
-    let enumdecl = if let syn::Data::Enum(e) = input.data {
-        e
-    } else {
-        return TokenStream::from(syn::Error::new_spanned(&input.ident, "AutoError only supports enums").to_compile_error());
+    // Refer to the payload field at position n by name, recording its use
+    let field = |n: usize, used: &mut [bool]| -> String {
+        let i = payload.get(n).copied().unwrap_or(n);
+        if let Some(u) = used.get_mut(i) {
+            *u = true;
+        }
+        field_binding(i).to_string()
     };
 
-    let error_ident = input.ident;
-    let error_variants: Result<Vec<_>, TokenStream> = enumdecl.variants.iter().map(|v| parse_variant(v)).collect();
-    let error_variants = match error_variants {
-        Ok(v) => v,
-        Err(e) => {return e}
-    };
+    while let Some(c) = chars.next() {
+        if c == '}' {
+            result.push(c);
+            if chars.peek() == Some(&'}') {
+                result.push(chars.next().unwrap());
+            }
+            continue;
+        }
+        if c != '{' {
+            result.push(c);
+            continue;
+        }
+        if chars.peek() == Some(&'{') {
+            result.push_str("{{");
+            chars.next();
+            continue;
+        }
 
-    let from_impls = error_variants.iter().map(|var| {
-        if !var.make_from {
+        let mut placeholder = String::new();
+        for c in chars.by_ref() {
+            if c == '}' {
+                break;
+            }
+            placeholder.push(c);
+        }
+        let (arg, spec) = match placeholder.find(':') {
+            Some(i) => placeholder.split_at(i),
+            None => (placeholder.as_str(), ""),
+        };
+        if spec.contains(".*") {
             return None;
         }
 
-        let sourcetype = &var.variant.fields.iter().next().unwrap().ty;
-        let curvar = &var.variant.ident;
+        result.push('{');
+        let arg = arg.trim();
+        if arg.is_empty() {
+            result.push_str(&field(next_positional, &mut used));
+            next_positional += 1;
+        } else if let Ok(i) = arg.parse::<usize>() {
+            result.push_str(&field(i, &mut used));
+        } else {
+            result.push_str(arg);
+        }
 
-        Some(quote!{
-            impl ::std::convert::From<#sourcetype> for #error_ident {
-                fn from (e: #sourcetype) -> Self {
-                    Self::#curvar(e)
-                }
+        // Width and precision can refer to positional arguments as N$
+        let mut digits = String::new();
+        for c in spec.chars() {
+            if c.is_ascii_digit() {
+                digits.push(c);
+                continue;
             }
-        })
-    });
+            if c == '$' && !digits.is_empty() {
+                result.push_str(&field(digits.parse().unwrap(), &mut used));
+            } else {
+                result.push_str(&digits);
+            }
+            digits.clear();
+            result.push(c);
+        }
+        result.push_str(&digits);
+        result.push('}');
+    }
 
-    let display_branches = error_variants.iter().map(|var| {
-        let format_str = &var.format_str;
-        let curvar = &var.variant.ident;
-        let params: Vec<_> = var.variant.fields.iter().enumerate().map(|(i, _field)| {
-            format_ident!("f{}", i)
-        }).collect();
-        match var.variant.fields {
-            syn::Fields::Unnamed(_) => quote!{
-                Self::#curvar(#(#params),*) => f.write_fmt(format_args!(#format_str #(,#params)*)),
-            },
-            syn::Fields::Unit => quote!{
-                Self::#curvar => f.write_fmt(format_args!(#format_str)),
-            },
-            _ => panic!("Internal error (AutoError)")
+    Some((result, used))
+}
+
+// Check the placeholders in a format string refer to payload fields of the
+//  variant, as format_args! would otherwise report errors in generated code
+fn check_format_str(format_str: &str, payload_count: usize) -> Result<(), String> {
+    let check_index = |i: usize| {
+        if i < payload_count {
+            Ok(())
+        } else if payload_count == 1 {
+            Err(format!("Format string refers to field {}, but the variant has 1 field", i))
+        } else {
+            Err(format!("Format string refers to field {}, but the variant has {} fields", i, payload_count))
         }
-        
-    });
+    };
 
-    let source_branches = error_variants.iter().map(|var| {
-        if !var.err {
-            return None;
+    let mut next_positional = 0;
+    let mut chars = format_str.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '}' {
+            if chars.next() != Some('}') {
+                return Err("Invalid format string, unmatched `}`".to_string());
+            }
+            continue;
+        }
+        if c != '{' {
+            continue;
+        }
+        if chars.peek() == Some(&'{') {
+            chars.next();
+            continue;
         }
-        let curvar = &var.variant.ident;
-        Some(quote!{
-            Self::#curvar(e) => Some(e),
-        })
-    });
 
-    TokenStream::from(quote! {
-        #(#from_impls)*
+        let mut placeholder = String::new();
+        let mut closed = false;
+        for c in chars.by_ref() {
+            if c == '}' {
+                closed = true;
+                break;
+            }
+            placeholder.push(c);
+        }
+        if !closed {
+            return Err("Invalid format string, unmatched `{`".to_string());
+        }
+        let (arg, spec) = match placeholder.find(':') {
+            Some(i) => placeholder.split_at(i),
+            None => (placeholder.as_str(), ""),
+        };
 
-        impl ::std::fmt::Display for #error_ident {
-            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
-                match self {
-                    #(#display_branches)*
-                }
+        // Precision given as .* takes the next positional argument first
+        if spec.contains(".*") {
+            check_index(next_positional)?;
+            next_positional += 1;
+        }
+        let arg = arg.trim();
+        if arg.is_empty() {
+            check_index(next_positional)?;
+            next_positional += 1;
+        } else if let Ok(i) = arg.parse::<usize>() {
+            check_index(i)?;
+        } else {
+            return Err(format!("Unknown field `{}` in format string, fields are referred to by position", arg));
+        }
+
+        let mut digits = String::new();
+        for c in spec.chars() {
+            if c.is_ascii_digit() {
+                digits.push(c);
+                continue;
+            }
+            if c == '$' && !digits.is_empty() {
+                check_index(digits.parse().unwrap())?;
             }
+            digits.clear();
         }
+    }
 
-        impl ::std::error::Error for #error_ident {
-            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-                match self {
-                    #(#source_branches)*
-                    _ => None,
-                }
+    Ok(())
+}
+
+// A placeholder value for a field of the given type in generated tests,
+//  together with how it is displayed when that is known. Returns None for
+//  types no value can be made up for.
+fn placeholder_value(ty: &syn::Type, n: usize) -> Option<(proc_macro2::TokenStream, Option<String>)> {
+    if let Some(inner) = strip_option(ty) {
+        let (value, _) = placeholder_value(inner, n)?;
+        return Some((quote!{ ::std::option::Option::Some(#value) }, None));
+    }
+    if let Some(inner) = strip_wrapper(ty, &["Box"]) {
+        // Boxed errors can be made from a message
+        if let syn::Type::TraitObject(object) = inner {
+            let is_error = object.bounds.iter().any(|bound| matches!(bound, syn::TypeParamBound::Trait(t) if t.path.segments.last().map_or(false, |s| s.ident == "Error")));
+            if !is_error {
+                return None;
             }
+            return Some((quote!{ ::std::convert::From::from("source") }, Some("source".to_string())));
+        }
+        let (value, display) = placeholder_value(inner, n)?;
+        return Some((quote!{ ::std::boxed::Box::new(#value) }, display));
+    }
+    if let Some(inner) = strip_wrapper(ty, &["Arc"]) {
+        let (value, display) = placeholder_value(inner, n)?;
+        return Some((quote!{ ::std::sync::Arc::new(#value) }, display));
+    }
+    if let Some(inner) = strip_wrapper(ty, &["Rc"]) {
+        let (value, display) = placeholder_value(inner, n)?;
+        return Some((quote!{ ::std::rc::Rc::new(#value) }, display));
+    }
+
+    let text = format!("field{}", n);
+    let path = match ty {
+        syn::Type::Reference(r) => match &*r.elem {
+            syn::Type::Path(path) if path.path.is_ident("str") => return Some((quote!{ #text }, Some(text))),
+            _ => return None,
+        },
+        syn::Type::Path(path) if path.qself.is_none() => path,
+        _ => return None,
+    };
+    let segments: Vec<_> = path.path.segments.iter().map(|s| s.ident.to_string()).collect();
+    let number = (n + 1).to_string();
+    match segments.last()?.as_str() {
+        "String" => Some((quote!{ ::std::string::String::from(#text) }, Some(text))),
+        "bool" => Some((quote!{ true }, Some("true".to_string()))),
+        "char" => {
+            let c = (b'a' + (n % 26) as u8) as char;
+            Some((quote!{ #c }, Some(c.to_string())))
+        }
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128" | "usize" => {
+            let lit = syn::LitInt::new(&number, proc_macro2::Span::call_site());
+            Some((quote!{ #lit }, Some(number)))
+        }
+        "f32" | "f64" => {
+            let lit = syn::LitFloat::new(&format!("{}.5", number), proc_macro2::Span::call_site());
+            Some((quote!{ #lit }, Some(format!("{}.5", number))))
+        }
+        "Error" if segments.ends_with(&["io".to_string(), "Error".to_string()]) => {
+            Some((quote!{ ::std::io::Error::new(::std::io::ErrorKind::Other, "source") }, Some("source".to_string())))
+        }
+        _ => None,
+    }
+}
+
+// The message a plain format string gives for the displayed payload fields,
+//  or None when some field used is not known how it's displayed
+fn expected_message(format_str: &str, fields: &[Option<String>]) -> Option<String> {
+    let mut result = String::new();
+    let mut next_positional = 0;
+    let mut chars = format_str.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '}' {
+            chars.next();
+            result.push(c);
+            continue;
+        }
+        if c != '{' {
+            result.push(c);
+            continue;
+        }
+        if chars.peek() == Some(&'{') {
+            chars.next();
+            result.push(c);
+            continue;
+        }
+
+        let mut placeholder = String::new();
+        for c in chars.by_ref() {
+            if c == '}' {
+                break;
+            }
+            placeholder.push(c);
+        }
+        let n = match placeholder.trim() {
+            "" => {
+                next_positional += 1;
+                next_positional - 1
+            }
+            n => n.parse().ok()?,
+        };
+        result.push_str(fields.get(n)?.as_ref()?);
+    }
+    Some(result)
+}
+
+// Whether a format string only has plain placeholders, without format specs,
+//  so it can be formatted by the runtime crate or from the format table
+fn is_plain_format_str(format_str: &str) -> bool {
+    let mut chars = format_str.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '{' {
+            continue;
+        }
+        if chars.peek() == Some(&'{') {
+            chars.next();
+            continue;
+        }
+        for c in chars.by_ref() {
+            if c == '}' {
+                break;
+            }
+            if c == ':' {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+// Whether the variant has a plain format string and no Vec fields, which
+//  need joining, so it can be formatted by the runtime crate or from the
+//  format table. Variants delegating to a field are left to format it
+//  themselves.
+fn is_plain_variant(var: &ErrorVariant) -> bool {
+    let has_vec = payload_fields(var).into_iter().any(|i| strip_vec(&var.variant.fields.iter().nth(i).unwrap().ty).is_some());
+    is_plain_format_str(&var.format_str) && !has_vec && appended_source(var).is_none() && delegated_field(var).is_none() && var.validation_fields.is_empty()
+        && var.config_path_field.is_none() && var.config_key_field.is_none()
+}
+
+// The field a variant delegates its message to, when its format string
+//  only displays that field, so the flags given to the formatter reach it
+fn delegated_field(var: &ErrorVariant) -> Option<usize> {
+    if var.format_str != "{}" && var.format_str != "{0}" {
+        return None;
+    }
+    let i = *payload_fields(var).first()?;
+    if strip_vec(&var.variant.fields.iter().nth(i).unwrap().ty).is_some() {
+        return None;
+    }
+    Some(i)
+}
+
+// Whether field i of the variant is a Vec displayed by joining its
+//  elements, which aggregates of errors are, and other Vec fields only
+//  when the variant sets a separator
+fn is_joined(var: &ErrorVariant, i: usize) -> bool {
+    let ty = &var.variant.fields.iter().nth(i).unwrap().ty;
+    strip_vec(ty).is_some() && ((var.err && var.source_field == Some(i)) || var.separator.is_some())
+}
+
+// The separator placed between the elements of joined Vec fields
+fn separator<'a>(var: &'a ErrorVariant) -> &'a str {
+    var.separator.as_deref().unwrap_or(", ")
+}
+
+// The source field appended to the message of a variant, if it is set to
+//  do so and has a single source
+fn appended_source(var: &ErrorVariant) -> Option<usize> {
+    let source_field = var.source_field.filter(|_| var.err && var.chain_fmt == Some(true))?;
+    let ty = &var.variant.fields.iter().nth(source_field).unwrap().ty;
+    if strip_vec(ty).is_some() {
+        return None;
+    }
+    Some(source_field)
+}
+
+struct ErrorVariant<'a> {
+    err: bool,
+    source_as_ref: bool,
+    make_from: bool,
+    from_tuple: bool,
+    from_field: Option<usize>,
+    source_field: Option<usize>,
+    format_str: String,
+    separator: Option<String>,
+    whatever: bool,
+    code: Option<syn::LitStr>,
+    status: Option<u16>,
+    body: Option<String>,
+    grpc: Option<syn::Ident>,
+    problem_type: Option<String>,
+    title: Option<String>,
+    jsonrpc_code: Option<i64>,
+    exit_code: Option<u8>,
+    errno: Option<syn::Expr>,
+    py_exc: Option<syn::Path>,
+    napi_status: Option<syn::Ident>,
+    routes: Vec<(&'static Routing, syn::LitStr)>,
+    retryable: Option<bool>,
+    chain_fmt: Option<bool>,
+    severity: Option<syn::Ident>,
+    log_level: Option<syn::Ident>,
+    report: bool,
+    help: Option<String>,
+    url: Option<String>,
+    msg_key: Option<String>,
+    into: Option<syn::Expr>,
+    arg_names: Vec<(usize, String)>,
+    arc_fields: Vec<usize>,
+    boxed_fields: Vec<usize>,
+    backtrace_field: Option<usize>,
+    location_field: Option<usize>,
+    timestamp_field: Option<usize>,
+    instance_id_field: Option<(usize, Option<syn::Path>)>,
+    config_path_field: Option<usize>,
+    config_key_field: Option<usize>,
+    labels: Vec<(usize, Option<String>)>,
+    validation_fields: Vec<usize>,
+    source_code: Option<usize>,
+    deserialize: bool,
+    catch_all: bool,
+    from_poison: bool,
+    from_panic: bool,
+    context: bool,
+    hidden: bool,
+    cfgs: Vec<&'a syn::Attribute>,
+    deprecated: Vec<&'a syn::Attribute>,
+    superseded_by: Option<syn::Ident>,
+    variant: &'a syn::Variant,
+}
+
+// Whether the field captures a backtrace, location, timestamp or instance
+//  ID, or holds the configuration file or key, rather than being part of
+//  the payload of the variant
+fn is_captured(var: &ErrorVariant, i: usize) -> bool {
+    var.backtrace_field == Some(i) || var.location_field == Some(i) || var.timestamp_field == Some(i) || var.instance_id_field.as_ref().map_or(false, |(field, _)| *field == i)
+        || var.config_path_field == Some(i) || var.config_key_field == Some(i)
+}
+
+// The fields making up the payload of the variant
+fn payload_fields(var: &ErrorVariant) -> Vec<usize> {
+    (0..var.variant.fields.len()).filter(|i| !is_captured(var, *i)).collect()
+}
+
+// The value of a field when constructing a variant without it given,
+//  capturing the backtrace, location or timestamp, or generating the
+//  instance ID for fields marked so, and using the default value otherwise
+fn field_value(var: &ErrorVariant, i: usize) -> proc_macro2::TokenStream {
+    if var.backtrace_field == Some(i) {
+        quote!{ ::std::convert::From::from(::std::backtrace::Backtrace::capture()) }
+    } else if var.location_field == Some(i) {
+        quote!{ ::std::panic::Location::caller() }
+    } else if var.timestamp_field == Some(i) {
+        let ty = &var.variant.fields.iter().nth(i).unwrap().ty;
+        quote!{ <#ty>::now() }
+    } else if let Some((_, generator)) = var.instance_id_field.as_ref().filter(|(field, _)| *field == i) {
+        let ty = &var.variant.fields.iter().nth(i).unwrap().ty;
+        match generator {
+            Some(generator) => quote!{ #generator() },
+            None => quote!{ <#ty>::new() },
+        }
+    } else {
+        quote!{ ::std::default::Default::default() }
+    }
+}
+
+// Render a type for use in diagnostics, leaving out the spaces between
+//  tokens except those separating words
+fn type_name(ty: &syn::Type) -> String {
+    let tokens = ty.to_token_stream().to_string();
+    let chars: Vec<_> = tokens.chars().collect();
+    let is_word = |c: Option<&char>| c.map_or(false, |c| c.is_alphanumeric() || *c == '_');
+    chars.iter().enumerate().filter(|(i, c)| {
+        **c != ' ' || (is_word(chars.get(i.wrapping_sub(1))) && is_word(chars.get(i + 1)))
+    }).map(|(_, c)| c).collect()
+}
+
+// The types a variant is converted from by its From implementations: the
+//  marked (or only) field, or the tuple of all fields not captured. Cow
+//  messages are converted from both static strings and owned ones.
+fn from_source_types(var: &ErrorVariant) -> Vec<syn::Type> {
+    let field_type = |i: usize| {
+        let ty = &var.variant.fields.iter().nth(i).unwrap().ty;
+        if var.arc_fields.contains(&i) {
+            unshared_type(ty)
+        } else if var.boxed_fields.contains(&i) {
+            unboxed_type(ty)
+        } else {
+            ty.clone()
+        }
+    };
+    if var.make_from {
+        let ty = field_type(var.from_field.unwrap_or(0));
+        if is_cow_str(&ty) {
+            vec![syn::parse_quote!{ &'static str }, syn::parse_quote!{ ::std::string::String }]
+        } else {
+            vec![ty]
+        }
+    } else if var.from_tuple {
+        let types = payload_fields(var).into_iter().map(field_type);
+        vec![syn::parse_quote!{ (#(#types,)*) }]
+    } else {
+        vec![]
+    }
+}
+
+// Functions constructing a variant with a location field are marked
+//  #[track_caller], so the location is that of their caller
+fn location_track_caller(var: &ErrorVariant) -> Option<proc_macro2::TokenStream> {
+    if var.location_field.is_some() {
+        Some(quote!{ #[track_caller] })
+    } else {
+        None
+    }
+}
+
+// Items generated for a deprecated variant are deprecated as well, as are
+//  those of a variant superseded by another one
+fn deprecation(var: &ErrorVariant, error_ident: &syn::Ident) -> proc_macro2::TokenStream {
+    let deprecated = &var.deprecated;
+    match &var.superseded_by {
+        Some(new) if deprecated.is_empty() => {
+            let note = format!("Superseded by {}::{}", error_ident, new);
+            quote!{ #[deprecated(note = #note)] }
+        },
+        _ => quote!{ #(#deprecated)* },
+    }
+}
+
+struct EnumAttrs {
+    accessors: bool,
+    constructors: bool,
+    builders: bool,
+    context_selectors: bool,
+    track_caller: bool,
+    display_chain: bool,
+    debug: bool,
+    kind: Option<syn::Ident>,
+    eq: bool,
+    hash: bool,
+    test_helpers: bool,
+    macros: bool,
+    result_alias: Option<syn::Ident>,
+    into: Option<syn::Type>,
+    into_variants: bool,
+    catalog: Option<syn::Ident>,
+    actix: bool,
+    warp: bool,
+    rocket: bool,
+    tonic: bool,
+    pyo3: bool,
+    napi: bool,
+    log: bool,
+    emit_log: bool,
+    log_kv: bool,
+    slog: bool,
+    valuable: bool,
+    tracing: bool,
+    otel: bool,
+    metrics: Option<String>,
+    sentry: bool,
+    miette: bool,
+    url: Option<syn::LitStr>,
+    localized: bool,
+    compact: bool,
+    format_table: bool,
+    no_alloc: bool,
+    heapless: bool,
+    validator: bool,
+    generate_tests: bool,
+    chain_fmt: bool,
+    problem_details: Option<syn::Ident>,
+    serde: bool,
+    jsonrpc: Option<syn::Ident>,
+    async_graphql: bool,
+    deserialize: bool,
+    schemars: bool,
+    arbitrary: bool,
+    termination: Option<syn::Ident>,
+    ffi: Option<syn::Ident>,
+    last_error: Option<String>,
+    msrv: Option<(u64, u64)>,
+    no_bound: Vec<syn::Ident>,
+    bound: Option<Vec<syn::WherePredicate>>,
+    heuristic: ErrorHeuristic,
+}
+
+// Whether the attributes contain #[doc(hidden)], as used for
+//  forward compatibility variants such as __NonExhaustive
+fn is_doc_hidden(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().filter(|attr| attr.path.is_ident("doc")).any(|attr| {
+        match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => list.nested.iter().any(|arg| matches!(arg, syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("hidden"))),
+            _ => false,
+        }
+    })
+}
+
+// Whether the attributes derive the trait with the given name
+fn derives(attrs: &[syn::Attribute], name: &str) -> bool {
+    attrs.iter().filter(|attr| attr.path.is_ident("derive")).any(|attr| {
+        match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => list.nested.iter().any(|arg| matches!(arg, syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.segments.last().map_or(false, |s| s.ident == name))),
+            _ => false,
+        }
+    })
+}
+
+// Collect the doc comment lines from the given attributes
+fn doc_text(attrs: &[syn::Attribute]) -> String {
+    let lines: Vec<_> = attrs.iter().filter(|attr| attr.path.is_ident("doc")).filter_map(|attr| {
+        match attr.parse_meta() {
+            Ok(syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Str(v), .. })) => Some(v.value().trim().to_string()),
+            _ => None,
+        }
+    }).collect();
+    lines.join("\n")
+}
+
+// The name of an attribute argument, which diagnostics about the argument
+//  point at, or the argument itself when it has no name
+fn arg_name(arg: &syn::NestedMeta) -> proc_macro2::TokenStream {
+    match arg {
+        syn::NestedMeta::Meta(meta) => meta.path().to_token_stream(),
+        syn::NestedMeta::Lit(lit) => lit.to_token_stream(),
+    }
+}
+
+// Find the attribute argument with the given name, if present
+fn find_arg<'a>(args: &'a syn::punctuated::Punctuated<syn::NestedMeta, syn::Token![,]>, name: &str) -> Option<&'a syn::NestedMeta> {
+    args.iter().find(|arg| matches!(arg, syn::NestedMeta::Meta(meta) if meta.path().is_ident(name)))
+}
+
+// Parse the auto_error attribute on the enum itself
+fn parse_enum_attrs(attrs: &[syn::Attribute], error_ident: &syn::Ident) -> Result<EnumAttrs, TokenStream> {
+    let mut result = EnumAttrs {
+        accessors: false,
+        constructors: false,
+        builders: false,
+        context_selectors: false,
+        track_caller: false,
+        display_chain: false,
+        debug: false,
+        kind: None,
+        eq: false,
+        hash: false,
+        test_helpers: false,
+        macros: false,
+        result_alias: None,
+        into: None,
+        into_variants: false,
+        catalog: None,
+        actix: false,
+        warp: false,
+        rocket: false,
+        tonic: false,
+        pyo3: false,
+        napi: false,
+        log: false,
+        emit_log: false,
+        log_kv: false,
+        slog: false,
+        valuable: false,
+        tracing: false,
+        otel: false,
+        metrics: None,
+        sentry: false,
+        miette: false,
+        localized: false,
+        compact: false,
+        format_table: false,
+        no_alloc: false,
+        heapless: false,
+        validator: false,
+        generate_tests: false,
+        chain_fmt: false,
+        url: None,
+        problem_details: None,
+        serde: false,
+        jsonrpc: None,
+        async_graphql: false,
+        deserialize: false,
+        schemars: false,
+        arbitrary: false,
+        termination: None,
+        ffi: None,
+        last_error: None,
+        msrv: None,
+        no_bound: vec![],
+        bound: None,
+        heuristic: ErrorHeuristic::default(),
+    };
+
+    let mut attr: Option<_> = None;
+    for attr_cand in attrs.iter() {
+        if attr_cand.path.is_ident("auto_error") {
+            if attr.is_some() {
+                return Err(TokenStream::from(syn::Error::new_spanned(attr_cand, "Duplicate occurence of auto_error attribute").to_compile_error()));
+            }
+            attr = Some(attr_cand);
+        }
+    }
+
+    if let Some(attr) = attr {
+        let meta = attr.parse_meta().map_err(|e| e.to_compile_error())?;
+        let meta = match meta {
+            syn::Meta::List(list) => list,
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&meta, "Incorrect auto_error arguments").to_compile_error())); },
+        };
+
+        let mut errors = TokenStream::new();
+        for arg in meta.nested.iter() {
+            if let Err(e) = parse_enum_arg(&mut result, arg, error_ident) {
+                errors.extend(e);
+            }
+        }
+
+        if result.last_error.is_some() && result.ffi.is_none() {
+            errors.extend(TokenStream::from(syn::Error::new_spanned(find_arg(&meta.nested, "last_error"), "The last_error helpers require ffi to be enabled as well").to_compile_error()));
+        }
+        if result.test_helpers && result.kind.is_none() {
+            errors.extend(TokenStream::from(syn::Error::new_spanned(find_arg(&meta.nested, "test_helpers"), "The test_helpers require kind to be enabled as well").to_compile_error()));
+        }
+        if result.schemars && !result.serde {
+            errors.extend(TokenStream::from(syn::Error::new_spanned(find_arg(&meta.nested, "schemars"), "The schemars integration requires serde to be enabled as well").to_compile_error()));
+        }
+        if result.format_table && result.compact {
+            errors.extend(TokenStream::from(syn::Error::new_spanned(find_arg(&meta.nested, "format_table"), "The format_table and compact modes can't be combined").to_compile_error()));
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+    }
+
+    Ok(result)
+}
+
+// The ecosystem integrations that can be listed in integrations(...), each
+//  requiring the cargo feature of autoerror it is named after
+const INTEGRATIONS: &[&str] = &[
+    "actix", "warp", "rocket", "tonic", "pyo3", "napi", "log", "emit_log", "log_kv", "slog", "valuable",
+    "tracing", "otel", "metrics", "sentry", "miette", "async_graphql", "serde", "deserialize", "schemars",
+    "arbitrary", "heapless", "validator",
+];
+
+// Parse a single auto_error argument of the enum
+fn parse_enum_arg(result: &mut EnumAttrs, arg: &syn::NestedMeta, error_ident: &syn::Ident) -> Result<(), TokenStream> {
+    match arg {
+        syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("integrations") => {
+            let mut errors = TokenStream::new();
+            for integration in list.nested.iter() {
+                let known = match integration {
+                    syn::NestedMeta::Meta(meta) => INTEGRATIONS.iter().any(|name| meta.path().is_ident(name)),
+                    _ => false,
+                };
+                if !known {
+                    let message = format!("Unknown integration, expected one of {}", INTEGRATIONS.join(", "));
+                    errors.extend(TokenStream::from(syn::Error::new_spanned(arg_name(integration), message).to_compile_error()));
+                } else if let Err(e) = parse_enum_arg(result, integration, error_ident) {
+                    errors.extend(e);
+                }
+            }
+            if !errors.is_empty() {
+                return Err(errors);
+            }
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("display_chain") => {
+            result.display_chain = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("accessors") => {
+            result.accessors = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("constructors") => {
+            result.constructors = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("builders") => {
+            result.builders = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("context_selectors") => {
+            result.context_selectors = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("track_caller") => {
+            result.track_caller = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("debug") => {
+            result.debug = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("eq") => {
+            result.eq = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("hash") => {
+            result.hash = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("test_helpers") => {
+            result.test_helpers = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("macros") => {
+            result.macros = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("kind") => {
+            result.kind = Some(format_ident!("{}Kind", error_ident));
+        }
+        syn::NestedMeta::Meta(syn::Meta::NameValue(arg)) if arg.path.is_ident("kind") => {
+            result.kind = match &arg.lit {
+                syn::Lit::Str(v) => Some(v.parse().map_err(|e| e.to_compile_error())?),
+                _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for kind, expected string").to_compile_error())); },
+            };
+        }
+        syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("error_types") => {
+            for ty in list.nested.iter() {
+                match ty {
+                    syn::NestedMeta::Meta(syn::Meta::Path(path)) => result.heuristic.error_types.push(path.clone()),
+                    _ => { return Err(TokenStream::from(syn::Error::new_spanned(ty, "Incorrect value for error_types, expected types").to_compile_error())); },
+                }
+            }
+        }
+        syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("error_suffixes") => {
+            let mut suffixes = vec![];
+            for suffix in list.nested.iter() {
+                match suffix {
+                    syn::NestedMeta::Lit(syn::Lit::Str(v)) if !v.value().is_empty() => suffixes.push(v.value()),
+                    _ => { return Err(TokenStream::from(syn::Error::new_spanned(suffix, "Incorrect value for error_suffixes, expected non-empty strings").to_compile_error())); },
+                }
+            }
+            result.heuristic.suffixes = Some(suffixes);
+        }
+        syn::NestedMeta::Meta(syn::Meta::NameValue(arg)) if arg.path.is_ident("heuristic") => {
+            result.heuristic.disabled = match &arg.lit {
+                syn::Lit::Bool(v) => !v.value,
+                _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for heuristic, expected bool").to_compile_error())); },
+            };
+        }
+        syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("no_bound") => {
+            for param in list.nested.iter() {
+                match param {
+                    syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.get_ident().is_some() => {
+                        result.no_bound.push(path.get_ident().unwrap().clone());
+                    }
+                    _ => { return Err(TokenStream::from(syn::Error::new_spanned(param, "Incorrect value for no_bound, expected type parameters").to_compile_error())); },
+                }
+            }
+        }
+        syn::NestedMeta::Meta(syn::Meta::NameValue(arg)) if arg.path.is_ident("bound") => {
+            result.bound = match &arg.lit {
+                syn::Lit::Str(v) => {
+                    let parser = syn::punctuated::Punctuated::<syn::WherePredicate, syn::Token![,]>::parse_terminated;
+                    Some(v.parse_with(parser).map_err(|e| e.to_compile_error())?.into_iter().collect())
+                }
+                _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for bound, expected string").to_compile_error())); },
+            };
+        }
+        syn::NestedMeta::Meta(syn::Meta::NameValue(arg)) if arg.path.is_ident("msrv") => {
+            let version = match &arg.lit {
+                syn::Lit::Str(v) => {
+                    let value = v.value();
+                    let mut parts = value.split('.').map(|part| part.parse::<u64>());
+                    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+                        (Some(Ok(major)), Some(Ok(minor)), None, None) | (Some(Ok(major)), Some(Ok(minor)), Some(Ok(_)), None) => Some((major, minor)),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            // autoerror itself needs 1.56, its rust-version, so the code it
+            //  generates is never built by an older compiler
+            result.msrv = match version {
+                Some(version) if version < (1, 56) => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "autoerror itself requires Rust 1.56, so the msrv can't be older").to_compile_error())); },
+                Some(version) => Some(version),
+                None => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for msrv, expected a Rust version such as \"1.56\"").to_compile_error())); },
+            };
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("actix") => {
+            if !cfg!(feature = "actix") {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "The actix integration requires the actix feature of autoerror").to_compile_error()));
+            }
+            result.actix = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("warp") => {
+            if !cfg!(feature = "warp") {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "The warp integration requires the warp feature of autoerror").to_compile_error()));
+            }
+            result.warp = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("rocket") => {
+            if !cfg!(feature = "rocket") {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "The rocket integration requires the rocket feature of autoerror").to_compile_error()));
+            }
+            result.rocket = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("pyo3") => {
+            if !cfg!(feature = "pyo3") {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "The pyo3 integration requires the pyo3 feature of autoerror").to_compile_error()));
+            }
+            result.pyo3 = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("napi") => {
+            if !cfg!(feature = "napi") {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "The napi integration requires the napi feature of autoerror").to_compile_error()));
+            }
+            result.napi = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("log") || path.is_ident("emit_log") || path.is_ident("log_kv") => {
+            if !cfg!(feature = "log") {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "The log integration requires the log feature of autoerror").to_compile_error()));
+            }
+            result.log = true;
+            result.emit_log |= path.is_ident("emit_log");
+            result.log_kv |= path.is_ident("log_kv");
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("slog") => {
+            if !cfg!(feature = "slog") {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "The slog integration requires the slog feature of autoerror").to_compile_error()));
+            }
+            result.slog = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("valuable") => {
+            if !cfg!(feature = "valuable") {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "The valuable integration requires the valuable feature of autoerror").to_compile_error()));
+            }
+            result.valuable = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("tracing") => {
+            if !cfg!(feature = "tracing") {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "The tracing integration requires the tracing feature of autoerror").to_compile_error()));
+            }
+            result.tracing = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("otel") => {
+            if !cfg!(feature = "otel") {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "The otel integration requires the otel feature of autoerror").to_compile_error()));
+            }
+            result.otel = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("metrics") => {
+            if !cfg!(feature = "metrics") {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "The metrics integration requires the metrics feature of autoerror").to_compile_error()));
+            }
+            result.metrics = Some(format!("{}_total", to_snake_case(error_ident)));
+        }
+        syn::NestedMeta::Meta(syn::Meta::NameValue(arg)) if arg.path.is_ident("metrics") => {
+            if !cfg!(feature = "metrics") {
+                return Err(TokenStream::from(syn::Error::new_spanned(&arg.path, "The metrics integration requires the metrics feature of autoerror").to_compile_error()));
+            }
+            result.metrics = match &arg.lit {
+                syn::Lit::Str(v) => Some(v.value()),
+                _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for metrics, expected string").to_compile_error())); },
+            };
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("sentry") => {
+            if !cfg!(feature = "sentry") {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "The sentry integration requires the sentry feature of autoerror").to_compile_error()));
+            }
+            result.sentry = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("localized") => {
+            result.localized = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("no_alloc") => {
+            result.no_alloc = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("heapless") => {
+            if !cfg!(feature = "heapless") {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "The heapless integration requires the heapless feature of autoerror").to_compile_error()));
+            }
+            result.no_alloc = true;
+            result.heapless = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("validator") => {
+            if !cfg!(feature = "validator") {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "The validator integration requires the validator feature of autoerror").to_compile_error()));
+            }
+            result.validator = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("compact") => {
+            if !cfg!(feature = "runtime") {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "The compact mode requires the runtime feature of autoerror").to_compile_error()));
+            }
+            result.compact = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("format_table") => {
+            if !cfg!(feature = "runtime") {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "The format_table mode requires the runtime feature of autoerror").to_compile_error()));
+            }
+            result.format_table = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("chain_fmt") => {
+            result.chain_fmt = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("generate_tests") => {
+            result.generate_tests = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("miette") => {
+            if !cfg!(feature = "miette") {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "The miette integration requires the miette feature of autoerror").to_compile_error()));
+            }
+            result.miette = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("tonic") => {
+            if !cfg!(feature = "tonic") {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "The tonic integration requires the tonic feature of autoerror").to_compile_error()));
+            }
+            result.tonic = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("async_graphql") => {
+            if !cfg!(feature = "async_graphql") {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "The async_graphql integration requires the async_graphql feature of autoerror").to_compile_error()));
+            }
+            result.async_graphql = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("serde") || path.is_ident("deserialize") => {
+            if !cfg!(feature = "serde") {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "The serde integration requires the serde feature of autoerror").to_compile_error()));
+            }
+            result.serde = true;
+            result.deserialize |= path.is_ident("deserialize");
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("schemars") => {
+            if !cfg!(feature = "schemars") {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "The schemars integration requires the schemars feature of autoerror").to_compile_error()));
+            }
+            result.schemars = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("arbitrary") => {
+            if !cfg!(feature = "arbitrary") {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "The arbitrary integration requires the arbitrary feature of autoerror").to_compile_error()));
+            }
+            result.arbitrary = true;
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("problem_details") => {
+            result.problem_details = Some(format_ident!("{}ProblemDetails", error_ident));
+        }
+        syn::NestedMeta::Meta(syn::Meta::NameValue(arg)) if arg.path.is_ident("problem_details") => {
+            result.problem_details = match &arg.lit {
+                syn::Lit::Str(v) => Some(v.parse().map_err(|e| e.to_compile_error())?),
+                _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for problem_details, expected string").to_compile_error())); },
+            };
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("jsonrpc") => {
+            result.jsonrpc = Some(format_ident!("{}JsonRpcError", error_ident));
+        }
+        syn::NestedMeta::Meta(syn::Meta::NameValue(arg)) if arg.path.is_ident("jsonrpc") => {
+            result.jsonrpc = match &arg.lit {
+                syn::Lit::Str(v) => Some(v.parse().map_err(|e| e.to_compile_error())?),
+                _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for jsonrpc, expected string").to_compile_error())); },
+            };
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("termination") => {
+            result.termination = Some(format_ident!("{}Termination", error_ident));
+        }
+        syn::NestedMeta::Meta(syn::Meta::NameValue(arg)) if arg.path.is_ident("termination") => {
+            result.termination = match &arg.lit {
+                syn::Lit::Str(v) => Some(v.parse().map_err(|e| e.to_compile_error())?),
+                _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for termination, expected string").to_compile_error())); },
+            };
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("ffi") => {
+            result.ffi = Some(format_ident!("{}Code", error_ident));
+        }
+        syn::NestedMeta::Meta(syn::Meta::NameValue(arg)) if arg.path.is_ident("ffi") => {
+            result.ffi = match &arg.lit {
+                syn::Lit::Str(v) => Some(v.parse().map_err(|e| e.to_compile_error())?),
+                _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for ffi, expected string").to_compile_error())); },
+            };
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("last_error") => {
+            result.last_error = Some(to_snake_case(error_ident));
+        }
+        syn::NestedMeta::Meta(syn::Meta::NameValue(arg)) if arg.path.is_ident("last_error") => {
+            result.last_error = match &arg.lit {
+                syn::Lit::Str(v) => Some(v.value()),
+                _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for last_error, expected string").to_compile_error())); },
+            };
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("catalog") => {
+            result.catalog = Some(format_ident!("{}Info", error_ident));
+        }
+        syn::NestedMeta::Meta(syn::Meta::NameValue(arg)) if arg.path.is_ident("catalog") => {
+            result.catalog = match &arg.lit {
+                syn::Lit::Str(v) => Some(v.parse().map_err(|e| e.to_compile_error())?),
+                _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for catalog, expected string").to_compile_error())); },
+            };
+        }
+        syn::NestedMeta::Meta(syn::Meta::NameValue(arg)) if arg.path.is_ident("url") => {
+            result.url = match &arg.lit {
+                syn::Lit::Str(v) => Some(v.clone()),
+                _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for url, expected string").to_compile_error())); },
+            };
+        }
+        syn::NestedMeta::Meta(syn::Meta::NameValue(arg)) if arg.path.is_ident("result_alias") => {
+            result.result_alias = match &arg.lit {
+                syn::Lit::Str(v) => Some(v.parse().map_err(|e| e.to_compile_error())?),
+                _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for result_alias, expected string").to_compile_error())); },
+            };
+        }
+        syn::NestedMeta::Meta(syn::Meta::NameValue(arg)) if arg.path.is_ident("into") || arg.path.is_ident("into_variants") => {
+            if result.into.is_some() {
+                return Err(TokenStream::from(syn::Error::new_spanned(&arg.path, "Only one of into and into_variants can be given").to_compile_error()));
+            }
+            result.into = match &arg.lit {
+                syn::Lit::Str(v) => Some(v.parse().map_err(|e| e.to_compile_error())?),
+                _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, format!("Incorrect value for {}, expected string", arg.path.to_token_stream())).to_compile_error())); },
+            };
+            result.into_variants = arg.path.is_ident("into_variants");
+        }
+        _ => { return Err(TokenStream::from(syn::Error::new_spanned(arg_name(arg), "Unknown parameter").to_compile_error())); },
+    }
+    Ok(())
+}
+
+struct FieldAttrs {
+    from: Option<usize>,
+    source: Option<usize>,
+    labels: Vec<(usize, Option<String>)>,
+    source_code: Option<usize>,
+    arg_names: Vec<(usize, String)>,
+    arc: Vec<usize>,
+    boxed: Vec<usize>,
+    backtrace: Option<usize>,
+    location: Option<usize>,
+    timestamp: Option<usize>,
+    instance_id: Option<(usize, Option<syn::Path>)>,
+    config_path: Option<usize>,
+    config_key: Option<usize>,
+}
+
+// Find the fields marked with #[auto_error(from)] and #[auto_error(source)], if any,
+//  as well as the source code and labeled spans used in miette diagnostics, the
+//  names given to message arguments, the fields holding their error in an Arc or
+//  Box, the fields capturing a backtrace, location, timestamp or instance ID,
+//  and the fields holding the configuration file and key an error occurred in
+fn parse_field_attrs(variant: &syn::Variant, errors: &mut TokenStream) -> FieldAttrs {
+    let mut result = FieldAttrs {
+        from: None,
+        source: None,
+        labels: vec![],
+        source_code: None,
+        arg_names: vec![],
+        arc: vec![],
+        boxed: vec![],
+        backtrace: None,
+        location: None,
+        timestamp: None,
+        instance_id: None,
+        config_path: None,
+        config_key: None,
+    };
+    for (i, field) in variant.fields.iter().enumerate() {
+        for attr in field.attrs.iter() {
+            if !attr.path.is_ident("auto_error") {
+                continue;
+            }
+
+            let meta = match attr.parse_meta() {
+                Ok(syn::Meta::List(list)) => list,
+                Ok(meta) => {
+                    errors.extend(TokenStream::from(syn::Error::new_spanned(&meta, "Incorrect auto_error arguments").to_compile_error()));
+                    continue;
+                }
+                Err(e) => {
+                    errors.extend(TokenStream::from(e.to_compile_error()));
+                    continue;
+                }
+            };
+
+            for arg in meta.nested.iter() {
+                if let Err(e) = parse_field_arg(&mut result, arg, i) {
+                    errors.extend(e);
+                }
+            }
+        }
+    }
+    result
+}
+
+// Parse a single auto_error argument of field i
+fn parse_field_arg(result: &mut FieldAttrs, arg: &syn::NestedMeta, i: usize) -> Result<(), TokenStream> {
+    match arg {
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("from") => {
+            if result.from.is_some() {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "Only one field can be marked as from").to_compile_error()));
+            }
+            result.from = Some(i);
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("source") => {
+            if result.source.is_some() {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "Only one field can be marked as source").to_compile_error()));
+            }
+            result.source = Some(i);
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("source_code") => {
+            if result.source_code.is_some() {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "Only one field can be marked as source_code").to_compile_error()));
+            }
+            result.source_code = Some(i);
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("backtrace") => {
+            if result.backtrace.is_some() {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "Only one field can be marked as backtrace").to_compile_error()));
+            }
+            result.backtrace = Some(i);
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("location") => {
+            if result.location.is_some() {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "Only one field can be marked as location").to_compile_error()));
+            }
+            result.location = Some(i);
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("config_path") => {
+            if result.config_path.is_some() {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "Only one field can be marked as config_path").to_compile_error()));
+            }
+            result.config_path = Some(i);
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("config_key") => {
+            if result.config_key.is_some() {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "Only one field can be marked as config_key").to_compile_error()));
+            }
+            result.config_key = Some(i);
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("timestamp") => {
+            if result.timestamp.is_some() {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "Only one field can be marked as timestamp").to_compile_error()));
+            }
+            result.timestamp = Some(i);
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("instance_id") => {
+            if result.instance_id.is_some() {
+                return Err(TokenStream::from(syn::Error::new_spanned(path, "Only one field can be marked as instance_id").to_compile_error()));
+            }
+            result.instance_id = Some((i, None));
+        }
+        syn::NestedMeta::Meta(syn::Meta::NameValue(arg)) if arg.path.is_ident("instance_id") => {
+            if result.instance_id.is_some() {
+                return Err(TokenStream::from(syn::Error::new_spanned(&arg.path, "Only one field can be marked as instance_id").to_compile_error()));
+            }
+            let generator = match &arg.lit {
+                syn::Lit::Str(v) => v.parse().map_err(|e| TokenStream::from(e.to_compile_error()))?,
+                _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for instance_id, expected path to a function").to_compile_error())); },
+            };
+            result.instance_id = Some((i, Some(generator)));
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("arc") => {
+            result.arc.push(i);
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("boxed") => {
+            result.boxed.push(i);
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("label") => {
+            result.labels.push((i, None));
+        }
+        syn::NestedMeta::Meta(syn::Meta::NameValue(arg)) if arg.path.is_ident("label") => {
+            match &arg.lit {
+                syn::Lit::Str(v) => result.labels.push((i, Some(v.value()))),
+                _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for label, expected string").to_compile_error())); },
+            }
+        }
+        syn::NestedMeta::Meta(syn::Meta::NameValue(arg)) if arg.path.is_ident("arg") => {
+            match &arg.lit {
+                syn::Lit::Str(v) => result.arg_names.push((i, v.value())),
+                _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for arg, expected string").to_compile_error())); },
+            }
+        }
+        _ => { return Err(TokenStream::from(syn::Error::new_spanned(arg_name(arg), "Unknown parameter").to_compile_error())); },
+    }
+    Ok(())
+}
+
+// Parse an attribute like parse_meta does, but also accepting negative
+//  integer values such as jsonrpc_code = -32001, and paths to constants
+//  such as errno = libc::ENOENT, which are turned into string literals
+fn parse_attr_meta(attr: &syn::Attribute) -> syn::Result<syn::Meta> {
+    fn merge_negative(tokens: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let mut result: Vec<proc_macro2::TokenTree> = vec![];
+        let mut tokens = tokens.into_iter().peekable();
+        while let Some(token) = tokens.next() {
+            let is_errno = matches!(result.last(), Some(proc_macro2::TokenTree::Ident(ident)) if ident == "errno");
+            match token {
+                proc_macro2::TokenTree::Punct(ref punct) if punct.as_char() == '=' && is_errno && matches!(tokens.peek(), Some(proc_macro2::TokenTree::Ident(_)) | Some(proc_macro2::TokenTree::Punct(_))) => {
+                    let mut expr = vec![];
+                    while let Some(next) = tokens.peek() {
+                        if matches!(next, proc_macro2::TokenTree::Punct(punct) if punct.as_char() == ',') {
+                            break;
+                        }
+                        expr.push(tokens.next().unwrap());
+                    }
+                    let mut lit = proc_macro2::Literal::string(&expr.iter().cloned().collect::<proc_macro2::TokenStream>().to_string());
+                    lit.set_span(expr[0].span());
+                    result.push(token);
+                    result.push(proc_macro2::TokenTree::Literal(lit));
+                }
+                proc_macro2::TokenTree::Punct(ref punct) if punct.as_char() == '-' => {
+                    let value = match tokens.peek() {
+                        Some(proc_macro2::TokenTree::Literal(lit)) => lit.to_string().parse::<i64>().ok(),
+                        _ => None,
+                    };
+                    match value {
+                        Some(value) => {
+                            let mut lit = proc_macro2::Literal::i64_unsuffixed(-value);
+                            lit.set_span(punct.span());
+                            tokens.next();
+                            result.push(proc_macro2::TokenTree::Literal(lit));
+                        }
+                        None => result.push(token),
+                    }
+                }
+                proc_macro2::TokenTree::Group(group) => {
+                    let mut merged = proc_macro2::Group::new(group.delimiter(), merge_negative(group.stream()));
+                    merged.set_span(group.span());
+                    result.push(proc_macro2::TokenTree::Group(merged));
+                }
+                token => result.push(token),
+            }
+        }
+        result.into_iter().collect()
+    }
+
+    let path = &attr.path;
+    let tokens = merge_negative(attr.tokens.clone());
+    syn::parse2(quote!{ #path #tokens })
+}
+
+// The gRPC status codes, by their name in attributes and in tonic::Code
+const GRPC_CODES: &[(&str, &str)] = &[
+    ("ok", "Ok"),
+    ("cancelled", "Cancelled"),
+    ("unknown", "Unknown"),
+    ("invalid_argument", "InvalidArgument"),
+    ("deadline_exceeded", "DeadlineExceeded"),
+    ("not_found", "NotFound"),
+    ("already_exists", "AlreadyExists"),
+    ("permission_denied", "PermissionDenied"),
+    ("resource_exhausted", "ResourceExhausted"),
+    ("failed_precondition", "FailedPrecondition"),
+    ("aborted", "Aborted"),
+    ("out_of_range", "OutOfRange"),
+    ("unimplemented", "Unimplemented"),
+    ("internal", "Internal"),
+    ("unavailable", "Unavailable"),
+    ("data_loss", "DataLoss"),
+    ("unauthenticated", "Unauthenticated"),
+];
+
+// The integer types an enum can be represented as
+const REPR_TYPES: &[&str] = &["u8", "u16", "u32", "u64", "usize", "i8", "i16", "i32", "i64", "isize"];
+
+// The severity levels, from least to most severe, by their name in
+//  attributes and in the generated severity enum, with their description
+const SEVERITIES: &[(&str, &str, &str)] = &[
+    ("debug", "Debug", "Diagnostic information only."),
+    ("info", "Info", "Expected failures, worth recording but not acting upon."),
+    ("warn", "Warn", "Failures that may need attention."),
+    ("error", "Error", "Failures that need attention."),
+    ("critical", "Critical", "Failures that need immediate attention."),
+];
+
+// Integrations routing the errors of a library into variants by class,
+//  with the class other catching the errors not routed elsewhere
+struct Routing {
+    name: &'static str,
+    enabled: bool,
+    error_type: &'static str,
+    classes: &'static [&'static str],
+    condition: fn(&str) -> proc_macro2::TokenStream,
+}
+
+const ROUTINGS: &[Routing] = &[
+    Routing {
+        name: "sqlx",
+        enabled: cfg!(feature = "sqlx"),
+        error_type: "sqlx::Error",
+        classes: &["row_not_found", "pool_timed_out", "unique_violation", "foreign_key_violation", "not_null_violation", "check_violation", "other"],
+        condition: sqlx_condition,
+    },
+    Routing {
+        name: "diesel",
+        enabled: cfg!(feature = "diesel"),
+        error_type: "diesel::result::Error",
+        classes: &["not_found", "unique_violation", "foreign_key_violation", "not_null_violation", "check_violation", "serialization_failure", "read_only_transaction", "other"],
+        condition: diesel_condition,
+    },
+    Routing {
+        name: "reqwest",
+        enabled: cfg!(feature = "reqwest"),
+        error_type: "reqwest::Error",
+        classes: &["timeout", "connect", "status", "client_error", "server_error", "redirect", "body", "decode", "request", "builder", "other"],
+        condition: reqwest_condition,
+    },
+];
+
+// Condition matching a class of sqlx errors, held in e
+fn sqlx_condition(class: &str) -> proc_macro2::TokenStream {
+    let kind = match class {
+        "row_not_found" => { return quote!{ ::std::matches!(&e, ::sqlx::Error::RowNotFound) }; },
+        "pool_timed_out" => { return quote!{ ::std::matches!(&e, ::sqlx::Error::PoolTimedOut) }; },
+        "unique_violation" => format_ident!("UniqueViolation"),
+        "foreign_key_violation" => format_ident!("ForeignKeyViolation"),
+        "not_null_violation" => format_ident!("NotNullViolation"),
+        _ => format_ident!("CheckViolation"),
+    };
+    quote!{ ::std::matches!(&e, ::sqlx::Error::Database(db) if ::std::matches!(db.kind(), ::sqlx::error::ErrorKind::#kind)) }
+}
+
+// Condition matching a class of diesel errors, held in e
+fn diesel_condition(class: &str) -> proc_macro2::TokenStream {
+    let kind = match class {
+        "not_found" => { return quote!{ ::std::matches!(&e, ::diesel::result::Error::NotFound) }; },
+        "unique_violation" => format_ident!("UniqueViolation"),
+        "foreign_key_violation" => format_ident!("ForeignKeyViolation"),
+        "not_null_violation" => format_ident!("NotNullViolation"),
+        "check_violation" => format_ident!("CheckViolation"),
+        "serialization_failure" => format_ident!("SerializationFailure"),
+        _ => format_ident!("ReadOnlyTransaction"),
+    };
+    quote!{ ::std::matches!(&e, ::diesel::result::Error::DatabaseError(::diesel::result::DatabaseErrorKind::#kind, _)) }
+}
+
+// Condition matching a class of reqwest errors, held in e
+fn reqwest_condition(class: &str) -> proc_macro2::TokenStream {
+    match class {
+        "client_error" => quote!{ ::std::matches!(e.status(), ::std::option::Option::Some(status) if status.is_client_error()) },
+        "server_error" => quote!{ ::std::matches!(e.status(), ::std::option::Option::Some(status) if status.is_server_error()) },
+        _ => {
+            let check = format_ident!("is_{}", class);
+            quote!{ e.#check() }
+        }
+    }
+}
+
+// The log crate levels, by their name in attributes and in log::Level
+const LOG_LEVELS: &[(&str, &str)] = &[
+    ("error", "Error"),
+    ("warn", "Warn"),
+    ("info", "Info"),
+    ("debug", "Debug"),
+    ("trace", "Trace"),
+];
+
+// Parse a single variant in the enum
+fn parse_variant<'a>(variant: &'a syn::Variant, error_ident: &syn::Ident, heuristic: &ErrorHeuristic) -> Result<ErrorVariant<'a>, TokenStream> {
+    // validate fields are unnamed (but present!)
+    match variant.fields {
+        syn::Fields::Named(_) => { return Err(TokenStream::from(syn::Error::new_spanned(&variant.fields, "Named fields not supported").to_compile_error())); }
+        syn::Fields::Unnamed(_) => {}
+        syn::Fields::Unit => { }
+    }
+
+    let mut attr: Option<_> = None;
+    for attr_cand in variant.attrs.iter() {
+        if attr_cand.path.is_ident("auto_error") {
+            if attr.is_some() {
+                return Err(TokenStream::from(syn::Error::new_spanned(attr_cand, "Duplicate occurence of auto_error attribute").to_compile_error()));
+            }
+            attr = Some(attr_cand);
+        }
+    }
+
+    let args = match attr {
+        Some(attr) => {
+            let meta = parse_attr_meta(attr).map_err(|e| e.to_compile_error())?;
+            match meta {
+                syn::Meta::List(list) => list.nested,
+                _ => { return Err(TokenStream::from(syn::Error::new_spanned(&meta, "Incorrect auto_error arguments").to_compile_error())); },
+            }
+        }
+        None => syn::punctuated::Punctuated::new(),
+    };
+    let is_flag = |arg: &syn::NestedMeta, name: &str| matches!(arg, syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident(name));
+    let whatever = args.iter().any(|arg| is_flag(arg, "whatever"));
+    let catch_all = args.iter().any(|arg| is_flag(arg, "catch_all"));
+    let from_poison = args.iter().any(|arg| is_flag(arg, "from_poison"));
+    let from_panic = args.iter().any(|arg| is_flag(arg, "from_panic"));
+    let context = args.iter().any(|arg| is_flag(arg, "context"));
+
+    // Errors in the fields and parameters are all reported together,
+    //  rather than only the first one
+    let mut errors = TokenStream::new();
+    let field_attrs = parse_field_attrs(variant, &mut errors);
+
+    // Fields capturing a backtrace or location are filled in by the
+    //  generated code, and are not part of the payload of the variant,
+    //  just like the configuration file and key, which are set afterwards
+    let captured: Vec<_> = field_attrs.backtrace.iter()
+        .chain(field_attrs.location.iter())
+        .chain(field_attrs.timestamp.iter())
+        .chain(field_attrs.instance_id.iter().map(|(i, _)| i))
+        .chain(field_attrs.config_path.iter())
+        .chain(field_attrs.config_key.iter())
+        .copied()
+        .collect();
+    let payload_fields: Vec<_> = (0..variant.fields.len()).filter(|i| !captured.contains(i)).collect();
+    if catch_all && payload_fields.len() != 1 {
+        errors.extend(TokenStream::from(syn::Error::new_spanned(find_arg(&args, "catch_all"), "Catch-all variants should have a single message field").to_compile_error()));
+    }
+    if from_poison && payload_fields.len() > 1 {
+        errors.extend(TokenStream::from(syn::Error::new_spanned(find_arg(&args, "from_poison"), "Poison variants should have no fields or a single message field").to_compile_error()));
+    }
+
+    // The source is either the marked field, the only field, the only
+    //  field referring to the error type itself, or the only field whose
+    //  type is named like an error, as in Read(PathBuf, std::io::Error)
+    let self_fields: Vec<_> = variant.fields.iter().enumerate()
+        .filter(|(_, field)| is_self_type(&field.ty, error_ident))
+        .map(|(i, _)| i)
+        .collect();
+    // Fields gated by a cfg are already removed when disabled, so they
+    //  are ignored here to infer the same source in every configuration.
+    let ungated_fields: Vec<_> = variant.fields.iter().enumerate()
+        .filter(|(i, field)| !captured.contains(i) && !field.attrs.iter().any(|attr| attr.path.is_ident("cfg")))
+        .map(|(i, _)| i)
+        .collect();
+    let error_fields: Vec<_> = ungated_fields.iter().copied()
+        .filter(|i| {
+            let ty = &variant.fields.iter().nth(*i).unwrap().ty;
+            infer_is_error(ty, heuristic) || infer_is_error_named(ty, heuristic)
+        })
+        .collect();
+    let error_field = match error_fields[..] {
+        [i] => Some(i),
+        _ => None,
+    };
+    let only_field = match payload_fields.len() {
+        1 => Some(payload_fields[0]),
+        _ if ungated_fields.len() == 1 => Some(ungated_fields[0]),
+        _ => None,
+    };
+    let source_field = match field_attrs.source {
+        Some(i) => Some(i),
+        None if only_field.is_some() => only_field,
+        None if self_fields.len() == 1 => Some(self_fields[0]),
+        None if error_field.is_some() => error_field,
+        None => None,
+    };
+    let source_ty = source_field.map(|i| &variant.fields.iter().nth(i).unwrap().ty);
+    let is_error = source_ty.map_or(false, |ty| infer_is_error(ty, heuristic)) || (only_field.is_none() && source_field.is_some() && source_field == error_field);
+    // References to the error type itself are sources, but From
+    //  for those is not derived automatically.
+    let is_self = source_ty.map_or(false, |ty| is_self_type(ty, error_ident));
+    // Cow messages are converted from both static and owned strings
+    let is_cow = only_field.map_or(false, |i| is_cow_str(&variant.fields.iter().nth(i).unwrap().ty));
+
+    let mut result = ErrorVariant {
+        err: field_attrs.source.is_some() || is_error || is_self,
+        source_as_ref: source_ty.map_or(false, infer_source_as_ref),
+        make_from: (only_field.is_some() && only_field == source_field && is_error && !is_self) || field_attrs.from.is_some() || is_cow,
+        from_tuple: false,
+        from_field: field_attrs.from.or(only_field),
+        source_field,
+        format_str: infer_format_str(variant, &captured),
+        separator: None,
+        whatever,
+        code: None,
+        status: None,
+        body: None,
+        grpc: None,
+        problem_type: None,
+        title: None,
+        jsonrpc_code: None,
+        exit_code: None,
+        errno: None,
+        py_exc: None,
+        napi_status: None,
+        routes: vec![],
+        retryable: if args.iter().any(|arg| is_flag(arg, "retryable")) { Some(true) } else { None },
+        chain_fmt: if args.iter().any(|arg| is_flag(arg, "chain_fmt")) { Some(true) } else { None },
+        severity: None,
+        log_level: None,
+        report: args.iter().any(|arg| is_flag(arg, "report")),
+        help: None,
+        url: None,
+        msg_key: None,
+        into: None,
+        arg_names: field_attrs.arg_names.clone(),
+        arc_fields: field_attrs.arc.clone(),
+        boxed_fields: field_attrs.boxed.clone(),
+        backtrace_field: field_attrs.backtrace,
+        location_field: field_attrs.location,
+        timestamp_field: field_attrs.timestamp,
+        instance_id_field: field_attrs.instance_id.clone(),
+        config_path_field: field_attrs.config_path,
+        config_key_field: field_attrs.config_key,
+        labels: field_attrs.labels.clone(),
+        validation_fields: vec![],
+        source_code: field_attrs.source_code,
+        deserialize: !whatever,
+        catch_all,
+        from_poison,
+        from_panic,
+        context,
+        hidden: is_doc_hidden(&variant.attrs),
+        cfgs: variant.attrs.iter().filter(|attr| attr.path.is_ident("cfg") || attr.path.is_ident("cfg_attr")).collect(),
+        deprecated: variant.attrs.iter().filter(|attr| attr.path.is_ident("deprecated")).collect(),
+        superseded_by: None,
+        variant,
+    };
+
+    // Whatever variants hold a message, optionally followed by a source
+    if whatever && (payload_fields.is_empty() || payload_fields.len() > 2) {
+        errors.extend(TokenStream::from(syn::Error::new_spanned(find_arg(&args, "whatever"), "Whatever variants should have a message field and optionally a source field").to_compile_error()));
+    } else if whatever {
+        result.err = payload_fields.len() == 2;
+        result.source_field = payload_fields.get(1).copied();
+        result.source_as_ref = false;
+        result.make_from = false;
+        result.format_str = "{}".to_string();
+    }
+
+    // Context variants hold a message followed by the source it describes
+    if context && payload_fields.len() != 2 {
+        errors.extend(TokenStream::from(syn::Error::new_spanned(find_arg(&args, "context"), "Context variants should have a message field and a source field").to_compile_error()));
+    } else if context {
+        result.err = true;
+        result.source_field = Some(payload_fields[1]);
+        result.source_as_ref = false;
+        result.make_from = false;
+        result.format_str = "{}".to_string();
+    }
+
+    // Panic variants hold the message of the panic, optionally followed by
+    //  the payload it was raised with
+    if from_panic && (payload_fields.is_empty() || payload_fields.len() > 2) {
+        errors.extend(TokenStream::from(syn::Error::new_spanned(find_arg(&args, "from_panic"), "Panic variants should have a message field and optionally a payload field").to_compile_error()));
+    } else if from_panic {
+        result.make_from = false;
+        result.format_str = "{}".to_string();
+    }
+
+    for arg in args.iter() {
+        if is_flag(arg, "whatever") || is_flag(arg, "catch_all") || is_flag(arg, "from_poison") || is_flag(arg, "from_panic") || is_flag(arg, "context") || is_flag(arg, "chain_fmt") || is_flag(arg, "boxed") || is_flag(arg, "retryable") || is_flag(arg, "report") {
+            continue;
+        }
+        if let Err(e) = parse_variant_arg(&mut result, arg) {
+            errors.extend(e);
+        }
+    }
+
+    // Boxed variants are converted from the unboxed value of their field
+    if args.iter().any(|arg| is_flag(arg, "boxed")) {
+        match result.from_field.filter(|_| !result.from_tuple) {
+            Some(i) => {
+                result.make_from = true;
+                result.boxed_fields.push(i);
+            }
+            None => { errors.extend(TokenStream::from(syn::Error::new_spanned(find_arg(&args, "boxed"), "Boxed variants should be converted from a single field").to_compile_error())); },
+        }
+    }
+
+    // Variants routed a class of errors (such as those of sqlx) are
+    //  converted by the From implementation of the variant for the other
+    //  errors, which holds the error like any variant converted from it
+    for (routing, class) in result.routes.clone() {
+        if class.value() == "other" {
+            if result.from_field.is_none() || result.from_tuple {
+                let message = format!("The variant for other {} errors should have a single field, or a field marked as from", routing.name);
+                errors.extend(TokenStream::from(syn::Error::new_spanned(find_arg(&args, routing.name), message).to_compile_error()));
+            }
+            result.make_from = true;
+        } else {
+            if payload_fields.len() > 1 {
+                let message = format!("Variants routed {} errors should have no fields or a single {} field", routing.name, routing.error_type);
+                errors.extend(TokenStream::from(syn::Error::new_spanned(find_arg(&args, routing.name), message).to_compile_error()));
+            }
+            result.make_from = false;
+        }
+    }
+
+    // Inconsistencies between the parameters and the fields point at the
+    //  parameter when given explicitly, and at the variant otherwise
+    let arg_or_variant = |name: &str| match find_arg(&args, name) {
+        Some(arg) => arg_name(arg),
+        None => variant.to_token_stream(),
+    };
+    if let Err(message) = check_format_str(&result.format_str, payload_fields.len()) {
+        let format_str = match find_arg(&args, "format_str") {
+            Some(syn::NestedMeta::Meta(syn::Meta::NameValue(arg))) => arg.lit.to_token_stream(),
+            _ => variant.to_token_stream(),
+        };
+        errors.extend(TokenStream::from(syn::Error::new_spanned(format_str, message).to_compile_error()));
+    }
+    if result.err && result.source_field.is_none() {
+        errors.extend(TokenStream::from(syn::Error::new_spanned(arg_or_variant("err"), "Wrapped errors should have exactly 1 argument, or a field marked as source").to_compile_error()));
+    }
+
+    if result.make_from && payload_fields.len() != 1 && result.from_field.is_none() {
+        errors.extend(TokenStream::from(syn::Error::new_spanned(arg_or_variant("make_from"), "Can only derive from for variants with 1 field, or with a field marked as from").to_compile_error()));
+    }
+
+    if result.from_tuple && payload_fields.is_empty() {
+        errors.extend(TokenStream::from(syn::Error::new_spanned(arg_or_variant("make_from"), "Can only derive tuple from for variants with fields").to_compile_error()));
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(result)
+}
+
+// Parse a single auto_error argument of a variant
+fn parse_variant_arg(result: &mut ErrorVariant, arg: &syn::NestedMeta) -> Result<(), TokenStream> {
+    let arg = match arg {
+        syn::NestedMeta::Meta(arg) => arg,
+        _ => { return Err(TokenStream::from(syn::Error::new_spanned(arg, "Incorrect auto_error arguments").to_compile_error())); },
+    };
+    let arg = match arg {
+        syn::Meta::NameValue(arg) => arg,
+        _ => { return Err(TokenStream::from(syn::Error::new_spanned(arg, "Incorrect auto_error arguments").to_compile_error())); },
+    };
+    if arg.path.is_ident("err") {
+        match &arg.lit {
+            syn::Lit::Bool(v) => {
+                result.err = v.value;
+            },
+            syn::Lit::Str(v) if v.value() == "as_ref" => {
+                result.err = true;
+                result.source_as_ref = true;
+            },
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for err, expected bool or \"as_ref\"").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("format_str") {
+        result.format_str = match &arg.lit {
+            syn::Lit::Str(v) => v.value(),
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for format_str, expected string").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("status") {
+        result.status = match &arg.lit {
+            syn::Lit::Int(v) => match v.base10_parse::<u16>() {
+                Ok(status) if (100..1000).contains(&status) => Some(status),
+                _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for status, expected HTTP status code").to_compile_error())); },
+            },
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for status, expected HTTP status code").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("body") {
+        result.body = match &arg.lit {
+            syn::Lit::Str(v) => Some(v.value()),
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for body, expected string").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("grpc") {
+        result.grpc = match &arg.lit {
+            syn::Lit::Str(v) => match GRPC_CODES.iter().find(|(name, _)| *name == v.value()) {
+                Some((_, code)) => Some(syn::Ident::new(code, v.span())),
+                None => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Unknown gRPC code").to_compile_error())); },
+            },
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for grpc, expected string").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("problem_type") {
+        result.problem_type = match &arg.lit {
+            syn::Lit::Str(v) => Some(v.value()),
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for problem_type, expected string").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("title") {
+        result.title = match &arg.lit {
+            syn::Lit::Str(v) => Some(v.value()),
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for title, expected string").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("jsonrpc_code") {
+        result.jsonrpc_code = match &arg.lit {
+            syn::Lit::Int(v) => Some(v.base10_parse().map_err(|e| e.to_compile_error())?),
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for jsonrpc_code, expected integer").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("exit_code") {
+        result.exit_code = match &arg.lit {
+            syn::Lit::Int(v) => match v.base10_parse::<u8>() {
+                Ok(exit_code) if exit_code != 0 => Some(exit_code),
+                _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for exit_code, expected integer from 1 to 255").to_compile_error())); },
+            },
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for exit_code, expected integer from 1 to 255").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("errno") {
+        result.errno = match &arg.lit {
+            syn::Lit::Int(v) => Some(syn::parse_quote!{ #v }),
+            syn::Lit::Str(v) => Some(v.parse().map_err(|e| e.to_compile_error())?),
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for errno, expected integer or constant").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("py_exc") {
+        // Builtin exceptions are given by their Python name, custom
+        //  exceptions by their path
+        result.py_exc = match &arg.lit {
+            syn::Lit::Str(v) if v.value().contains("::") => Some(v.parse().map_err(|e| e.to_compile_error())?),
+            syn::Lit::Str(v) => {
+                let exc = syn::Ident::new(&format!("Py{}", v.value()), v.span());
+                Some(syn::parse_quote!{ ::pyo3::exceptions::#exc })
+            }
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for py_exc, expected string").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("napi_status") {
+        result.napi_status = match &arg.lit {
+            syn::Lit::Str(v) => Some(v.parse().map_err(|e| e.to_compile_error())?),
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for napi_status, expected string").to_compile_error())); },
+        };
+    } else if let Some(routing) = ROUTINGS.iter().find(|routing| arg.path.is_ident(routing.name)) {
+        if !routing.enabled {
+            let message = format!("The {0} integration requires the {0} feature of autoerror", routing.name);
+            return Err(TokenStream::from(syn::Error::new_spanned(&arg.path, message).to_compile_error()));
+        }
+        match &arg.lit {
+            syn::Lit::Str(v) if routing.classes.contains(&v.value().as_str()) => result.routes.push((routing, v.clone())),
+            syn::Lit::Str(_) => {
+                let message = format!("Unknown {} error class, expected one of {}", routing.name, routing.classes.join(", "));
+                return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, message).to_compile_error()));
+            },
+            _ => {
+                let message = format!("Incorrect value for {}, expected string", routing.name);
+                return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, message).to_compile_error()));
+            },
+        }
+    } else if arg.path.is_ident("severity") {
+        result.severity = match &arg.lit {
+            syn::Lit::Str(v) => match SEVERITIES.iter().find(|(name, _, _)| *name == v.value()) {
+                Some((_, level, _)) => Some(syn::Ident::new(level, v.span())),
+                None => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Unknown severity, expected one of debug, info, warn, error or critical").to_compile_error())); },
+            },
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for severity, expected string").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("log") {
+        result.log_level = match &arg.lit {
+            syn::Lit::Str(v) => match LOG_LEVELS.iter().find(|(name, _)| *name == v.value()) {
+                Some((_, level)) => Some(syn::Ident::new(level, v.span())),
+                None => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Unknown log level, expected one of error, warn, info, debug or trace").to_compile_error())); },
+            },
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for log, expected string").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("superseded_by") {
+        result.superseded_by = match &arg.lit {
+            syn::Lit::Str(v) => Some(v.parse().map_err(|e| e.to_compile_error())?),
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for superseded_by, expected string").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("help") {
+        result.help = match &arg.lit {
+            syn::Lit::Str(v) => Some(v.value()),
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for help, expected string").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("url") {
+        result.url = match &arg.lit {
+            syn::Lit::Str(v) => Some(v.value()),
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for url, expected string").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("msg_key") {
+        result.msg_key = match &arg.lit {
+            syn::Lit::Str(v) => Some(v.value()),
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for msg_key, expected string").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("into") {
+        result.into = match &arg.lit {
+            syn::Lit::Str(v) => Some(v.parse().map_err(|e| e.to_compile_error())?),
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for into, expected string").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("chain_fmt") {
+        result.chain_fmt = match &arg.lit {
+            syn::Lit::Bool(v) => Some(v.value),
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for chain_fmt, expected bool").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("transient") {
+        result.retryable = match &arg.lit {
+            syn::Lit::Bool(v) => Some(v.value),
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for transient, expected bool").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("deserialize") {
+        result.deserialize = match &arg.lit {
+            syn::Lit::Bool(v) => v.value,
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for deserialize, expected bool").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("code") {
+        result.code = match &arg.lit {
+            syn::Lit::Str(v) => Some(v.clone()),
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for code, expected string").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("separator") {
+        result.separator = match &arg.lit {
+            syn::Lit::Str(v) => Some(v.value()),
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for separator, expected string").to_compile_error())); },
+        };
+    } else if arg.path.is_ident("make_from") {
+        match &arg.lit {
+            syn::Lit::Bool(v) => {
+                result.make_from = v.value;
+                result.from_tuple = false;
+            },
+            syn::Lit::Str(v) if v.value() == "tuple" => {
+                result.make_from = false;
+                result.from_tuple = true;
+            },
+            _ => { return Err(TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for make_from, expected bool or \"tuple\"").to_compile_error())); },
+        };
+    } else {
+        return Err(TokenStream::from(syn::Error::new_spanned(&arg.path, "Unknown parameter").to_compile_error()));
+    }
+    Ok(())
+}
+
+/// Derive basic error type infrastruture for enum types.
+///
+/// Supports unnamed and unit enum variants, and uses the type definition
+///  to derive `std::fmt::Display` and `std::error:Error` for the error type,
+///  as well as `std::from::From<T>` for any unnamed variant with one parameter
+///  inferred to be an error type (currently determined by whether it's type
+///  name is Error, looking through `Box`, `Arc` and `Rc`). Boxed trait objects
+///  such as `Box<dyn std::error::Error + Send + Sync>` are also recognized.
+///
+/// Default behaviour can be overridden with the auto_error attribute
+///  - format_str takes a string which becomes the format string for that
+///    variant. Fields not mentioned in the format string are left out of
+///    the message. Placeholders refer to the fields by position, and are
+///    checked to refer to existing fields.
+///    Variants whose format string only displays a single field pass the
+///    formatter on to it, so flags such as `{:#}`, width and precision apply
+///    to the field.
+///  - make_from forces derivation of std::from::From when set to true. When
+///    set to "tuple", std::from::From is instead derived from a tuple of all
+///    the variant's fields. Two variants converted from the same type are
+///    reported, as their implementations would conflict, and need make_from
+///    set to false on one of them.
+///  - err forces the std::error::Error implementation to return the inner
+///    type during calls to source, or in other words to treat the inner
+///    type as an error type. The inner type can be an `Option`, in which
+///    case source only returns the inner error when present. When set to
+///    "as_ref", the inner type is turned into an error through
+///    `AsRef<dyn std::error::Error>`, which is needed for types such as
+///    `anyhow::Error` that don't implement `std::error::Error` themselves.
+///    `anyhow::Error` is detected automatically, as is `eyre::Report` when
+///    the eyre feature is enabled.
+///  - separator sets the string placed between the elements of `Vec` fields
+///    when displaying them. Aggregates are joined with ", " by default,
+///    other `Vec` fields only when the variant sets a separator, and are
+///    otherwise displayed as a whole, e.g. with `{:?}`.
+///  - chain_fmt appends the message of the source to the message of the variant,
+///    as in "failed to save user: disk full", without repeating it in the format
+///    string. Set to false, it turns off chain_fmt given on the enum for the
+///    variant.
+///  - whatever marks the variant as the catch-all for ad-hoc errors. It holds a
+///    message, optionally followed by a source, and gets `whatever` and
+///    `whatever_with_source` constructors taking anything implementing
+///    `std::fmt::Display` as message, such as `format_args!(...)`.
+///  - code takes a string with a stable code for the variant, returned by a
+///    generated `code` method as `Option<&'static str>`. When any variant has a
+///    code all of them need one, except hidden variants, for which `code` returns
+///    `None`, and codes need to be unique.
+///    Enums with an integer `#[repr(..)]` and explicit discriminants instead get a
+///    `code` method returning the discriminant as that integer type, and their
+///    kind enum gets the same discriminants and a `TryFrom` implementation from
+///    the integer type. Integrations report whichever kind of code is generated.
+///  - status takes the HTTP status code used for the variant by the web framework
+///    integrations, defaulting to 500.
+///  - body takes the response body used for the variant by the actix and rocket
+///    integrations instead of the error message, such as to keep internal details
+///    out of responses.
+///  - grpc takes the name of the gRPC status code used for the variant, such as
+///    "not_found", defaulting to "internal".
+///  - problem_type and title take the type URI and title used for the variant in
+///    RFC 7807 problem details, defaulting to "about:blank" and the variant name
+///    written out as words.
+///  - jsonrpc_code takes the JSON-RPC error code of the variant, defaulting to
+///    -32603 (internal error).
+///  - exit_code takes the process exit code of the variant, from 1 to 255,
+///    returned by a generated `exit_code` method. Variants without one exit with 1.
+///  - errno takes the raw OS error code of the variant, as an integer or a
+///    constant such as `libc::ENOENT`. The generated `raw_os_error` method returns
+///    it, and `from_raw_os_error` and `from_io_error` turn a code back into the
+///    variant. Such variants can't have payload fields, and codes need to be
+///    unique.
+///  - py_exc takes the Python exception raised for the variant by the pyo3
+///    integration, such as "ValueError", or the path to a custom exception type,
+///    defaulting to "RuntimeError".
+///  - napi_status takes the name of the `napi::Status` used for the variant by the
+///    napi integration, such as "InvalidArg", defaulting to "GenericFailure".
+///  - sqlx routes a class of `sqlx::Error` to the variant, one of "row_not_found",
+///    "pool_timed_out", "unique_violation", "foreign_key_violation",
+///    "not_null_violation" or "check_violation", when the sqlx feature is
+///    enabled. The generated `From<sqlx::Error>` picks the variant by the class of
+///    the error, and otherwise falls back on the variant converted from
+///    `sqlx::Error`, or the one marked "other". Routed variants have no fields or
+///    hold the `sqlx::Error`.
+///  - diesel routes a class of `diesel::result::Error` to the variant in the same
+///    way when the diesel feature is enabled, one of "not_found",
+///    "unique_violation", "foreign_key_violation", "not_null_violation",
+///    "check_violation", "serialization_failure" or "read_only_transaction",
+///    with "other" marking the variant for the remaining errors.
+///  - reqwest routes `reqwest::Error` to the variant by its classification when
+///    the reqwest feature is enabled, one of "timeout", "connect", "status",
+///    "client_error", "server_error", "redirect", "body", "decode", "request"
+///    or "builder", with "other" marking the variant for the remaining errors.
+///    As errors can fall into several classes, such as a timeout while
+///    connecting, the classes are checked in the order of the variants.
+///  - retryable, or transient set to true, marks the variant as retryable in the
+///    generated `is_retryable` method. Variants wrapping the error type itself
+///    return whether the wrapped error is retryable, unless transient is given
+///    explicitly.
+///  - severity takes the severity of the variant, one of "debug", "info", "warn",
+///    "error" or "critical", defaulting to "error". When any variant has one, a
+///    `severity` method returns it as a generated enum named after the error type
+///    with Severity appended, ordered from least to most severe.
+///  - log takes the `log::Level` of the variant, one of "error", "warn", "info",
+///    "debug" or "trace", defaulting to "error". The tracing integration uses the
+///    same level.
+///  - report makes the sentry integration report every error of the variant
+///    created through the generated `From` implementations and constructors.
+///  - help takes a help text suggesting how to resolve the error, returned by a
+///    generated `help` method and shown by display_chain, termination and the
+///    miette integration.
+///  - url takes a documentation link for the variant, returned by a generated
+///    `url` method and used by the miette integration. Variants without one use
+///    the url template given on the enum, if any.
+///  - msg_key takes a stable key for the message of the variant, for localizing
+///    errors through a translation catalog such as Fluent. When any variant has
+///    one, a generated `message_key` method returns it, defaulting to the
+///    variant name in kebab-case, and `message_args` returns the fields used in
+///    the format string as named values. Arguments are named arg0, arg1, ...
+///    after their position, unless the field is marked with
+///    `#[auto_error(arg = "...")]`.
+///  - into takes the conversion of the variant into the error type given by into
+///    on the enum. A path to a variant or function is called with the fields of
+///    the variant, as in `into = "ApiError::NotFound"`, and any other expression
+///    is evaluated with the error in scope as `error`, as in
+///    `into = "ApiError::Internal(error.to_string())"`.
+///  - deserialize set to false leaves the variant out of deserialization, for
+///    variants holding data that can't be deserialized. Such errors deserialize
+///    into the variant marked catch_all, which should have a single field
+///    constructible from the message, or into the whatever variant.
+///  - from_poison marks the variant that poisoned locks are converted into, with a
+///    generated `From<std::sync::PoisonError<T>>` implementation for any guard,
+///    so `lock()?` works. The guard is dropped, and the variant should have no
+///    fields or a single field constructible from the message of the error.
+///  - from_panic marks the variant that panic payloads caught by
+///    `std::panic::catch_unwind` are converted into, with a generated
+///    `From<Box<dyn Any + Send>>` implementation. The variant holds the message of
+///    the panic, taken from the payload when it is a string, optionally followed
+///    by a `Box<dyn Any + Send>` field keeping the payload itself.
+///  - context marks the variant holding a message and a source describing it,
+///    such as `Context(String, Box<dyn Error + Send + Sync>)`, and generates an
+///    extension trait (the error type name with Context appended) with `context`
+///    and `with_context` methods on `Result`, wrapping any error convertible into
+///    the source together with a message, as in
+///    `fs::read(path).context("loading config")?`. When the source is an
+///    `Option`, the trait is implemented for `Option` as well.
+///
+/// Source returning works for variants with a single field, or for variants where
+///  one field is marked with `#[auto_error(source)]`. Fields referring to the
+///  error type itself (such as `Box<Self>`) are treated as sources, allowing
+///  errors to wrap other errors of the same type. Otherwise the only field whose
+///  type name ends in Error, such as the `std::io::Error` in
+///  `Read(PathBuf, std::io::Error)`, is the source, without deriving From for it.
+///  From derivation works for variants with a single field, or for variants where
+///  one field is marked with `#[auto_error(from)]`. The remaining fields are then
+///  filled in using `Default::default()`.
+///
+/// Types listed in `error_types(sqlx::Error, MyFailure)` on the enum are
+///  treated as errors regardless of their name, getting source and From just
+///  like types named Error. Listed paths match the end of the field type, so
+///  `MyFailure` also covers `crate::MyFailure`. `error_suffixes("Error", "Err",
+///  "Failure")` replaces the name check, treating types whose name ends in
+///  one of the suffixes as errors, and `heuristic = false` turns off looking
+///  at type names altogether, leaving marked sources, listed types and
+///  trait objects of Error.
+///
+/// Picking the source among several fields by type name is a breaking change
+///  for variants that had no source before. A field whose type is named like an
+///  error without implementing Error, such as a plain `struct ValidationError`,
+///  no longer compiles next to other fields. Such variants take `err = false`, or
+///  the enum takes `heuristic = false` to turn the name check off entirely.
+///
+/// Variants holding a `Vec` of errors are aggregates. Their elements are joined
+///  when displayed, the first element is returned from source, and a generated
+///  `sources` method iterates over all of them.
+///
+/// Source fields marked with `#[auto_error(arc)]` hold their error in an `Arc`,
+///  so they can be cloned. The generated `From` implementations then take the
+///  error itself (or a `Box` for trait objects) and wrap it.
+///
+/// Fields marked with `#[auto_error(boxed)]`, or the field a variant marked with
+///  it is converted from, hold a large value in a `Box` to keep the error type
+///  small. The generated `From` implementations take the value itself and box
+///  it, so callers convert from the unboxed type as before.
+///
+/// Variants with a single `Cow<'static, str>` field hold a message that is only
+///  allocated when needed. They get `From` implementations for both `&'static str`
+///  and `String`, borrowing static messages and taking ownership of the others.
+///
+/// Fields marked with `#[auto_error(backtrace)]` or `#[auto_error(location)]`
+///  capture a `std::backtrace::Backtrace`, or the `&'static std::panic::Location`
+///  where the error was created. The generated `From` implementations, constructors
+///  and context selectors fill them in rather than taking them, they are left out
+///  of the message, and generated `backtrace` and `location` methods return them.
+///  Fields marked with `#[auto_error(timestamp)]` likewise capture the time the
+///  error was created, calling `now()` on the field type, such as
+///  `std::time::SystemTime` or a type of your own, and are returned by a generated
+///  `timestamp` method.
+///  Fields marked with `#[auto_error(instance_id)]` get a unique ID generated for
+///  every error created, by calling `new()` on the field type, or the function given
+///  as in `#[auto_error(instance_id = "uuid::Uuid::new_v4")]`. A generated
+///  `instance_id` method returns it, so users can report the ID of the error they saw.
+///
+/// Fields marked with `#[auto_error(config_path)]` and `#[auto_error(config_key)]`
+///  hold the configuration file and key an error occurred in, and are appended to
+///  the message when set, as in "invalid port 70000 in config.toml at key
+///  server.port". They start out empty (or `None` when optional), and are set
+///  through the generated `in_config` and `at_key` methods, as in
+///  `err.in_config("config.toml").at_key("server.port")`. The path needs to
+///  implement `AsRef<Path>`, and the key `AsRef<str>`.
+///
+/// The derive also generates `chain` and `root_cause` methods, which respectively
+///  iterate over the error and its chain of sources, and return the last error in
+///  that chain, as well as a `variant_name` method returning the name of the variant.
+///
+/// Variants marked `#[doc(hidden)]`, such as a `__NonExhaustive` variant kept for
+///  forward compatibility, are displayed as "unknown error", and are left out of
+///  the generated accessors, constructors, context selectors, catalog and list
+///  of kinds.
+///
+/// Any `cfg` and `cfg_attr` attributes on a variant are carried over to the code
+///  generated for it, so feature gated variants work as expected.
+///
+/// A `#[deprecated]` attribute on a variant is carried over to the generated
+///  accessors, constructors, builders, context selectors and context extension
+///  trait, so code creating or inspecting the variant is warned about it. The
+///  generated implementations themselves allow its use, and trait implementations
+///  such as `From` can't be deprecated in Rust. Marking the variant with
+///  `#[auto_error(superseded_by = "NewVariant")]` names its replacement, which
+///  deprecates the generated items with a note pointing to the new variant when it
+///  isn't deprecated itself.
+///
+/// Fields disabled through a `cfg` attribute are removed before the derive sees
+///  them, and fields with a `cfg` attribute are not considered when inferring the
+///  source and `std::from::From`, so both are the same in every configuration. A
+///  `cfg_attr` can select a different format_str for each configuration.
+///
+/// The enum itself can also carry an auto_error attribute, enabling optional
+///  functionality
+///  - display_chain generates a `display_chain` method, rendering the error
+///    followed by its chain of sources as "error: ..." and "caused by: ..." lines,
+///    and a "help: ..." line when the variant has a help text.
+///  - chain_fmt appends the message of the source to the message of every variant
+///    with a source, as chain_fmt does for a single variant. Leave it off when a
+///    reporter walks the chain of sources itself, or its messages appear twice.
+///  - accessors generates `is_*`, `as_*` and `into_*` methods for every variant,
+///    named after the variant in snake case. Unit variants only get an `is_*`
+///    method.
+///  - constructors generates a constructor function for every variant, named after
+///    the variant in snake case and taking `impl Into<T>` for each field. Names
+///    that are keywords become raw identifiers, such as `r#type` for a Type variant.
+///  - track_caller marks the generated constructors with `#[track_caller]`.
+///  - builders generates a builder for every variant with three or more fields,
+///    named after the variant with Builder appended. The variant's snake case
+///    function returns an empty builder with a setter per field, named by the
+///    field's `arg` or `arg0`, `arg1`, ... otherwise, and `build()` is only
+///    available once every field is set: `Error::parse_error().line(12).column(4)
+///    .message(m).build()`. These variants get no positional constructor, and
+///    builders are not supported for generic enums.
+///  - context_selectors generates a context selector struct for every variant,
+///    named after the variant with Context appended, holding all fields but the
+///    source. Together with the generated `IntoError`, `ResultExt` and `OptionExt`
+///    traits (prefixed with the error type name) these allow adding context at
+///    the call site, as in `fs::read(path).context(ReadConfigContext(path))?`, or
+///    through `with_context` taking a closure returning the selector, which is
+///    only called on errors, so any context is only computed when needed.
+///  - debug generates a `std::fmt::Debug` implementation showing the error message
+///    followed by its chain of sources and, when captured, its backtrace, which
+///    is what gets printed when main returns the error. The enum should then not
+///    derive Debug itself.
+///  - kind generates a fieldless companion enum mirroring the variants, named
+///    after the error type with Kind appended (or the given name when set to a
+///    string), together with a `kind` method and a `From<&Error>` implementation.
+///    A `KINDS` constant lists the kinds of all variants, for enumerating every
+///    error in documentation or exhaustive tests.
+///    Kinds are parsed back from their variant name or code with `FromStr` and
+///    `TryFrom<&str>`, returning the unrecognized string as error.
+///  - eq implements `PartialEq` and `Eq` comparing only the variants of errors,
+///    ignoring their fields, so tests can check for a kind of error as in
+///    `assert_eq!(e, Error::NotFound)`. The same comparison is available as a
+///    `same_kind` method.
+///  - test_helpers generates `expect_kind` and `expect_err_kind` methods for tests,
+///    asserting that an error or result is of the given kind, as in
+///    `Error::expect_err_kind(parse(s), ErrorKind::NotFound)`. On failure they panic
+///    with the actual kind, message and chain of sources, or the value when the
+///    result is `Ok`. Requires kind to be enabled as well.
+///  - hash implements `Hash` using only the variant of errors and their code,
+///    ignoring their fields. Together with eq this allows keeping errors in a
+///    `HashSet` or `HashMap`, such as for deduplicating alerts.
+///  - macros generates `bail_*!` and `ensure_*!` macros, named after the error
+///    type in snake case, that return early with the given variant, as in
+///    `ensure_error!(line.len() < 80, TooLong(line.len()))`. The error type needs
+///    to be in scope where the macros are used.
+///  - result_alias takes a name, and generates a result type of that name with
+///    the error type as default error, as in
+///    `pub type Result<T, E = Error> = core::result::Result<T, E>`.
+///  - into takes the name of another error type, and generates a From
+///    implementation converting errors into it, as set on every variant by into.
+///    This saves writing large conversions by hand between the layers of an
+///    application, such as from domain errors to API errors.
+///  - into_variants does the same as into, but variants without into of their own
+///    convert into the variant of the same name of the other error type, passing
+///    their fields along. This keeps module level errors in sync with the crate
+///    level error containing their variants, as variants missing there fail to
+///    compile.
+///  - url takes a template for the documentation links of the variants, in which
+///    {code} is replaced by the code of the variant, as in
+///    `url = "https://docs.example.com/errors/{code}"`.
+///  - localized displays errors through a lookup function registered with the
+///    generated `set_localizer` method, which is given the message key and
+///    arguments of the error, as returned by `message_key` and `message_args`.
+///    Errors are displayed using their format string when no function is
+///    registered or it returns None, so one error type can serve both logs and
+///    translated messages.
+///  - compact formats the messages of variants with plain format strings, whose
+///    placeholders have no format specs, through the autoerror-runtime crate
+///    rather than generating the formatting code for each variant, which keeps
+///    the generated code small for error types with many variants. Requires the
+///    runtime feature, and autoerror-runtime as dependency.
+///  - format_table does the same as compact, but keeps the format strings in a
+///    static table indexed by the position of the variant, so only that index is
+///    passed to the autoerror-runtime crate for each variant. Requires the runtime
+///    feature, and autoerror-runtime as dependency. It can't be combined with
+///    compact.
+///  - no_alloc generates a `write_message` method writing the message into any
+///    `std::fmt::Write` without allocating, so errors can be displayed in panic
+///    handlers and allocators. The generated Display never allocates itself, but
+///    the fields it displays need to do the same. Localized error types look up
+///    their messages as strings, and can't be no_alloc.
+///  - heapless does the same as no_alloc, and generates a `to_heapless` method
+///    returning the message in a `heapless::String<N>`, failing when it doesn't
+///    fit. Requires the heapless feature, and heapless as dependency.
+///  - msrv takes the minimum supported Rust version of the crate, as in
+///    `msrv = "1.56"`. Parameters generating code that needs a newer compiler
+///    are then rejected: exit codes need 1.61, localized 1.63 and backtrace
+///    fields 1.65. As autoerror itself requires Rust 1.56, older versions are
+///    rejected, and on such compilers proc-macro2 and quote may need to be held
+///    back to releases that still support them. These rules are checked against
+///    the msrv, but the generated code is not built with that compiler.
+///  - generate_tests emits a test module next to the error type, with a test for
+///    every variant constructing it from placeholder values, checking that its
+///    message matches its format string and that `source` returns a source exactly
+///    when the variant has one. Variants with fields no placeholder value can be
+///    made up for are skipped. Messages of localized error types and format
+///    strings with format specs are only checked to display without failing. The
+///    tests, such as `error_autoerror_tests::io_message_and_source`, are visible
+///    to the module of the error type. Not supported for generic enums.
+///  - catalog generates an associated `CATALOG` constant describing every variant
+///    by name, code, format string and doc comment, using a generated struct named
+///    after the error type with Info appended (or the given name when set to a
+///    string).
+///  - problem_details generates a `to_problem_details` method returning the RFC
+///    7807 problem details of the error, with the status, type and title of the
+///    variant and the error message as detail, using a generated struct named
+///    after the error type with ProblemDetails appended (or the given name when
+///    set to a string).
+///  - jsonrpc generates `jsonrpc_code` and `to_jsonrpc_error` methods, the latter
+///    returning a JSON-RPC 2.0 error object with the code of the variant, the
+///    error message, and the message of the source as data. The object uses a
+///    generated struct named after the error type with JsonRpcError appended (or
+///    the given name when set to a string), which can also be converted into
+///    from the error.
+///  - termination generates a wrapper around `Result<(), Error>` implementing
+///    `std::process::Termination`, named after the error type with Termination
+///    appended (or the given name when set to a string). Returned from `main`, it
+///    prints the error and its chain of sources and exits with the exit code of
+///    the variant.
+///  - ffi generates a fieldless `#[repr(C)]` companion enum mirroring the variants,
+///    numbered from 1 in declaration order, named after the error type with Code
+///    appended (or the given name when set to a string), together with an
+///    `ffi_code` method.
+///  - last_error, used together with ffi, keeps a thread local last error set
+///    through `set_last_error`, and read through `take_last_error` and
+///    `with_last_error`. It also exports the C functions `*_last_error_code`,
+///    `*_error_message` and `*_error_message_free`, prefixed with the error type
+///    name in snake case (or the given prefix when set to a string).
+///  - integrations takes a list of the ecosystem integrations below, as in
+///    `integrations(serde, tracing, metrics = "app_errors")`, which is the same as
+///    giving them one by one but rejects anything else. Each of them needs the
+///    cargo feature of autoerror it is named after, reporting which one is missing
+///    otherwise (log_kv and emit_log need the log feature, deserialize the serde
+///    feature).
+///  - actix generates an `actix_web::ResponseError` implementation responding
+///    with the status of the variant and the error message as plain text body.
+///    Requires the actix feature.
+///  - warp generates a `warp::reject::Reject` implementation, together with an
+///    `into_rejection` method. Requires the warp feature.
+///  - rocket generates a `rocket::response::Responder` implementation responding
+///    with the status of the variant and the error message as body. Requires the
+///    rocket feature.
+///  - tonic generates a conversion into `tonic::Status`, with the gRPC code of
+///    the variant and the error message. Requires the tonic feature.
+///  - pyo3 generates a conversion into `pyo3::PyErr`, raising the Python exception
+///    of the variant with the error message. Requires the pyo3 feature.
+///  - napi generates a conversion into `napi::Error`, with the N-API status of
+///    the variant and the error message as reason. Requires the napi feature.
+///  - log generates a `log_level` method returning the log level of the variant.
+///    emit_log does the same, and additionally logs every error created through
+///    the generated `From` implementations and constructors at that level.
+///    log_kv also does the same, and additionally implements `log::kv::ToValue`
+///    and `log::kv::Source`, so structured loggers get the error with its chain of
+///    sources, or as kind, code, message and source pairs. Requires the log
+///    feature, and log with its kv_std feature as dependency for log_kv.
+///  - slog implements `slog::Value` and `slog::KV`, the latter giving the error as
+///    kind, code, message and source pairs, as in `error!(log, "failed"; e)`.
+///    Requires the slog feature.
+///  - valuable implements `valuable::Valuable` and `valuable::Structable`, giving
+///    the error as a struct of its kind, code and message, which tracing records
+///    as structured value through `tracing::field::valuable`. Requires the
+///    valuable feature, and valuable as dependency.
+///  - tracing generates a `record` method emitting a tracing event at the log
+///    level of the variant, with the variant name as kind, the code when given,
+///    and the error message, as well as an `as_value` method for recording the
+///    error in the fields of other events. Requires the tracing feature.
+///  - otel generates a `record_exception` method recording the error as an
+///    exception event on the current OpenTelemetry span, with the variant name as
+///    type, the error message and the code when given, and setting the status of
+///    the span to error. Requires the otel feature, and opentelemetry as
+///    dependency.
+///  - metrics increments a counter from the metrics crate for every error created
+///    through the generated `From` implementations and constructors, labeled with
+///    the variant name as kind and the code when given. The counter is named after
+///    the error type in snake case with _total appended, or the given name when set
+///    to a string. Requires the metrics feature.
+///  - sentry generates a `capture` method reporting the error and its chain of
+///    sources to Sentry, with a fingerprint made of the error type name and the
+///    code of the variant, or its name when it has no code. Requires the sentry
+///    feature.
+///  - miette implements `miette::Diagnostic` for the error, with the code,
+///    severity, help and url of the variant. A field marked with
+///    `#[auto_error(source_code)]` provides the source code, and fields marked
+///    with `#[auto_error(label)]` or `#[auto_error(label = "...")]` the labeled
+///    spans within it. Requires the miette feature.
+///  - async_graphql generates an `async_graphql::ErrorExtensions` implementation,
+///    adding the variant name, and the code and status when given, to the
+///    extensions of the GraphQL error. Requires the async_graphql feature.
+///  - serde implements `serde::Serialize` for the error, as an object with the
+///    variant name as kind, the code when given, the error message, and the
+///    messages of its chain of sources as source. The generated problem details
+///    and JSON-RPC error objects derive `serde::Serialize` as well. Requires the
+///    serde feature, and serde with its derive feature as dependency.
+///  - deserialize does the same as serde, but additionally includes the fields of
+///    the variant when serializing, and implements `serde::Deserialize` turning
+///    that back into the error. Errors of a known kind without fields are
+///    rejected.
+///  - schemars implements `schemars::JsonSchema` describing the serialized form of
+///    the error, so it can be included in OpenAPI documents. The generated problem
+///    details and JSON-RPC error objects derive it as well. Requires serde to be
+///    enabled, the schemars feature, and schemars as dependency.
+///  - arbitrary implements `arbitrary::Arbitrary`, picking a variant and filling
+///    in its fields from the unstructured data, so fuzzers and property tests can
+///    exercise every variant. Captured fields are filled in as when the error is
+///    created. Requires the arbitrary feature, and arbitrary as dependency, and all
+///    other fields to implement `Arbitrary`.
+///  - validator displays fields holding `validator::ValidationErrors` as a summary
+///    of the fields that failed, as in "email: must be a valid email; name:
+///    length", using the message of each validation or else its code. The
+///    generated `validation_errors` method returns these messages by field path,
+///    which the serde integration includes as `validation`, and the problem
+///    details as `errors`. Requires the validator feature.
+///
+/// Generic enums are supported. Fields whose type mentions a type parameter
+///  are required to implement `std::fmt::Display`, and `std::error::Error`
+///  when they are returned from source. Type parameters only used in fields
+///  left out of the message can be listed in `no_bound(T, U)` on the enum to
+///  skip these bounds, and `bound = "T: MyTrait"` replaces the inferred bounds
+///  with the given ones.
+///
+/// # Example
+///
+/// ```
+/// use autoerror::AutoError;
+///
+/// #[derive(Debug, AutoError)]
+/// enum Error {
+///     #[auto_error(format_str="Document not found")]
+///     NotFound,
+///     IO(std::io::Error),
+///     #[auto_error(make_from=true)]
+///     Other(String),
+/// }
+/// ```
+#[proc_macro_derive(AutoError, attributes(auto_error))]
+pub fn derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let enumdecl = if let syn::Data::Enum(e) = input.data {
+        e
+    } else {
+        return TokenStream::from(syn::Error::new_spanned(&input.ident, "AutoError only supports enums").to_compile_error());
+    };
+
+    // Errors in the attributes of the enum and all its variants are
+    //  reported together
+    let mut errors = TokenStream::new();
+    let enum_attrs = parse_enum_attrs(&input.attrs, &input.ident);
+    if let Err(e) = &enum_attrs {
+        errors.extend(e.clone());
+    }
+
+    let non_exhaustive = input.attrs.iter().any(|attr| attr.path.is_ident("non_exhaustive"));
+    let repr = input.attrs.iter().filter(|attr| attr.path.is_ident("repr")).filter_map(|attr| match attr.parse_meta() {
+        Ok(syn::Meta::List(list)) => Some(list.nested),
+        _ => None,
+    }).flatten().find_map(|arg| match arg {
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if REPR_TYPES.iter().any(|ty| path.is_ident(ty)) => path.get_ident().cloned(),
+        _ => None,
+    });
+    let error_ident = input.ident;
+    let vis = input.vis;
+    let generics = input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let heuristic = enum_attrs.as_ref().map(|enum_attrs| enum_attrs.heuristic.clone()).unwrap_or_default();
+    let mut error_variants = vec![];
+    for v in enumdecl.variants.iter() {
+        match parse_variant(v, &error_ident, &heuristic) {
+            Ok(var) => error_variants.push(var),
+            Err(e) => errors.extend(e),
+        }
+    }
+    let enum_attrs = match enum_attrs {
+        Ok(enum_attrs) => enum_attrs,
+        Err(_) => { return errors; },
+    };
+    // The checks across variants below only see the variants that parsed,
+    //  so those requiring some variant to be present are skipped otherwise
+    let all_variants = errors.is_empty();
+    // Variants append their source to their message as set on the enum,
+    //  unless they say otherwise
+    for var in error_variants.iter_mut() {
+        var.chain_fmt = var.chain_fmt.or(Some(enum_attrs.chain_fmt));
+    }
+    for var in error_variants.iter() {
+        if let Some(new) = &var.superseded_by {
+            if !enumdecl.variants.iter().any(|v| v.ident == *new) {
+                errors.extend(TokenStream::from(syn::Error::new_spanned(new, "Unknown variant").to_compile_error()));
+            }
+        }
+    }
+    // With the validator integration, validation errors are displayed as a
+    //  summary of the fields that failed
+    if enum_attrs.validator {
+        for var in error_variants.iter_mut() {
+            var.validation_fields = payload_fields(var).into_iter()
+                .filter(|i| is_validation_errors(&var.variant.fields.iter().nth(*i).unwrap().ty))
+                .collect();
+        }
+    }
+
+    // Generated code that needs a newer compiler than the msrv is rejected,
+    //  rather than failing to compile on the older compiler later. Format
+    //  strings need no entry, as their fields are passed explicitly.
+    if let Some((major, minor)) = enum_attrs.msrv {
+        let requirements = [
+            ((1, 61), "Exit codes", enum_attrs.termination.is_some() || error_variants.iter().any(|var| var.exit_code.is_some())),
+            ((1, 63), "Localized error types", enum_attrs.localized),
+            ((1, 65), "Backtrace fields", error_variants.iter().any(|var| var.backtrace_field.is_some())),
+        ];
+        for ((req_major, req_minor), what, used) in requirements.iter() {
+            if *used && (major, minor) < (*req_major, *req_minor) {
+                let message = format!("{} require Rust {}.{}, which is newer than the msrv of {}.{}", what, req_major, req_minor, major, minor);
+                errors.extend(TokenStream::from(syn::Error::new_spanned(&error_ident, message).to_compile_error()));
+            }
+        }
+    }
+    // Sentry events are grouped by the error type and the code of the
+    //  variant, or its name when it has no code
+    let sentry_capture = |var: &ErrorVariant, error: proc_macro2::TokenStream| {
+        let name = error_ident.unraw().to_string();
+        let group = var.code.as_ref().map_or_else(|| var.variant.ident.unraw().to_string(), |code| code.value());
+        quote!{
+            ::sentry::with_scope(
+                |scope| scope.set_fingerprint(::std::option::Option::Some(&[#name, #group])),
+                || ::sentry::capture_error(#error),
+            )
+        }
+    };
+
+    // Newly created errors are logged at the level of their variant when
+    //  asked to, which requires them to be displayable, counted per variant
+    //  when metrics are enabled, and reported to Sentry when marked so.
+    let on_created = |var: &ErrorVariant, value: proc_macro2::TokenStream| {
+        let report = enum_attrs.sentry && var.report;
+        if !enum_attrs.emit_log && enum_attrs.metrics.is_none() && !report {
+            return value;
+        }
+        let log = if enum_attrs.emit_log {
+            let level = var.log_level.clone().unwrap_or_else(|| format_ident!("Error"));
+            Some(quote!{ ::log::log!(::log::Level::#level, "{}", error); })
+        } else {
+            None
+        };
+        let metrics = enum_attrs.metrics.as_ref().map(|counter| {
+            let name = var.variant.ident.unraw().to_string();
+            let code = var.code.as_ref().map(|code| quote!{ , "code" => #code });
+            quote!{ ::metrics::counter!(#counter, "kind" => #name #code).increment(1); }
+        });
+        let capture = if report {
+            let capture = sentry_capture(var, quote!{ &error });
+            Some(quote!{ #capture; })
+        } else {
+            None
+        };
+        quote!{{
+            let error = #value;
+            #log
+            #metrics
+            #capture
+            error
+        }}
+    };
+    let mut from_generics = generics.clone();
+    if enum_attrs.emit_log {
+        from_generics.make_where_clause().predicates.push(syn::parse_quote!{ Self: ::std::fmt::Display });
+    }
+    if enum_attrs.sentry && error_variants.iter().any(|var| var.report) {
+        from_generics.make_where_clause().predicates.push(syn::parse_quote!{ Self: ::std::error::Error });
+    }
+    let from_where = &from_generics.where_clause;
+
+    // Variants converted from the same type would give conflicting From
+    //  implementations, unless they are compiled in under different cfgs
+    let mut from_types: Vec<(String, String, &syn::Ident)> = vec![];
+    for var in error_variants.iter() {
+        for sourcetype in from_source_types(var) {
+            let sourcetype = type_name(&sourcetype);
+            let cfgs = &var.cfgs;
+            let cfgs = quote!{ #(#cfgs)* }.to_string();
+            if let Some((_, _, other)) = from_types.iter().find(|(t, c, _)| *t == sourcetype && *c == cfgs) {
+                let message = format!(
+                    "Conflicting From<{}> implementations for {} and {}, set make_from=false on one of them",
+                    sourcetype, other, var.variant.ident,
+                );
+                errors.extend(TokenStream::from(syn::Error::new_spanned(&var.variant.ident, message).to_compile_error()));
+            }
+            from_types.push((sourcetype, cfgs, &var.variant.ident));
+        }
+    }
+
+    // Poisoned locks are converted into the variant marked from_poison for
+    //  any guard, which is dropped, keeping just the message if asked to
+    let poison_variants: Vec<_> = error_variants.iter().filter(|var| var.from_poison).collect();
+    if poison_variants.len() > 1 {
+        errors.extend(TokenStream::from(syn::Error::new_spanned(poison_variants[1].variant, "Only one variant can be marked as from_poison").to_compile_error()));
+    }
+    let poison_impl = poison_variants.first().map(|var| {
+        let curvar = &var.variant.ident;
+        let cfgs = &var.cfgs;
+        let payload = payload_fields(var);
+        let args = (0..var.variant.fields.len()).map(|i| {
+            if payload.contains(&i) {
+                quote!{ ::std::convert::From::from(::std::string::ToString::to_string(&e)) }
+            } else {
+                field_value(var, i)
+            }
+        });
+        let value = match var.variant.fields {
+            syn::Fields::Unit => quote!{ Self::#curvar },
+            _ => quote!{ Self::#curvar(#(#args),*) },
+        };
+        let body = on_created(var, value);
+        let track_caller = location_track_caller(var);
+
+        let mut poison_generics = from_generics.clone();
+        poison_generics.params.push(syn::parse_quote!{ AutoErrorGuard });
+        let (poison_impl_generics, _, poison_where) = poison_generics.split_for_impl();
+        quote!{
+            #(#cfgs)*
+            impl #poison_impl_generics ::std::convert::From<::std::sync::PoisonError<AutoErrorGuard>> for #error_ident #ty_generics #poison_where {
+                #track_caller
+                fn from (e: ::std::sync::PoisonError<AutoErrorGuard>) -> Self {
+                    #body
+                }
+            }
+        }
+    });
+
+    // Panic payloads caught by catch_unwind are converted into the variant
+    //  marked from_panic, taking the message from the payload when it is a
+    //  string, like the default panic hook does
+    let panic_variants: Vec<_> = error_variants.iter().filter(|var| var.from_panic).collect();
+    if panic_variants.len() > 1 {
+        errors.extend(TokenStream::from(syn::Error::new_spanned(panic_variants[1].variant, "Only one variant can be marked as from_panic").to_compile_error()));
+    }
+    let panic_impl = panic_variants.first().map(|var| {
+        let curvar = &var.variant.ident;
+        let cfgs = &var.cfgs;
+        let payload = payload_fields(var);
+        let args = (0..var.variant.fields.len()).map(|i| {
+            if i == payload[0] {
+                quote!{ ::std::convert::From::from(message) }
+            } else if payload.get(1) == Some(&i) {
+                quote!{ e }
+            } else {
+                field_value(var, i)
+            }
+        });
+        let body = on_created(var, quote!{ Self::#curvar(#(#args),*) });
+        let track_caller = location_track_caller(var);
+        quote!{
+            #(#cfgs)*
+            impl #impl_generics ::std::convert::From<::std::boxed::Box<dyn ::std::any::Any + ::std::marker::Send>> for #error_ident #ty_generics #from_where {
+                #track_caller
+                fn from (e: ::std::boxed::Box<dyn ::std::any::Any + ::std::marker::Send>) -> Self {
+                    let message = if let ::std::option::Option::Some(message) = e.downcast_ref::<&'static str>() {
+                        ::std::string::ToString::to_string(message)
+                    } else if let ::std::option::Option::Some(message) = e.downcast_ref::<::std::string::String>() {
+                        ::std::clone::Clone::clone(message)
+                    } else {
+                        ::std::string::String::from("Box<dyn Any>")
+                    };
+                    #body
+                }
+            }
+        }
+    });
+
+    // The marked field (or the only field) is taken from the source
+    //  value, any other fields get their default value. Fields holding
+    //  their error in an Arc or Box are converted from the error itself.
+    let from_value = |var: &ErrorVariant| {
+        let curvar = &var.variant.ident;
+        if payload_fields(var).is_empty() {
+            let args = (0..var.variant.fields.len()).map(|i| field_value(var, i));
+            return match var.variant.fields {
+                syn::Fields::Unit => quote!{ Self::#curvar },
+                _ => quote!{ Self::#curvar(#(#args),*) },
+            };
+        }
+        let from_field = var.from_field.unwrap_or(0);
+        let fieldtype = &var.variant.fields.iter().nth(from_field).unwrap().ty;
+        let shared = var.arc_fields.contains(&from_field) || var.boxed_fields.contains(&from_field);
+        let cow = is_cow_str(fieldtype);
+        let args: Vec<_> = (0..var.variant.fields.len()).map(|i| {
+            if i == from_field && shared {
+                share_expr(fieldtype, quote!{ e })
+            } else if i == from_field && cow {
+                quote!{ ::std::borrow::Cow::from(e) }
+            } else if i == from_field {
+                quote!{ e }
+            } else {
+                field_value(var, i)
+            }
+        }).collect();
+        quote!{ Self::#curvar(#(#args),*) }
+    };
+
+    // Variants can be routed classes of errors (such as those of sqlx),
+    //  falling back on the variant marked for other errors, or the one
+    //  converted from them
+    let mut route_fallbacks: Vec<&ErrorVariant> = vec![];
+    let mut route_impls = vec![];
+    for routing in ROUTINGS.iter() {
+        let class_of = |var: &ErrorVariant| var.routes.iter()
+            .find(|(other, _)| std::ptr::eq(*other, routing))
+            .map(|(_, class)| class.clone());
+        let routes: Vec<_> = error_variants.iter()
+            .filter_map(|var| class_of(var).filter(|class| class.value() != "other").map(|class| (var, class)))
+            .collect();
+        for (i, (_, class)) in routes.iter().enumerate() {
+            if routes[..i].iter().any(|(_, other)| other.value() == class.value()) {
+                let message = format!("Only one variant can be routed each class of {} errors", routing.name);
+                errors.extend(TokenStream::from(syn::Error::new_spanned(class, message).to_compile_error()));
+            }
+        }
+        let others: Vec<_> = error_variants.iter()
+            .filter(|var| class_of(var).map_or(false, |class| class.value() == "other"))
+            .collect();
+        if others.len() > 1 {
+            let message = format!("Only one variant can be marked for other {} errors", routing.name);
+            errors.extend(TokenStream::from(syn::Error::new_spanned(others[1].variant, message).to_compile_error()));
+        }
+        if routes.is_empty() {
+            continue;
+        }
+        let fallback = others.first().copied().or_else(|| error_variants.iter().find(|var| {
+            from_source_types(var).iter().any(|ty| type_name(ty).trim_start_matches("::") == routing.error_type)
+        }));
+        let fallback = match fallback {
+            Some(fallback) => fallback,
+            None if !all_variants => continue,
+            None => {
+                let message = format!(
+                    "Routing {0} errors needs a variant converted from {1}, or one marked with {0} = \"other\", for the remaining errors",
+                    routing.name, routing.error_type,
+                );
+                errors.extend(TokenStream::from(syn::Error::new_spanned(routes[0].0.variant, message).to_compile_error()));
+                continue;
+            }
+        };
+        let checks = routes.iter().map(|(var, class)| {
+            let condition = (routing.condition)(&class.value());
+            let body = on_created(var, from_value(var));
+            let cfgs = &var.cfgs;
+            quote!{
+                #(#cfgs)*
+                {
+                    if #condition {
+                        return #body;
+                    }
+                }
+            }
+        });
+        let sourcetype = from_source_types(fallback).remove(0);
+        let body = on_created(fallback, from_value(fallback));
+        let cfgs = &fallback.cfgs;
+        let track_caller = routes.iter().map(|(var, _)| *var).chain(std::iter::once(fallback)).find_map(location_track_caller);
+        route_impls.push(quote!{
+            #(#cfgs)*
+            impl #impl_generics ::std::convert::From<#sourcetype> for #error_ident #ty_generics #from_where {
+                #track_caller
+                fn from (e: #sourcetype) -> Self {
+                    #(#checks)*
+                    #body
+                }
+            }
+        });
+        route_fallbacks.push(fallback);
+    }
+
+    let from_impls = error_variants.iter().flat_map(|var| {
+        if !var.make_from || route_fallbacks.iter().any(|fallback| std::ptr::eq(*fallback, var)) {
+            return vec![];
+        }
+
+        let cfgs = &var.cfgs;
+        let body = on_created(var, from_value(var));
+        let track_caller = location_track_caller(var);
+
+        from_source_types(var).into_iter().map(|sourcetype| quote!{
+            #(#cfgs)*
+            impl #impl_generics ::std::convert::From<#sourcetype> for #error_ident #ty_generics #from_where {
+                #track_caller
+                fn from (e: #sourcetype) -> Self {
+                    #body
+                }
+            }
+        }).collect()
+    });
+
+    let tuple_from_impls = error_variants.iter().map(|var| {
+        if !var.from_tuple {
+            return None;
+        }
+
+        let sourcetype = from_source_types(var).remove(0);
+        let curvar = &var.variant.ident;
+        let cfgs = &var.cfgs;
+        let mut tuple_index = 0;
+        let args: Vec<_> = var.variant.fields.iter().enumerate().map(|(i, field)| {
+            if is_captured(var, i) {
+                return field_value(var, i);
+            }
+            let index = syn::Index::from(tuple_index);
+            tuple_index += 1;
+            if var.arc_fields.contains(&i) || var.boxed_fields.contains(&i) {
+                share_expr(&field.ty, quote!{ e.#index })
+            } else {
+                quote!{ e.#index }
+            }
+        }).collect();
+        let body = on_created(var, quote!{ Self::#curvar(#(#args),*) });
+        let track_caller = location_track_caller(var);
+
+        Some(quote!{
+            #(#cfgs)*
+            impl #impl_generics ::std::convert::From<#sourcetype> for #error_ident #ty_generics #from_where {
+                #track_caller
+                fn from (e: #sourcetype) -> Self {
+                    #body
+                }
+            }
+        })
+    });
+
+    // With format_table, variants with plain format strings get an entry in
+    //  a table of format strings, indexed by their position in the enum
+    let table_index = |var: &ErrorVariant| {
+        if !enum_attrs.format_table || var.hidden || !is_plain_variant(var) {
+            return None;
+        }
+        error_variants.iter().position(|other| other.variant.ident == var.variant.ident)
+    };
+    let display_table = if error_variants.iter().any(|var| table_index(var).is_some()) {
+        let formats = error_variants.iter().map(|var| {
+            match table_index(var) {
+                Some(_) => var.format_str.clone(),
+                None => String::new(),
+            }
+        });
+        let count = error_variants.len();
+        Some(quote!{
+            static AUTOERROR_FORMATS: [&str; #count] = [#(#formats),*];
+        })
+    } else {
+        None
+    };
+
+    // Hidden variants are not displayed, and only hit the catch-all arm
+    let display_branches = error_variants.iter().filter(|var| !var.hidden).map(|var| {
+        let curvar = &var.variant.ident;
+        let cfgs = &var.cfgs;
+        let params: Vec<_> = var.variant.fields.iter().enumerate().map(|(i, _field)| {
+            field_binding(i)
+        }).collect();
+        // Fields are referred to by name when possible, passing them
+        //  positionally otherwise. Named fields are passed explicitly, as
+        //  identifiers captured in format strings need Rust 1.58
+        let payload = payload_fields(var);
+        let (format_str, used, args) = match rewrite_format_str(&var.format_str, &payload, params.len()) {
+            Some((format_str, used)) => {
+                let args: Vec<_> = params.iter().zip(used.iter()).filter(|(_, used)| **used).map(|(param, _)| quote!{ #param = #param }).collect();
+                (format_str, used, args)
+            }
+            None => {
+                let used: Vec<_> = (0..params.len()).map(|i| payload.contains(&i)).collect();
+                let args = payload.iter().map(|i| {
+                    let param = &params[*i];
+                    quote!{ #param }
+                }).collect();
+                (var.format_str.clone(), used, args)
+            }
+        };
+        let appended = appended_source(var);
+        let config = [var.config_path_field, var.config_key_field];
+        let bindings = params.iter().zip(used.iter()).enumerate().map(|(i, (param, used))| {
+            if *used || appended == Some(i) || config.contains(&Some(i)) {
+                quote!{ #param }
+            } else {
+                quote!{ _ }
+            }
+        });
+        // In compact mode, variants with plain format strings are formatted
+        //  by the runtime crate, which takes the payload fields by position.
+        //  With format_table, it is given their entry in the table instead.
+        let table_entry = table_index(var);
+        if (enum_attrs.compact && is_plain_variant(var)) || table_entry.is_some() {
+            let template = match table_entry {
+                Some(index) => quote!{ AUTOERROR_FORMATS[#index] },
+                None => {
+                    let template = &var.format_str;
+                    quote!{ #template }
+                }
+            };
+            let bindings = (0..params.len()).map(|i| {
+                if payload.contains(&i) {
+                    let param = &params[i];
+                    quote!{ #param }
+                } else {
+                    quote!{ _ }
+                }
+            });
+            let args = payload.iter().map(|i| &params[*i]);
+            let pattern = match var.variant.fields {
+                syn::Fields::Unit => quote!{ Self::#curvar },
+                _ => quote!{ Self::#curvar(#(#bindings),*) },
+            };
+            return quote!{
+                #(#cfgs)*
+                #pattern => ::autoerror_runtime::write_message(f, #template, &[#(#args as &dyn ::std::fmt::Display),*]),
+            };
+        }
+
+        // Aggregates and Vec fields given a separator are displayed by
+        //  joining their elements
+        let separator = separator(var);
+        let joins = params.iter().zip(used.iter()).enumerate().filter(|(i, (_, used))| **used && is_joined(var, *i)).map(|(_, (param, _))| {
+            quote!{ let #param = AutoErrorJoin(#param, #separator); }
+        });
+        let validations = var.validation_fields.iter().filter(|i| used[**i]).map(|i| {
+            let param = &params[*i];
+            quote!{ let #param = &AutoErrorValidation(#param); }
+        });
+        // The source is appended after the message when asked to, but only
+        //  when present for optional sources
+        let append = appended.map(|i| {
+            let param = &params[i];
+            let ty = &var.variant.fields.iter().nth(i).unwrap().ty;
+            if strip_option(ty).is_some() {
+                quote!{
+                    if let ::std::option::Option::Some(source) = #param {
+                        ::std::write!(f, ": {}", source)?;
+                    }
+                }
+            } else {
+                quote!{ ::std::write!(f, ": {}", #param)?; }
+            }
+        });
+        // The configuration file and key are written after the message, when
+        //  set, as in "invalid port in config.toml at key server.port"
+        let config_suffix = [(var.config_path_field, true), (var.config_key_field, false)].iter().filter_map(|(field, is_path)| {
+            let i = (*field)?;
+            let param = &params[i];
+            let ty = &var.variant.fields.iter().nth(i).unwrap().ty;
+            let write = if *is_path {
+                quote!{
+                    let value: &::std::path::Path = ::std::convert::AsRef::as_ref(value);
+                    if !value.as_os_str().is_empty() {
+                        ::std::write!(f, " in {}", value.display())?;
+                    }
+                }
+            } else {
+                quote!{
+                    let value: &str = ::std::convert::AsRef::as_ref(value);
+                    if !value.is_empty() {
+                        ::std::write!(f, " at key {}", value)?;
+                    }
+                }
+            };
+            Some(if strip_option(ty).is_some() {
+                quote!{
+                    if let ::std::option::Option::Some(value) = #param {
+                        #write
+                    }
+                }
+            } else {
+                quote!{
+                    let value = #param;
+                    #write
+                }
+            })
+        }).collect::<Vec<_>>();
+        let append = match append {
+            Some(append) => Some(quote!{ #(#config_suffix)* #append }),
+            None if !config_suffix.is_empty() => Some(quote!{ #(#config_suffix)* }),
+            None => None,
+        };
+        // Variants delegating to a field pass the formatter on to it, keeping
+        //  its flags such as alternate, width and precision
+        let write = match delegated_field(var) {
+            Some(i) => {
+                let param = &params[i];
+                quote!{ ::std::fmt::Display::fmt(#param, f) }
+            },
+            None => quote!{ f.write_fmt(::std::format_args!(#format_str #(,#args)*)) },
+        };
+        match var.variant.fields {
+            syn::Fields::Unnamed(_) if append.is_some() => quote!{
+                #(#cfgs)*
+                Self::#curvar(#(#bindings),*) => {
+                    #(#joins)*
+                    #(#validations)*
+                    #write?;
+                    #append
+                    ::std::result::Result::Ok(())
+                },
+            },
+            syn::Fields::Unnamed(_) => quote!{
+                #(#cfgs)*
+                Self::#curvar(#(#bindings),*) => {
+                    #(#joins)*
+                    #(#validations)*
+                    #write
+                },
+            },
+            syn::Fields::Unit => quote!{
+                #(#cfgs)*
+                Self::#curvar => f.write_fmt(::std::format_args!(#format_str)),
+            },
+            _ => panic!("Internal error (AutoError)")
+        }
+        
+    });
+
+    // Validation errors are displayed as the messages of the fields that
+    //  failed, rather than by their own Display listing all parameters
+    let display_validation = if error_variants.iter().any(|var| !var.validation_fields.is_empty()) {
+        let fields_fn = validation_fields_fn();
+        Some(quote!{
+            struct AutoErrorValidation<'a>(&'a ::validator::ValidationErrors);
+
+            impl ::std::fmt::Display for AutoErrorValidation<'_> {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    #fields_fn
+                    let mut fields = ::std::collections::BTreeMap::new();
+                    autoerror_validation_fields(self.0, "", &mut fields);
+                    for (i, (path, messages)) in fields.iter().enumerate() {
+                        if i != 0 {
+                            f.write_str("; ")?;
+                        }
+                        ::std::write!(f, "{}: {}", path, messages.join(", "))?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    let display_localized = if enum_attrs.localized {
+        Some(quote!{
+            if let ::std::option::Option::Some(message) = self.localized_message() {
+                return f.write_str(&message);
+            }
+        })
+    } else {
+        None
+    };
+
+    let display_fallback = if error_variants.iter().any(|var| var.hidden) {
+        Some(quote!{ _ => f.write_str("unknown error"), })
+    } else {
+        None
+    };
+
+    // Aggregate variants get all their elements returned from sources
+    let aggregate_branches: Vec<_> = error_variants.iter().filter_map(|var| {
+        if !var.err {
+            return None;
+        }
+        let source_field = var.source_field.unwrap();
+        let ty = &var.variant.fields.iter().nth(source_field).unwrap().ty;
+        let inner = source_expr(strip_vec(ty)?, var.source_as_ref);
+        let curvar = &var.variant.ident;
+        let cfgs = &var.cfgs;
+        let params = (0..var.variant.fields.len()).map(|i| {
+            if i == source_field {
+                quote!{ e }
+            } else {
+                quote!{ _ }
+            }
+        });
+        Some(quote!{
+            #(#cfgs)*
+            Self::#curvar(#(#params),*) => ::std::boxed::Box::new(e.iter().filter_map(|e| #inner)),
+        })
+    }).collect();
+
+    let source_branches = error_variants.iter().map(|var| {
+        if !var.err {
+            return None;
+        }
+        let curvar = &var.variant.ident;
+        let cfgs = &var.cfgs;
+        let source_field = var.source_field.unwrap();
+        let source = source_expr(&var.variant.fields.iter().nth(source_field).unwrap().ty, var.source_as_ref);
+        let params = (0..var.variant.fields.len()).map(|i| {
+            if i == source_field {
+                quote!{ e }
+            } else {
+                quote!{ _ }
+            }
+        });
+        Some(quote!{
+            #(#cfgs)*
+            Self::#curvar(#(#params),*) => #source,
+        })
+    });
+
+    // The wildcard arm is only needed when some variants have no source,
+    //  as it would be unreachable otherwise
+    let source_body = if error_variants.iter().all(|var| !var.err) {
+        quote!{ ::std::option::Option::None }
+    } else if error_variants.iter().all(|var| var.err) {
+        quote!{
+            match self {
+                #(#source_branches)*
+            }
+        }
+    } else {
+        quote!{
+            match self {
+                #(#source_branches)*
+                _ => ::std::option::Option::None,
+            }
+        }
+    };
+
+    // Type parameters marked no_bound get no inferred bounds, and bounds
+    //  given explicitly replace the inferred ones altogether.
+    if let Some(param) = enum_attrs.no_bound.iter().find(|param| !generics.type_params().any(|p| p.ident == **param)) {
+        errors.extend(TokenStream::from(syn::Error::new_spanned(param, "Unknown type parameter").to_compile_error()));
+    }
+    let bounded_generics = syn::Generics {
+        params: generics.params.iter().filter(|param| match param {
+            syn::GenericParam::Type(param) => !enum_attrs.no_bound.contains(&param.ident),
+            _ => true,
+        }).cloned().collect(),
+        ..generics.clone()
+    };
+
+    // Fields involving type parameters need bounds for the generated
+    //  Display and Error implementations to typecheck.
+    let mut display_predicates: Vec<syn::WherePredicate> = vec![];
+    let mut source_predicates: Vec<syn::WherePredicate> = vec![];
+    for var in error_variants.iter().filter(|_| enum_attrs.bound.is_none()) {
+        for (i, field) in var.variant.fields.iter().enumerate() {
+            // References to the error type itself are covered by the
+            //  implementations being generated, but need to be 'static
+            //  when returned as a source.
+            if is_self_type(&field.ty, &error_ident) {
+                if var.err && var.source_field == Some(i) && !generics.params.is_empty() {
+                    source_predicates.push(syn::parse_quote!{ Self: 'static });
+                }
+                continue;
+            }
+            if !uses_type_params(&field.ty, &bounded_generics) {
+                continue;
+            }
+            let ty = if is_joined(var, i) { strip_vec(&field.ty).unwrap() } else { &field.ty };
+            display_predicates.push(syn::parse_quote!{ #ty: ::std::fmt::Display });
+            if var.err && var.source_field == Some(i) {
+                if var.source_as_ref {
+                    let ty = strip_option(ty).unwrap_or(ty);
+                    source_predicates.push(syn::parse_quote!{ #ty: ::std::convert::AsRef<dyn ::std::error::Error + 'static> });
+                } else {
+                    let ty = source_type(ty);
+                    source_predicates.push(syn::parse_quote!{ #ty: ::std::error::Error + 'static });
+                }
+            }
+        }
+    }
+    if let Some(bound) = &enum_attrs.bound {
+        display_predicates.extend(bound.iter().cloned());
+        source_predicates.extend(bound.iter().cloned());
+    }
+    let mut display_generics = generics.clone();
+    display_generics.make_where_clause().predicates.extend(display_predicates.iter().cloned());
+    let mut error_generics = generics.clone();
+    let error_where = error_generics.make_where_clause();
+    error_where.predicates.push(syn::parse_quote!{ Self: ::std::fmt::Debug + ::std::fmt::Display });
+    error_where.predicates.extend(source_predicates.iter().cloned());
+    // The Debug implementation can't depend on Self: Error, as that
+    //  requires Debug in turn.
+    let mut debug_generics = generics.clone();
+    let debug_where = debug_generics.make_where_clause();
+    debug_where.predicates.extend(display_predicates.iter().cloned());
+    debug_where.predicates.extend(source_predicates.iter().cloned());
+    let display_where = &display_generics.where_clause;
+    let error_where = &error_generics.where_clause;
+    let debug_where = &debug_generics.where_clause;
+
+    let debug_impl = if enum_attrs.debug {
+        // The backtrace is shown after the sources when it was captured, as
+        //  anyhow does
+        let backtrace = if error_variants.iter().any(|var| var.backtrace_field.is_some()) {
+            Some(quote!{
+                if let ::std::option::Option::Some(backtrace) = self.backtrace() {
+                    if backtrace.status() == ::std::backtrace::BacktraceStatus::Captured {
+                        ::std::write!(f, "\n\nStack backtrace:\n{}", backtrace)?;
+                    }
+                }
+            })
+        } else {
+            None
+        };
+        Some(quote!{
+            impl #impl_generics ::std::fmt::Debug for #error_ident #ty_generics #debug_where {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    ::std::fmt::Display::fmt(self, f)?;
+                    let mut source = ::std::error::Error::source(self);
+                    if source.is_some() {
+                        f.write_str("\n\nCaused by:")?;
+                    }
+                    while let ::std::option::Option::Some(e) = source {
+                        ::std::write!(f, "\n    {}", e)?;
+                        source = e.source();
+                    }
+                    #backtrace
+                    ::std::result::Result::Ok(())
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    // Inherent helper methods on the error type
+    let mut methods = vec![quote!{
+        /// Iterate over this error and its chain of sources.
+        pub fn chain(&self) -> impl ::std::iter::Iterator<Item = &(dyn ::std::error::Error + 'static)> + '_
+        where
+            Self: 'static,
+        {
+            ::std::iter::successors(::std::option::Option::Some(self as &(dyn ::std::error::Error + 'static)), |e| ::std::error::Error::source(*e))
+        }
+
+        /// The last error in the chain of sources of this error.
+        pub fn root_cause(&self) -> &(dyn ::std::error::Error + 'static)
+        where
+            Self: 'static,
+        {
+            self.chain().last().unwrap()
+        }
+    }];
+
+    let name_branches = error_variants.iter().map(|var| {
+        let curvar = &var.variant.ident;
+        let cfgs = &var.cfgs;
+        let name = curvar.unraw().to_string();
+        quote!{ #(#cfgs)* Self::#curvar { .. } => #name, }
+    });
+    methods.push(quote!{
+        /// The name of the variant of this error.
+        pub fn variant_name(&self) -> &'static str {
+            match self {
+                #(#name_branches)*
+            }
+        }
+    });
+
+    // Messages are written into any fmt::Write without allocating, which
+    //  the generated Display only does for localized messages
+    if enum_attrs.no_alloc {
+        if enum_attrs.localized {
+            errors.extend(TokenStream::from(syn::Error::new_spanned(&error_ident, "Localized error types allocate their messages, and can't be no_alloc").to_compile_error()));
+        }
+        methods.push(quote!{
+            /// Write the message of this error into the given writer, without
+            ///  allocating.
+            pub fn write_message<W: ::std::fmt::Write + ?::std::marker::Sized>(&self, w: &mut W) -> ::std::fmt::Result {
+                ::std::write!(w, "{}", self)
+            }
+        });
+    }
+    if enum_attrs.heapless {
+        methods.push(quote!{
+            /// The message of this error in a fixed capacity string, failing
+            ///  when it doesn't fit.
+            pub fn to_heapless<const N: usize>(&self) -> ::std::result::Result<::heapless::String<N>, ::std::fmt::Error> {
+                let mut message = ::heapless::String::new();
+                self.write_message(&mut message)?;
+                ::std::result::Result::Ok(message)
+            }
+        });
+    }
+
+    // The messages of failed validations by field, for the variants holding
+    //  validation errors
+    if enum_attrs.validator {
+        let branches = error_variants.iter().filter(|var| !var.validation_fields.is_empty()).map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let bindings = (0..var.variant.fields.len()).map(|i| {
+                if i == var.validation_fields[0] {
+                    quote!{ errors }
+                } else {
+                    quote!{ _ }
+                }
+            });
+            quote!{
+                #(#cfgs)*
+                Self::#curvar(#(#bindings),*) => {
+                    let mut fields = ::std::collections::BTreeMap::new();
+                    autoerror_validation_fields(errors, "", &mut fields);
+                    ::std::option::Option::Some(fields)
+                }
+            }
+        });
+        let fields_fn = validation_fields_fn();
+        methods.push(quote!{
+            /// The messages of the failed validations held by this error, by
+            ///  the path of the field they failed on, if any.
+            pub fn validation_errors(&self) -> ::std::option::Option<::std::collections::BTreeMap<::std::string::String, ::std::vec::Vec<::std::string::String>>> {
+                #fields_fn
+                #[allow(unreachable_patterns)]
+                match self {
+                    #(#branches)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+        });
+    }
+
+    // Backtraces, locations, timestamps and instance IDs captured when the
+    //  error was created
+    let captured_field = |var: &ErrorVariant, field: usize| {
+        let curvar = &var.variant.ident;
+        let cfgs = &var.cfgs;
+        let bindings = (0..var.variant.fields.len()).map(|i| {
+            if i == field {
+                quote!{ f }
+            } else {
+                quote!{ _ }
+            }
+        });
+        quote!{ #(#cfgs)* Self::#curvar(#(#bindings),*) }
+    };
+    if error_variants.iter().any(|var| var.backtrace_field.is_some()) {
+        let branches = error_variants.iter().filter(|var| var.backtrace_field.is_some()).map(|var| {
+            let pattern = captured_field(var, var.backtrace_field.unwrap());
+            quote!{ #pattern => ::std::option::Option::Some(::std::borrow::Borrow::<::std::backtrace::Backtrace>::borrow(f)), }
+        });
+        let fallback = if error_variants.iter().any(|var| var.backtrace_field.is_none()) {
+            Some(quote!{ _ => ::std::option::Option::None, })
+        } else {
+            None
+        };
+        methods.push(quote!{
+            /// The backtrace captured when this error was created, if any.
+            pub fn backtrace(&self) -> ::std::option::Option<&::std::backtrace::Backtrace> {
+                match self {
+                    #(#branches)*
+                    #fallback
+                }
+            }
+        });
+    }
+    if error_variants.iter().any(|var| var.location_field.is_some()) {
+        let branches = error_variants.iter().filter(|var| var.location_field.is_some()).map(|var| {
+            let pattern = captured_field(var, var.location_field.unwrap());
+            quote!{ #pattern => ::std::option::Option::Some(*f), }
+        });
+        let fallback = if error_variants.iter().any(|var| var.location_field.is_none()) {
+            Some(quote!{ _ => ::std::option::Option::None, })
+        } else {
+            None
+        };
+        methods.push(quote!{
+            /// The location in the source code where this error was created, if any.
+            pub fn location(&self) -> ::std::option::Option<&'static ::std::panic::Location<'static>> {
+                match self {
+                    #(#branches)*
+                    #fallback
+                }
+            }
+        });
+    }
+    let timestamp_fields: Vec<_> = error_variants.iter().filter_map(|var| {
+        let i = var.timestamp_field?;
+        Some((var, &var.variant.fields.iter().nth(i).unwrap().ty))
+    }).collect();
+    if let Some((_, timestamp_type)) = timestamp_fields.first() {
+        if let Some((_, ty)) = timestamp_fields.iter().find(|(_, ty)| type_name(ty) != type_name(timestamp_type)) {
+            errors.extend(TokenStream::from(syn::Error::new_spanned(ty, "All timestamp fields need to have the same type").to_compile_error()));
+        }
+        let branches = timestamp_fields.iter().map(|(var, _)| {
+            let pattern = captured_field(var, var.timestamp_field.unwrap());
+            quote!{ #pattern => ::std::option::Option::Some(f), }
+        });
+        let fallback = if error_variants.iter().any(|var| var.timestamp_field.is_none()) {
+            Some(quote!{ _ => ::std::option::Option::None, })
+        } else {
+            None
+        };
+        methods.push(quote!{
+            /// The time at which this error was created, if captured.
+            pub fn timestamp(&self) -> ::std::option::Option<&#timestamp_type> {
+                match self {
+                    #(#branches)*
+                    #fallback
+                }
+            }
+        });
+    }
+    // The configuration file and key are set on the error after it was
+    //  created, by the code that knows where the configuration came from
+    let config_setters = [
+        ("config_path", "in_config", "path", "Set the configuration file this error occurred in, for the variants holding it.", error_variants.iter().filter_map(|var| Some((var, var.config_path_field?))).collect::<Vec<_>>()),
+        ("config_key", "at_key", "key", "Set the configuration key this error occurred at, for the variants holding it.", error_variants.iter().filter_map(|var| Some((var, var.config_key_field?))).collect::<Vec<_>>()),
+    ];
+    for (attr, method, arg, doc, fields) in config_setters.iter() {
+        let types: Vec<_> = fields.iter().map(|(var, i)| {
+            let ty = &var.variant.fields.iter().nth(*i).unwrap().ty;
+            strip_option(ty).unwrap_or(ty)
+        }).collect();
+        let first_type = match types.first() {
+            Some(ty) => *ty,
+            None => continue,
+        };
+        if let Some(ty) = types.iter().find(|ty| type_name(ty) != type_name(first_type)) {
+            errors.extend(TokenStream::from(syn::Error::new_spanned(ty, format!("All {} fields need to have the same type", attr)).to_compile_error()));
+        }
+        let method = format_ident!("{}", method);
+        let arg = format_ident!("{}", arg);
+        let branches = fields.iter().map(|(var, i)| {
+            let pattern = captured_field(var, *i);
+            let ty = &var.variant.fields.iter().nth(*i).unwrap().ty;
+            if strip_option(ty).is_some() {
+                quote!{ #pattern => *f = ::std::option::Option::Some(::std::convert::Into::into(#arg)), }
+            } else {
+                quote!{ #pattern => *f = ::std::convert::Into::into(#arg), }
+            }
+        });
+        methods.push(quote!{
+            #[doc = #doc]
+            pub fn #method(mut self, #arg: impl ::std::convert::Into<#first_type>) -> Self {
+                #[allow(unreachable_patterns)]
+                match &mut self {
+                    #(#branches)*
+                    _ => {}
+                }
+                self
+            }
+        });
+    }
+
+    let instance_id_fields: Vec<_> = error_variants.iter().filter_map(|var| {
+        let (i, _) = var.instance_id_field.as_ref()?;
+        Some((var, *i, &var.variant.fields.iter().nth(*i).unwrap().ty))
+    }).collect();
+    if let Some((_, _, instance_id_type)) = instance_id_fields.first() {
+        if let Some((_, _, ty)) = instance_id_fields.iter().find(|(_, _, ty)| type_name(ty) != type_name(instance_id_type)) {
+            errors.extend(TokenStream::from(syn::Error::new_spanned(ty, "All instance_id fields need to have the same type").to_compile_error()));
+        }
+        let branches = instance_id_fields.iter().map(|(var, i, _)| {
+            let pattern = captured_field(var, *i);
+            quote!{ #pattern => ::std::option::Option::Some(f), }
+        });
+        let fallback = if error_variants.iter().any(|var| var.instance_id_field.is_none()) {
+            Some(quote!{ _ => ::std::option::Option::None, })
+        } else {
+            None
+        };
+        methods.push(quote!{
+            /// The ID generated for this instance of the error, if any, for
+            ///  correlating reports about it.
+            pub fn instance_id(&self) -> ::std::option::Option<&#instance_id_type> {
+                match self {
+                    #(#branches)*
+                    #fallback
+                }
+            }
+        });
+    }
+
+    let context_impl = if enum_attrs.context_selectors {
+        if !generics.params.is_empty() {
+            errors.extend(TokenStream::from(syn::Error::new_spanned(&generics, "Context selectors are not supported for generic enums").to_compile_error()));
+        }
+
+        let into_error = format_ident!("{}IntoError", error_ident);
+        let result_ext = format_ident!("{}ResultExt", error_ident);
+        let option_ext = format_ident!("{}OptionExt", error_ident);
+
+        let selectors = error_variants.iter().filter(|var| !var.hidden).map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let selector = format_ident!("{}Context", curvar);
+            let source_field = if var.err { var.source_field } else { None };
+
+            // The selector holds all fields except the source and captured
+            //  fields, each of which can be anything convertible into the
+            //  actual field type.
+            let context_types: Vec<_> = var.variant.fields.iter().enumerate()
+                .filter(|(i, _)| Some(*i) != source_field && !is_captured(var, *i))
+                .map(|(_, field)| &field.ty)
+                .collect();
+            let type_params: Vec<_> = (0..context_types.len()).map(|i| format_ident!("F{}", i)).collect();
+            let mut context_index = 0;
+            let args: Vec<_> = (0..var.variant.fields.len()).map(|i| {
+                if Some(i) == source_field {
+                    quote!{ ::std::convert::Into::into(source) }
+                } else if is_captured(var, i) {
+                    field_value(var, i)
+                } else {
+                    let index = syn::Index::from(context_index);
+                    context_index += 1;
+                    quote!{ ::std::convert::Into::into(self.#index) }
+                }
+            }).collect();
+            let value = match var.variant.fields {
+                syn::Fields::Unit => quote!{ #error_ident::#curvar },
+                _ => quote!{ #error_ident::#curvar(#(#args),*) },
+            };
+
+            let doc = format!("Context selector for [`{}::{}`].", error_ident, curvar);
+            let deprecated = deprecation(var, &error_ident);
+            let decl = if context_types.is_empty() {
+                quote!{
+                    #[doc = #doc]
+                    #deprecated
+                    #[derive(::std::fmt::Debug, ::std::clone::Clone, ::std::marker::Copy)]
+                    #vis struct #selector;
+                }
+            } else {
+                quote!{
+                    #[doc = #doc]
+                    #deprecated
+                    #[derive(::std::fmt::Debug, ::std::clone::Clone, ::std::marker::Copy)]
+                    #vis struct #selector<#(#type_params = #context_types),*>(#(pub #type_params),*);
+                }
+            };
+
+            let bounds = quote!{ #(#type_params: ::std::convert::Into<#context_types>,)* };
+            let var_track_caller = location_track_caller(var);
+            let imp = if let Some(source_field) = source_field {
+                let source_ty = &var.variant.fields.iter().nth(source_field).unwrap().ty;
+                quote!{
+                    #[allow(deprecated)]
+                    impl<#bounds AutoErrorSource: ::std::convert::Into<#source_ty>> #into_error<AutoErrorSource> for #selector<#(#type_params),*> {
+                        #var_track_caller
+                        fn into_error(self, source: AutoErrorSource) -> #error_ident {
+                            #value
+                        }
+                    }
+                }
+            } else {
+                quote!{
+                    #[allow(deprecated)]
+                    impl<#bounds> #into_error<()> for #selector<#(#type_params),*> {
+                        #var_track_caller
+                        fn into_error(self, _source: ()) -> #error_ident {
+                            #value
+                        }
+                    }
+
+                    #[allow(dead_code, deprecated)]
+                    impl<#bounds> #selector<#(#type_params),*> {
+                        /// Build the error described by this selector.
+                        #var_track_caller
+                        pub fn build(self) -> #error_ident {
+                            #into_error::into_error(self, ())
+                        }
+
+                        /// Fail with the error described by this selector.
+                        #var_track_caller
+                        pub fn fail<T>(self) -> ::std::result::Result<T, #error_ident> {
+                            ::std::result::Result::Err(self.build())
+                        }
+                    }
+                }
+            };
+
+            quote!{
+                #(#cfgs)*
+                #decl
+                #(#cfgs)*
+                #imp
+            }
+        });
+
+        // The extension methods pass on the location of their caller to
+        //  variants capturing it
+        let track_caller = if error_variants.iter().any(|var| var.location_field.is_some()) {
+            Some(quote!{ #[track_caller] })
+        } else {
+            None
+        };
+
+        let into_error_doc = format!("Conversion of a context selector and a source into a [`{}`].", error_ident);
+        let result_ext_doc = format!("Extension methods adding context to errors, turning them into a [`{}`].", error_ident);
+        let option_ext_doc = format!("Extension methods turning a missing value into a [`{}`].", error_ident);
+
+        Some(quote!{
+            #(#selectors)*
+
+            #[doc = #into_error_doc]
+            #vis trait #into_error<S> {
+                /// Combine the context with the source into an error.
+                fn into_error(self, source: S) -> #error_ident;
+            }
+
+            #[doc = #result_ext_doc]
+            #vis trait #result_ext<T, E> {
+                /// Wrap the error, if any, using the given context selector.
+                fn context<C: #into_error<E>>(self, context: C) -> ::std::result::Result<T, #error_ident>;
+
+                /// Wrap the error, if any, using the context selector returned by the
+                /// given function, which is only called on errors.
+                fn with_context<C: #into_error<E>, F: ::std::ops::FnOnce() -> C>(self, context: F) -> ::std::result::Result<T, #error_ident>;
+            }
+
+            impl<T, E> #result_ext<T, E> for ::std::result::Result<T, E> {
+                #track_caller
+                fn context<C: #into_error<E>>(self, context: C) -> ::std::result::Result<T, #error_ident> {
+                    match self {
+                        ::std::result::Result::Ok(value) => ::std::result::Result::Ok(value),
+                        ::std::result::Result::Err(e) => ::std::result::Result::Err(context.into_error(e)),
+                    }
+                }
+
+                #track_caller
+                fn with_context<C: #into_error<E>, F: ::std::ops::FnOnce() -> C>(self, context: F) -> ::std::result::Result<T, #error_ident> {
+                    match self {
+                        ::std::result::Result::Ok(value) => ::std::result::Result::Ok(value),
+                        ::std::result::Result::Err(e) => ::std::result::Result::Err(context().into_error(e)),
+                    }
+                }
+            }
+
+            #[doc = #option_ext_doc]
+            #vis trait #option_ext<T> {
+                /// Turn a missing value into the error described by the context selector.
+                fn context<C: #into_error<()>>(self, context: C) -> ::std::result::Result<T, #error_ident>;
+
+                /// Turn a missing value into the error described by the context selector
+                /// returned by the given function, which is only called when missing.
+                fn with_context<C: #into_error<()>, F: ::std::ops::FnOnce() -> C>(self, context: F) -> ::std::result::Result<T, #error_ident>;
+            }
+
+            impl<T> #option_ext<T> for ::std::option::Option<T> {
+                #track_caller
+                fn context<C: #into_error<()>>(self, context: C) -> ::std::result::Result<T, #error_ident> {
+                    match self {
+                        ::std::option::Option::Some(value) => ::std::result::Result::Ok(value),
+                        ::std::option::Option::None => ::std::result::Result::Err(context.into_error(())),
+                    }
+                }
+
+                #track_caller
+                fn with_context<C: #into_error<()>, F: ::std::ops::FnOnce() -> C>(self, context: F) -> ::std::result::Result<T, #error_ident> {
+                    match self {
+                        ::std::option::Option::Some(value) => ::std::result::Result::Ok(value),
+                        ::std::option::Option::None => ::std::result::Result::Err(context().into_error(())),
+                    }
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    // The context variant gets an extension trait wrapping any error into it
+    //  together with a message, and turning missing values into it when
+    //  the source is optional
+    let context_variants: Vec<_> = error_variants.iter().filter(|var| var.context).collect();
+    if context_variants.len() > 1 {
+        errors.extend(TokenStream::from(syn::Error::new_spanned(context_variants[1].variant, "Only one variant can be marked as context").to_compile_error()));
+    }
+    let context_ext_impl = match context_variants.first() {
+        Some(var) => {
+            if !generics.params.is_empty() {
+                errors.extend(TokenStream::from(syn::Error::new_spanned(&generics, "Context variants are not supported for generic enums").to_compile_error()));
+            }
+
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let context_ext = format_ident!("{}Context", error_ident);
+            let message_field = payload_fields(var)[0];
+            let source_field = var.source_field.unwrap();
+            let source_ty = &var.variant.fields.iter().nth(source_field).unwrap().ty;
+            let (source_inner, optional) = match strip_option(source_ty) {
+                Some(inner) => (inner, true),
+                None => (source_ty, false),
+            };
+            let construct = |source: proc_macro2::TokenStream| {
+                let args = (0..var.variant.fields.len()).map(|i| {
+                    if i == message_field {
+                        quote!{ ::std::convert::Into::into(::std::string::ToString::to_string(&context)) }
+                    } else if i == source_field {
+                        source.clone()
+                    } else {
+                        field_value(var, i)
+                    }
+                });
+                on_created(var, quote!{ #error_ident::#curvar(#(#args),*) })
+            };
+            let track_caller = location_track_caller(var);
+
+            let result_source = if optional {
+                quote!{ ::std::option::Option::Some(::std::convert::Into::into(e)) }
+            } else {
+                quote!{ ::std::convert::Into::into(e) }
+            };
+            let result_body = construct(result_source);
+            let option_impl = if optional {
+                let option_body = construct(quote!{ ::std::option::Option::None });
+                Some(quote!{
+                    #(#cfgs)*
+                    #[allow(deprecated)]
+                    impl<T> #context_ext<T> for ::std::option::Option<T> {
+                        #track_caller
+                        fn context<C: ::std::fmt::Display>(self, context: C) -> ::std::result::Result<T, #error_ident> {
+                            match self {
+                                ::std::option::Option::Some(value) => ::std::result::Result::Ok(value),
+                                ::std::option::Option::None => ::std::result::Result::Err(#option_body),
+                            }
+                        }
+
+                        #track_caller
+                        fn with_context<C: ::std::fmt::Display, F: ::std::ops::FnOnce() -> C>(self, context: F) -> ::std::result::Result<T, #error_ident> {
+                            match self {
+                                ::std::option::Option::Some(value) => ::std::result::Result::Ok(value),
+                                ::std::option::Option::None => {
+                                    let context = context();
+                                    ::std::result::Result::Err(#option_body)
+                                }
+                            }
+                        }
+                    }
+                })
+            } else {
+                None
+            };
+
+            let doc = format!("Extension methods wrapping errors into a [`{}::{}`] with a message describing them.", error_ident, curvar);
+            let deprecated = deprecation(var, &error_ident);
+            Some(quote!{
+                #(#cfgs)*
+                #[doc = #doc]
+                #deprecated
+                #vis trait #context_ext<T> {
+                    /// Wrap the error, if any, together with the given message.
+                    fn context<C: ::std::fmt::Display>(self, context: C) -> ::std::result::Result<T, #error_ident>;
+
+                    /// Wrap the error, if any, together with the message returned by the
+                    /// given function, which is only called on errors.
+                    fn with_context<C: ::std::fmt::Display, F: ::std::ops::FnOnce() -> C>(self, context: F) -> ::std::result::Result<T, #error_ident>;
+                }
+
+                #(#cfgs)*
+                #[allow(deprecated)]
+                impl<T, E: ::std::convert::Into<#source_inner>> #context_ext<T> for ::std::result::Result<T, E> {
+                    #track_caller
+                    fn context<C: ::std::fmt::Display>(self, context: C) -> ::std::result::Result<T, #error_ident> {
+                        match self {
+                            ::std::result::Result::Ok(value) => ::std::result::Result::Ok(value),
+                            ::std::result::Result::Err(e) => ::std::result::Result::Err(#result_body),
+                        }
+                    }
+
+                    #track_caller
+                    fn with_context<C: ::std::fmt::Display, F: ::std::ops::FnOnce() -> C>(self, context: F) -> ::std::result::Result<T, #error_ident> {
+                        match self {
+                            ::std::result::Result::Ok(value) => ::std::result::Result::Ok(value),
+                            ::std::result::Result::Err(e) => {
+                                let context = context();
+                                ::std::result::Result::Err(#result_body)
+                            }
+                        }
+                    }
+                }
+
+                #option_impl
+            })
+        }
+        None => None,
+    };
+
+    let result_alias_impl = match &enum_attrs.result_alias {
+        Some(alias) => {
+            if !generics.params.is_empty() {
+                errors.extend(TokenStream::from(syn::Error::new_spanned(&generics, "Result aliases are not supported for generic enums").to_compile_error()));
+            }
+            let doc = format!("Result type defaulting to [`{}`] as the error type.", error_ident);
+            Some(quote!{
+                #[doc = #doc]
+                #vis type #alias<T, E = #error_ident> = ::core::result::Result<T, E>;
+            })
+        }
+        None => None,
+    };
+
+    let macros_impl = if enum_attrs.macros {
+        let name = to_snake_case(&error_ident);
+        let bail = format_ident!("bail_{}", name);
+        let ensure = format_ident!("ensure_{}", name);
+        let bail_doc = format!("Return early with the given [`{}`] variant.", error_ident);
+        let ensure_doc = format!("Return early with the given [`{}`] variant if the condition does not hold.", error_ident);
+        Some(quote!{
+            #[doc = #bail_doc]
+            #[allow(unused_macros)]
+            macro_rules! #bail {
+                ($($variant:tt)+) => {
+                    return ::std::result::Result::Err(::std::convert::From::from(#error_ident::$($variant)+))
+                };
+            }
+
+            #[doc = #ensure_doc]
+            #[allow(unused_macros)]
+            macro_rules! #ensure {
+                ($cond:expr, $($variant:tt)+) => {
+                    if !$cond {
+                        return ::std::result::Result::Err(::std::convert::From::from(#error_ident::$($variant)+));
+                    }
+                };
+            }
+
+            #[allow(unused_imports)]
+            pub(crate) use #bail;
+            #[allow(unused_imports)]
+            pub(crate) use #ensure;
+        })
+    } else {
+        None
+    };
+
+    // Enums represented as an integer type with explicit discriminants get
+    //  numeric codes from them, which a fieldless enum carrying the same
+    //  discriminants turns into values
+    let numeric_codes = repr.as_ref().filter(|_| error_variants.iter().any(|var| var.variant.discriminant.is_some()));
+    let discriminants: Vec<_> = error_variants.iter().map(|var| {
+        match (&var.variant.discriminant, numeric_codes) {
+            (Some((_, value)), Some(_)) => Some(quote!{ = #value }),
+            _ => None,
+        }
+    }).collect();
+    if let Some(repr) = numeric_codes {
+        if let Some(code) = error_variants.iter().find_map(|var| var.code.as_ref()) {
+            errors.extend(TokenStream::from(syn::Error::new_spanned(code, "Codes can't be given to variants that get numeric codes from their discriminants").to_compile_error()));
+        }
+        let variants: Vec<_> = error_variants.iter().map(|var| &var.variant.ident).collect();
+        let cfgs: Vec<_> = error_variants.iter().map(|var| {
+            let cfgs = &var.cfgs;
+            quote!{ #(#cfgs)* }
+        }).collect();
+        methods.push(quote!{
+            /// The numeric code of this error, given by the discriminant of its variant.
+            pub fn code(&self) -> #repr {
+                #[allow(dead_code)]
+                #[repr(#repr)]
+                enum AutoErrorCode {
+                    #(#cfgs #variants #discriminants,)*
+                }
+
+                let code = match self {
+                    #(#cfgs Self::#variants { .. } => AutoErrorCode::#variants,)*
+                };
+                code as #repr
+            }
+        });
+    }
+
+    let kind_impl = enum_attrs.kind.as_ref().map(|kind_ident| {
+        let variants: Vec<_> = error_variants.iter().map(|var| &var.variant.ident).collect();
+        let cfgs: Vec<_> = error_variants.iter().map(|var| {
+            let cfgs = &var.cfgs;
+            quote!{ #(#cfgs)* }
+        }).collect();
+        let hidden = error_variants.iter().map(|var| {
+            if var.hidden {
+                Some(quote!{ #[doc(hidden)] })
+            } else {
+                None
+            }
+        });
+        let non_exhaustive = if non_exhaustive {
+            Some(quote!{ #[non_exhaustive] })
+        } else {
+            None
+        };
+        let doc = format!("The kind of a [`{}`], without any of its payload.", error_ident);
+        let repr_attr = numeric_codes.map(|repr| quote!{ #[repr(#repr)] });
+
+        // Kinds carry the numeric codes of their variants, and are converted
+        //  back from them
+        let try_from_code = numeric_codes.map(|repr| quote!{
+            impl ::std::convert::TryFrom<#repr> for #kind_ident {
+                type Error = #repr;
+
+                fn try_from(code: #repr) -> ::std::result::Result<Self, #repr> {
+                    match code {
+                        #(#cfgs code if code == Self::#variants as #repr => ::std::result::Result::Ok(Self::#variants),)*
+                        _ => ::std::result::Result::Err(code),
+                    }
+                }
+            }
+        });
+
+        // Kinds are parsed back from their variant name or string code, as
+        //  received from clients
+        let names = error_variants.iter().map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let name = curvar.unraw().to_string();
+            let code = var.code.as_ref().map(|code| quote!{ | #code });
+            quote!{ #(#cfgs)* #name #code => ::std::result::Result::Ok(Self::#curvar), }
+        });
+        let from_str = quote!{
+            impl<'autoerror> ::std::convert::TryFrom<&'autoerror str> for #kind_ident {
+                type Error = &'autoerror str;
+
+                fn try_from(s: &'autoerror str) -> ::std::result::Result<Self, &'autoerror str> {
+                    match s {
+                        #(#names)*
+                        _ => ::std::result::Result::Err(s),
+                    }
+                }
+            }
+
+            impl ::std::str::FromStr for #kind_ident {
+                type Err = ::std::string::String;
+
+                fn from_str(s: &str) -> ::std::result::Result<Self, ::std::string::String> {
+                    <Self as ::std::convert::TryFrom<&str>>::try_from(s).map_err(::std::string::ToString::to_string)
+                }
+            }
+        };
+
+        let visible: Vec<_> = error_variants.iter().filter(|var| !var.hidden).map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            quote!{ #(#cfgs)* #kind_ident::#curvar, }
+        }).collect();
+
+        methods.push(quote!{
+            /// The kind of this error.
+            pub fn kind(&self) -> #kind_ident {
+                #kind_ident::from(self)
+            }
+
+            /// The kinds of all variants of this error type.
+            pub const KINDS: &'static [#kind_ident] = &[
+                #(#visible)*
+            ];
+        });
+
+        quote!{
+            #[doc = #doc]
+            #[derive(::std::fmt::Debug, ::std::clone::Clone, ::std::marker::Copy, ::std::cmp::PartialEq, ::std::cmp::Eq, ::std::hash::Hash)]
+            #non_exhaustive
+            #repr_attr
+            #vis enum #kind_ident {
+                #(#cfgs #hidden #variants #discriminants,)*
+            }
+
+            #try_from_code
+
+            #from_str
+
+            #[allow(deprecated)]
+            impl #impl_generics ::std::convert::From<&#error_ident #ty_generics> for #kind_ident #where_clause {
+                fn from(e: &#error_ident #ty_generics) -> Self {
+                    match e {
+                        #(#cfgs #error_ident::#variants { .. } => Self::#variants,)*
+                    }
+                }
+            }
+        }
+    });
+
+    // Assertions on the kind of an error for tests, panicking with its
+    //  message and chain of sources rather than just the failed comparison
+    if enum_attrs.test_helpers {
+        let kind_ident = enum_attrs.kind.as_ref().unwrap();
+        methods.push(quote!{
+            /// Assert that this error is of the given kind, panicking with its
+            ///  message and chain of sources otherwise.
+            #[track_caller]
+            pub fn expect_kind(&self, kind: #kind_ident) -> &Self
+            where
+                Self: ::std::error::Error,
+            {
+                let actual = #kind_ident::from(self);
+                if actual != kind {
+                    let mut message = ::std::format!("expected error of kind {:?}, got {:?}: {}", kind, actual, self);
+                    let mut source = ::std::error::Error::source(self);
+                    while let ::std::option::Option::Some(e) = source {
+                        message.push_str(&::std::format!("\n    caused by: {}", e));
+                        source = e.source();
+                    }
+                    ::std::panic!("{}", message);
+                }
+                self
+            }
+
+            /// Assert that the result is an error of the given kind, panicking
+            ///  with the value or the error's message and chain of sources
+            ///  otherwise.
+            #[track_caller]
+            pub fn expect_err_kind<T: ::std::fmt::Debug>(result: ::std::result::Result<T, Self>, kind: #kind_ident) -> Self
+            where
+                Self: ::std::error::Error,
+            {
+                match result {
+                    ::std::result::Result::Ok(value) => ::std::panic!("expected error of kind {:?}, got Ok({:?})", kind, value),
+                    ::std::result::Result::Err(e) => {
+                        e.expect_kind(kind);
+                        e
+                    }
+                }
+            }
+        });
+    }
+
+    // Equality by variant, ignoring fields that may not be comparable
+    let eq_impl = if enum_attrs.eq {
+        methods.push(quote!{
+            /// Whether this error is of the same variant as the other error,
+            ///  ignoring their fields.
+            pub fn same_kind(&self, other: &Self) -> bool {
+                ::std::mem::discriminant(self) == ::std::mem::discriminant(other)
+            }
+        });
+
+        Some(quote!{
+            impl #impl_generics ::std::cmp::PartialEq for #error_ident #ty_generics #where_clause {
+                fn eq(&self, other: &Self) -> bool {
+                    ::std::mem::discriminant(self) == ::std::mem::discriminant(other)
+                }
+            }
+
+            impl #impl_generics ::std::cmp::Eq for #error_ident #ty_generics #where_clause {}
+        })
+    } else {
+        None
+    };
+
+    // Hashing by variant and code, consistent with equality by variant
+    let hash_impl = if enum_attrs.hash {
+        let code = if error_variants.iter().any(|var| var.code.is_some()) || numeric_codes.is_some() {
+            Some(quote!{ ::std::hash::Hash::hash(&self.code(), state); })
+        } else {
+            None
+        };
+        Some(quote!{
+            impl #impl_generics ::std::hash::Hash for #error_ident #ty_generics #where_clause {
+                fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+                    ::std::hash::Hash::hash(&::std::mem::discriminant(self), state);
+                    #code
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    // C compatible error codes, numbered from 1 in declaration order so
+    //  that 0 remains available to signal success
+    if enum_attrs.last_error.is_some() && !generics.params.is_empty() {
+        errors.extend(TokenStream::from(syn::Error::new_spanned(&generics, "The last_error helpers are not supported for generic enums").to_compile_error()));
+    }
+    let ffi_impl = enum_attrs.ffi.as_ref().map(|code_ident| {
+        let variants: Vec<_> = error_variants.iter().map(|var| &var.variant.ident).collect();
+        let cfgs: Vec<_> = error_variants.iter().map(|var| {
+            let cfgs = &var.cfgs;
+            quote!{ #(#cfgs)* }
+        }).collect();
+        let hidden = error_variants.iter().map(|var| {
+            if var.hidden {
+                Some(quote!{ #[doc(hidden)] })
+            } else {
+                None
+            }
+        });
+        let values = (1..=error_variants.len() as i32).map(proc_macro2::Literal::i32_unsuffixed);
+        let doc = format!("C compatible error code of a [`{}`].", error_ident);
+
+        methods.push(quote!{
+            /// The C compatible error code of this error.
+            pub fn ffi_code(&self) -> #code_ident {
+                match self {
+                    #(#cfgs Self::#variants { .. } => #code_ident::#variants,)*
+                }
+            }
+        });
+
+        let last_error = enum_attrs.last_error.as_ref().map(|prefix| {
+            let code_fn = format_ident!("{}_last_error_code", prefix);
+            let message_fn = format_ident!("{}_error_message", prefix);
+            let free_fn = format_ident!("{}_error_message_free", prefix);
+            let code_doc = format!("The error code of the last error set on this thread through [`{}::set_last_error`], or 0 when there is none.", error_ident);
+            let message_doc = format!("The message of the last error set on this thread through [`{}::set_last_error`], or null when there is none. The message needs to be freed with `{}`.", error_ident, free_fn);
+            let free_doc = format!("Free a message returned by `{}`.", message_fn);
+            let safety_doc = format!("The message needs to be null or returned by `{}`, and can't be used after it has been freed.", message_fn);
+            // The unsafe attribute form is needed from edition 2024 onwards,
+            //  the mixed site span gives it the edition of this crate instead.
+            let no_mangle = quote_spanned!{ proc_macro2::Span::mixed_site()=> #[no_mangle] };
+            quote!{
+                const _: () = {
+                    ::std::thread_local! {
+                        static LAST_ERROR: ::std::cell::RefCell<::std::option::Option<#error_ident>> = ::std::cell::RefCell::new(::std::option::Option::None);
+                    }
+
+                    #[allow(dead_code)]
+                    impl #error_ident {
+                        /// Store this error as the last error of the current thread,
+                        ///  for retrieval by C callers.
+                        pub fn set_last_error(self) {
+                            LAST_ERROR.with(|last| *last.borrow_mut() = ::std::option::Option::Some(self));
+                        }
+
+                        /// Take the last error of the current thread, if any.
+                        pub fn take_last_error() -> ::std::option::Option<Self> {
+                            LAST_ERROR.with(|last| last.borrow_mut().take())
+                        }
+
+                        /// Call the given function with the last error of the
+                        ///  current thread, if any, leaving it in place.
+                        pub fn with_last_error<R>(f: impl ::std::ops::FnOnce(::std::option::Option<&Self>) -> R) -> R {
+                            LAST_ERROR.with(|last| f(last.borrow().as_ref()))
+                        }
+                    }
+                };
+
+                #[doc = #code_doc]
+                #no_mangle
+                pub extern "C" fn #code_fn() -> i32 {
+                    #error_ident::with_last_error(|e| e.map_or(0, |e| e.ffi_code() as i32))
+                }
+
+                #[doc = #message_doc]
+                #no_mangle
+                pub extern "C" fn #message_fn() -> *mut ::std::os::raw::c_char {
+                    #error_ident::with_last_error(|e| match e {
+                        ::std::option::Option::Some(e) => {
+                            let message = ::std::string::ToString::to_string(e).replace('\0', "");
+                            ::std::ffi::CString::new(message).unwrap_or_default().into_raw()
+                        }
+                        ::std::option::Option::None => ::std::ptr::null_mut(),
+                    })
+                }
+
+                #[doc = #free_doc]
+                ///
+                /// # Safety
+                ///
+                #[doc = #safety_doc]
+                #no_mangle
+                pub unsafe extern "C" fn #free_fn(message: *mut ::std::os::raw::c_char) {
+                    if !message.is_null() {
+                        drop(unsafe { ::std::ffi::CString::from_raw(message) });
+                    }
+                }
+            }
+        });
+
+        quote!{
+            #[doc = #doc]
+            #[repr(C)]
+            #[derive(::std::fmt::Debug, ::std::clone::Clone, ::std::marker::Copy, ::std::cmp::PartialEq, ::std::cmp::Eq, ::std::hash::Hash)]
+            #vis enum #code_ident {
+                #(#cfgs #hidden #variants = #values,)*
+            }
+
+            #last_error
+        }
+    });
+
+    // HTTP status codes, defaulting to internal server error
+    let status_branches: Vec<_> = error_variants.iter().map(|var| {
+        let curvar = &var.variant.ident;
+        let cfgs = &var.cfgs;
+        let status = var.status.unwrap_or(500);
+        quote!{ #(#cfgs)* Self::#curvar { .. } => #status, }
+    }).collect();
+    // HTTP response bodies, which are the error message unless overridden
+    let response_body = if error_variants.iter().any(|var| var.body.is_some()) {
+        let branches = error_variants.iter().filter(|var| var.body.is_some()).map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let body = var.body.as_ref().unwrap();
+            quote!{ #(#cfgs)* Self::#curvar { .. } => ::std::string::ToString::to_string(#body), }
+        });
+        let fallback = if error_variants.iter().any(|var| var.body.is_none()) {
+            Some(quote!{ _ => ::std::string::ToString::to_string(&self), })
+        } else {
+            None
+        };
+        quote!{
+            match &self {
+                #(#branches)*
+                #fallback
+            }
+        }
+    } else {
+        quote!{ ::std::string::ToString::to_string(&self) }
+    };
+
+    let actix_impl = if enum_attrs.actix {
+        Some(quote!{
+            impl #impl_generics ::actix_web::ResponseError for #error_ident #ty_generics #error_where {
+                fn status_code(&self) -> ::actix_web::http::StatusCode {
+                    let status: u16 = match self {
+                        #(#status_branches)*
+                    };
+                    ::actix_web::http::StatusCode::from_u16(status).unwrap()
+                }
+
+                fn error_response(&self) -> ::actix_web::HttpResponse {
+                    ::actix_web::HttpResponse::build(::actix_web::ResponseError::status_code(self))
+                        .content_type("text/plain; charset=utf-8")
+                        .body(#response_body)
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    let warp_impl = if enum_attrs.warp {
+        let mut warp_generics = error_generics.clone();
+        warp_generics.make_where_clause().predicates.push(syn::parse_quote!{ Self: ::std::marker::Send + ::std::marker::Sync + 'static });
+        let warp_where = &warp_generics.where_clause;
+        Some(quote!{
+            impl #impl_generics ::warp::reject::Reject for #error_ident #ty_generics #warp_where {}
+
+            #[allow(dead_code)]
+            impl #impl_generics #error_ident #ty_generics #warp_where {
+                /// Turn this error into a warp rejection.
+                pub fn into_rejection(self) -> ::warp::Rejection {
+                    ::warp::reject::custom(self)
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    let rocket_impl = if enum_attrs.rocket {
+        let mut rocket_generics = error_generics.clone();
+        rocket_generics.params.insert(0, syn::parse_quote!{ 'r });
+        let (rocket_impl_generics, _, rocket_where) = rocket_generics.split_for_impl();
+        Some(quote!{
+            impl #rocket_impl_generics ::rocket::response::Responder<'r, 'static> for #error_ident #ty_generics #rocket_where {
+                fn respond_to(self, request: &'r ::rocket::Request<'_>) -> ::rocket::response::Result<'static> {
+                    let status: u16 = match &self {
+                        #(#status_branches)*
+                    };
+                    let body = #response_body;
+                    ::rocket::Response::build_from(::rocket::response::Responder::respond_to(body, request)?)
+                        .status(::rocket::http::Status::new(status))
+                        .ok()
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    // gRPC status codes, defaulting to internal
+    let tonic_impl = if enum_attrs.tonic {
+        let branches = error_variants.iter().map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let code = var.grpc.clone().unwrap_or_else(|| format_ident!("Internal"));
+            quote!{ #(#cfgs)* #error_ident::#curvar { .. } => ::tonic::Code::#code, }
+        });
+        Some(quote!{
+            impl #impl_generics ::std::convert::From<#error_ident #ty_generics> for ::tonic::Status #display_where {
+                fn from(e: #error_ident #ty_generics) -> Self {
+                    let code = match &e {
+                        #(#branches)*
+                    };
+                    ::tonic::Status::new(code, ::std::string::ToString::to_string(&e))
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    // Python exception types, defaulting to RuntimeError
+    let pyo3_impl = if enum_attrs.pyo3 {
+        let branches = error_variants.iter().map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let exc = var.py_exc.clone().unwrap_or_else(|| syn::parse_quote!{ ::pyo3::exceptions::PyRuntimeError });
+            quote!{ #(#cfgs)* #error_ident::#curvar { .. } => #exc::new_err(message), }
+        });
+        Some(quote!{
+            impl #impl_generics ::std::convert::From<#error_ident #ty_generics> for ::pyo3::PyErr #display_where {
+                fn from(e: #error_ident #ty_generics) -> Self {
+                    let message = ::std::string::ToString::to_string(&e);
+                    match &e {
+                        #(#branches)*
+                    }
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    // N-API statuses, defaulting to generic failure
+    let napi_impl = if enum_attrs.napi {
+        let branches = error_variants.iter().map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let status = var.napi_status.clone().unwrap_or_else(|| format_ident!("GenericFailure"));
+            quote!{ #(#cfgs)* #error_ident::#curvar { .. } => ::napi::Status::#status, }
+        });
+        Some(quote!{
+            impl #impl_generics ::std::convert::From<#error_ident #ty_generics> for ::napi::Error #display_where {
+                fn from(e: #error_ident #ty_generics) -> Self {
+                    let status = match &e {
+                        #(#branches)*
+                    };
+                    ::napi::Error::new(status, ::std::string::ToString::to_string(&e))
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    // Help texts, shown by the miette integration and the generated reports
+    let has_help = error_variants.iter().any(|var| var.help.is_some());
+    if has_help {
+        let branches = error_variants.iter().filter(|var| var.help.is_some()).map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let help = var.help.as_ref().unwrap();
+            quote!{ #(#cfgs)* Self::#curvar { .. } => ::std::option::Option::Some(#help), }
+        });
+        let fallback = if error_variants.iter().any(|var| var.help.is_none()) {
+            Some(quote!{ _ => ::std::option::Option::None, })
+        } else {
+            None
+        };
+        methods.push(quote!{
+            /// A help text suggesting how to resolve this error, if any.
+            pub fn help(&self) -> ::std::option::Option<&'static str> {
+                match self {
+                    #(#branches)*
+                    #fallback
+                }
+            }
+        });
+    }
+
+    // Documentation links, given per variant or through a template on the
+    //  enum filled in with the code of the variant
+    if let Some(template) = &enum_attrs.url {
+        if all_variants && template.value().contains("{code}") && error_variants.iter().all(|var| var.code.is_none()) {
+            errors.extend(TokenStream::from(syn::Error::new_spanned(template, "The url template uses {code}, but no variant has a code").to_compile_error()));
+        }
+    }
+    let urls: Vec<_> = error_variants.iter().filter_map(|var| {
+        let url = match (&var.url, &enum_attrs.url, &var.code) {
+            (Some(url), _, _) => url.clone(),
+            (None, Some(template), Some(code)) => template.value().replace("{code}", &code.value()),
+            (None, Some(template), None) if !template.value().contains("{code}") => template.value(),
+            _ => return None,
+        };
+        Some((var, url))
+    }).collect();
+    if !urls.is_empty() {
+        let branches = urls.iter().map(|(var, url)| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            quote!{ #(#cfgs)* Self::#curvar { .. } => ::std::option::Option::Some(::std::string::String::from(#url)), }
+        });
+        let fallback = if urls.len() < error_variants.len() {
+            Some(quote!{ _ => ::std::option::Option::None, })
+        } else {
+            None
+        };
+        methods.push(quote!{
+            /// A link to the documentation of this error, if any.
+            pub fn url(&self) -> ::std::option::Option<::std::string::String> {
+                match self {
+                    #(#branches)*
+                    #fallback
+                }
+            }
+        });
+    }
+
+    // Message keys and arguments, for localizing errors instead of
+    //  displaying the English format string. Only the fields used in the
+    //  format string are passed as arguments, named arg0, arg1, ... unless
+    //  given a name with #[auto_error(arg = "...")].
+    let i18n_impl = if enum_attrs.localized || error_variants.iter().any(|var| var.msg_key.is_some()) {
+        // The lookup function is shared by all instantiations of a generic
+        //  error, as statics can't depend on generic parameters
+        let localizer = if enum_attrs.localized {
+            Some(quote!{
+                const _: () = {
+                    type Localizer = ::std::sync::Arc<dyn ::std::ops::Fn(&str, &[(&'static str, ::std::string::String)]) -> ::std::option::Option<::std::string::String> + ::std::marker::Send + ::std::marker::Sync>;
+
+                    static LOCALIZER: ::std::sync::RwLock<::std::option::Option<Localizer>> = ::std::sync::RwLock::new(::std::option::Option::None);
+
+                    #[allow(dead_code)]
+                    impl #impl_generics #error_ident #ty_generics #display_where {
+                        /// Register the function looking up the localized message
+                        ///  for a message key and its arguments, used when
+                        ///  displaying the error. Errors are displayed using their
+                        ///  format string when it returns None.
+                        pub fn set_localizer(f: impl ::std::ops::Fn(&str, &[(&'static str, ::std::string::String)]) -> ::std::option::Option<::std::string::String> + ::std::marker::Send + ::std::marker::Sync + 'static) {
+                            *LOCALIZER.write().unwrap_or_else(|e| e.into_inner()) = ::std::option::Option::Some(::std::sync::Arc::new(f));
+                        }
+
+                        /// The localized message of this error, if a localizer
+                        ///  is registered and knows about it.
+                        pub fn localized_message(&self) -> ::std::option::Option<::std::string::String> {
+                            // The lock is released before calling the localizer,
+                            //  which may display errors or register another one
+                            let localizer = ::std::clone::Clone::clone(LOCALIZER.read().unwrap_or_else(|e| e.into_inner()).as_ref()?);
+                            localizer(self.message_key(), &self.message_args())
+                        }
+                    }
+                };
+            })
+        } else {
+            None
+        };
+        let key_branches = error_variants.iter().map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let key = match &var.msg_key {
+                Some(key) => key.clone(),
+                None => to_snake_case(curvar).replace('_', "-"),
+            };
+            quote!{ #(#cfgs)* Self::#curvar { .. } => #key, }
+        });
+        let arg_branches = error_variants.iter().map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let payload = payload_fields(var);
+            let used = match rewrite_format_str(&var.format_str, &payload, var.variant.fields.len()) {
+                Some((_, used)) => used,
+                None => (0..var.variant.fields.len()).map(|i| payload.contains(&i)).collect(),
+            };
+            let bindings = used.iter().enumerate().map(|(i, used)| {
+                if *used {
+                    let param = field_binding(i);
+                    quote!{ #param }
+                } else {
+                    quote!{ _ }
+                }
+            });
+            let separator = separator(var);
+            let args = var.variant.fields.iter().zip(used.iter()).enumerate().filter(|(_, (_, used))| **used).map(|(i, (field, _))| {
+                let param = field_binding(i);
+                let name = match var.arg_names.iter().find(|(j, _)| *j == i) {
+                    Some((_, name)) => name.clone(),
+                    None => format!("arg{}", i),
+                };
+                if is_joined(var, i) {
+                    quote!{ (#name, #param.iter().map(::std::string::ToString::to_string).collect::<::std::vec::Vec<_>>().join(#separator)) }
+                } else if strip_vec(&field.ty).is_some() {
+                    quote!{ (#name, ::std::format!("{:?}", #param)) }
+                } else {
+                    quote!{ (#name, ::std::string::ToString::to_string(#param)) }
+                }
+            });
+            match var.variant.fields {
+                syn::Fields::Unnamed(_) => quote!{
+                    #(#cfgs)*
+                    Self::#curvar(#(#bindings),*) => ::std::vec![#(#args),*],
+                },
+                _ => quote!{
+                    #(#cfgs)*
+                    Self::#curvar => ::std::vec::Vec::new(),
+                },
+            }
+        });
+        Some(quote!{
+            impl #impl_generics #error_ident #ty_generics #display_where {
+                /// The key identifying the message of this error in a translation catalog.
+                pub fn message_key(&self) -> &'static str {
+                    match self {
+                        #(#key_branches)*
+                    }
+                }
+
+                /// The named arguments to fill in the message of this error.
+                pub fn message_args(&self) -> ::std::vec::Vec<(&'static str, ::std::string::String)> {
+                    match self {
+                        #(#arg_branches)*
+                    }
+                }
+            }
+
+            #localizer
+        })
+    } else {
+        None
+    };
+
+    // miette diagnostics, with severities mapped onto the three levels
+    //  miette knows about
+    // Structured loggers get the error as key-value pairs, rather than just
+    //  its message
+    let mut kv_generics = error_generics.clone();
+    kv_generics.make_where_clause().predicates.push(syn::parse_quote!{ Self: 'static });
+    let kv_where = &kv_generics.where_clause;
+    let log_kv_impl = if enum_attrs.log_kv {
+        let code = if error_variants.iter().any(|var| var.code.is_some()) {
+            Some(quote!{
+                if let ::std::option::Option::Some(code) = self.code() {
+                    visitor.visit_pair(::log::kv::Key::from_str("code"), ::log::kv::Value::from(code))?;
+                }
+            })
+        } else if numeric_codes.is_some() {
+            Some(quote!{ visitor.visit_pair(::log::kv::Key::from_str("code"), ::log::kv::Value::from(self.code()))?; })
+        } else {
+            None
+        };
+        Some(quote!{
+            impl #impl_generics ::log::kv::ToValue for #error_ident #ty_generics #kv_where {
+                fn to_value(&self) -> ::log::kv::Value<'_> {
+                    ::log::kv::Value::from_dyn_error(self)
+                }
+            }
+
+            impl #impl_generics ::log::kv::Source for #error_ident #ty_generics #kv_where {
+                fn visit<'kvs>(&'kvs self, visitor: &mut dyn ::log::kv::VisitSource<'kvs>) -> ::std::result::Result<(), ::log::kv::Error> {
+                    visitor.visit_pair(::log::kv::Key::from_str("kind"), ::log::kv::Value::from(self.variant_name()))?;
+                    #code
+                    visitor.visit_pair(::log::kv::Key::from_str("message"), ::log::kv::Value::from_display(self))?;
+                    if let ::std::option::Option::Some(source) = ::std::error::Error::source(self) {
+                        visitor.visit_pair(::log::kv::Key::from_str("source"), ::log::kv::Value::from_dyn_error(source))?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+        })
+    } else {
+        None
+    };
+    let slog_impl = if enum_attrs.slog {
+        let code = if error_variants.iter().any(|var| var.code.is_some()) {
+            Some(quote!{
+                if let ::std::option::Option::Some(code) = self.code() {
+                    serializer.emit_str(::std::convert::Into::into("code"), code)?;
+                }
+            })
+        } else if numeric_codes.is_some() {
+            Some(quote!{ serializer.emit_i64(::std::convert::Into::into("code"), self.code() as i64)?; })
+        } else {
+            None
+        };
+        Some(quote!{
+            impl #impl_generics ::slog::Value for #error_ident #ty_generics #kv_where {
+                fn serialize(&self, _record: &::slog::Record<'_>, key: ::slog::Key, serializer: &mut dyn ::slog::Serializer) -> ::slog::Result {
+                    serializer.emit_error(key, self)
+                }
+            }
+
+            impl #impl_generics ::slog::KV for #error_ident #ty_generics #kv_where {
+                fn serialize(&self, _record: &::slog::Record<'_>, serializer: &mut dyn ::slog::Serializer) -> ::slog::Result {
+                    serializer.emit_str(::std::convert::Into::into("kind"), self.variant_name())?;
+                    #code
+                    serializer.emit_arguments(::std::convert::Into::into("message"), &::std::format_args!("{}", self))?;
+                    if let ::std::option::Option::Some(source) = ::std::error::Error::source(self) {
+                        serializer.emit_error(::std::convert::Into::into("source"), source)?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    // Valuable exposes the error as a struct of its kind, code and message
+    let valuable_impl = if enum_attrs.valuable {
+        let name = error_ident.unraw().to_string();
+        let (fields, code) = if error_variants.iter().any(|var| var.code.is_some()) {
+            (vec!["kind", "code", "message"], Some(quote!{ self.code().map_or(::valuable::Value::Unit, ::valuable::Value::String), }))
+        } else if numeric_codes.is_some() {
+            (vec!["kind", "code", "message"], Some(quote!{ ::valuable::Value::from(self.code()), }))
+        } else {
+            (vec!["kind", "message"], None)
+        };
+        Some(quote!{
+            static AUTOERROR_VALUABLE_FIELDS: &[::valuable::NamedField<'static>] = &[#(::valuable::NamedField::new(#fields)),*];
+
+            impl #impl_generics ::valuable::Valuable for #error_ident #ty_generics #kv_where {
+                fn as_value(&self) -> ::valuable::Value<'_> {
+                    ::valuable::Value::Structable(self)
+                }
+
+                fn visit(&self, visit: &mut dyn ::valuable::Visit) {
+                    let message = ::std::string::ToString::to_string(self);
+                    visit.visit_named_fields(&::valuable::NamedValues::new(AUTOERROR_VALUABLE_FIELDS, &[
+                        ::valuable::Value::String(self.variant_name()),
+                        #code
+                        ::valuable::Value::String(&message),
+                    ]));
+                }
+            }
+
+            impl #impl_generics ::valuable::Structable for #error_ident #ty_generics #kv_where {
+                fn definition(&self) -> ::valuable::StructDef<'_> {
+                    ::valuable::StructDef::new_static(#name, ::valuable::Fields::Named(AUTOERROR_VALUABLE_FIELDS))
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    let miette_impl = if enum_attrs.miette {
+        let code = if error_variants.iter().any(|var| var.code.is_some()) {
+            Some(quote!{
+                fn code<'a>(&'a self) -> ::std::option::Option<::std::boxed::Box<dyn ::std::fmt::Display + 'a>> {
+                    #error_ident::code(self).map(|code| ::std::boxed::Box::new(code) as ::std::boxed::Box<dyn ::std::fmt::Display + 'a>)
+                }
+            })
+        } else if numeric_codes.is_some() {
+            Some(quote!{
+                fn code<'a>(&'a self) -> ::std::option::Option<::std::boxed::Box<dyn ::std::fmt::Display + 'a>> {
+                    ::std::option::Option::Some(::std::boxed::Box::new(#error_ident::code(self)))
+                }
+            })
+        } else {
+            None
+        };
+        let severity_ident = format_ident!("{}Severity", error_ident);
+        let severity = if error_variants.iter().any(|var| var.severity.is_some()) {
+            Some(quote!{
+                fn severity(&self) -> ::std::option::Option<::miette::Severity> {
+                    ::std::option::Option::Some(match #error_ident::severity(self) {
+                        #severity_ident::Debug | #severity_ident::Info => ::miette::Severity::Advice,
+                        #severity_ident::Warn => ::miette::Severity::Warning,
+                        #severity_ident::Error | #severity_ident::Critical => ::miette::Severity::Error,
+                    })
+                }
+            })
+        } else {
+            None
+        };
+        // Variants without the value fall through to a wildcard, when any
+        let missing = |any_missing: bool| {
+            if any_missing {
+                Some(quote!{ _ => ::std::option::Option::None, })
+            } else {
+                None
+            }
+        };
+        let help = if has_help {
+            Some(quote!{
+                fn help<'a>(&'a self) -> ::std::option::Option<::std::boxed::Box<dyn ::std::fmt::Display + 'a>> {
+                    #error_ident::help(self).map(|help| ::std::boxed::Box::new(help) as ::std::boxed::Box<dyn ::std::fmt::Display>)
+                }
+            })
+        } else {
+            None
+        };
+        let url = if !urls.is_empty() {
+            Some(quote!{
+                fn url<'a>(&'a self) -> ::std::option::Option<::std::boxed::Box<dyn ::std::fmt::Display + 'a>> {
+                    #error_ident::url(self).map(|url| ::std::boxed::Box::new(url) as ::std::boxed::Box<dyn ::std::fmt::Display>)
+                }
+            })
+        } else {
+            None
+        };
+        let field_pattern = |var: &ErrorVariant, used: &[usize]| {
+            let curvar = &var.variant.ident;
+            let fields = (0..var.variant.fields.len()).map(|i| {
+                if used.contains(&i) {
+                    let name = field_binding(i);
+                    quote!{ #name }
+                } else {
+                    quote!{ _ }
+                }
+            });
+            quote!{ #error_ident::#curvar(#(#fields),*) }
+        };
+        let source_code = if error_variants.iter().any(|var| var.source_code.is_some()) {
+            let fallback = missing(error_variants.iter().any(|var| var.source_code.is_none()));
+            let branches = error_variants.iter().filter(|var| var.source_code.is_some()).map(|var| {
+                let cfgs = &var.cfgs;
+                let field = var.source_code.unwrap();
+                let pattern = field_pattern(var, &[field]);
+                let name = field_binding(field);
+                quote!{ #(#cfgs)* #pattern => ::std::option::Option::Some(#name), }
+            });
+            Some(quote!{
+                fn source_code(&self) -> ::std::option::Option<&dyn ::miette::SourceCode> {
+                    match self {
+                        #(#branches)*
+                        #fallback
+                    }
+                }
+            })
+        } else {
+            None
+        };
+        let labels = if error_variants.iter().any(|var| !var.labels.is_empty()) {
+            let fallback = missing(error_variants.iter().any(|var| var.labels.is_empty()));
+            let branches = error_variants.iter().filter(|var| !var.labels.is_empty()).map(|var| {
+                let cfgs = &var.cfgs;
+                let used: Vec<_> = var.labels.iter().map(|(i, _)| *i).collect();
+                let pattern = field_pattern(var, &used);
+                let spans = var.labels.iter().map(|(i, label)| {
+                    let name = field_binding(*i);
+                    let label = match label {
+                        Some(label) => quote!{ ::std::option::Option::Some(::std::string::String::from(#label)) },
+                        None => quote!{ ::std::option::Option::None },
+                    };
+                    quote!{ ::miette::LabeledSpan::new_with_span(#label, ::std::clone::Clone::clone(#name)) }
+                });
+                quote!{ #(#cfgs)* #pattern => ::std::option::Option::Some(::std::boxed::Box::new(::std::vec![#(#spans),*].into_iter())), }
+            });
+            Some(quote!{
+                fn labels(&self) -> ::std::option::Option<::std::boxed::Box<dyn ::std::iter::Iterator<Item = ::miette::LabeledSpan> + '_>> {
+                    match self {
+                        #(#branches)*
+                        #fallback
+                    }
+                }
+            })
+        } else {
+            None
+        };
+        Some(quote!{
+            impl #impl_generics ::miette::Diagnostic for #error_ident #ty_generics #error_where {
+                #code
+                #severity
+                #help
+                #url
+                #source_code
+                #labels
+            }
+        })
+    } else {
+        None
+    };
+
+    let problem_details_impl = enum_attrs.problem_details.as_ref().map(|details_ident| {
+        let branches = error_variants.iter().map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let problem_type = var.problem_type.clone().unwrap_or_else(|| "about:blank".to_string());
+            let title = var.title.clone().unwrap_or_else(|| to_title(curvar));
+            let status = var.status.unwrap_or(500);
+            quote!{ #(#cfgs)* Self::#curvar { .. } => (#problem_type, #title, #status), }
+        });
+        let doc = format!("RFC 7807 problem details describing a [`{}`].", error_ident);
+        let serde = match (enum_attrs.serde, enum_attrs.schemars) {
+            (true, true) => Some(quote!{ #[derive(::serde::Serialize, ::schemars::JsonSchema)] }),
+            (true, false) => Some(quote!{ #[derive(::serde::Serialize)] }),
+            _ => None,
+        };
+        let skip_instance = if enum_attrs.serde {
+            Some(quote!{ #[serde(skip_serializing_if = "::std::option::Option::is_none")] })
+        } else {
+            None
+        };
+        // Validation errors include the messages of the fields that failed
+        let (errors_field, errors_value) = if enum_attrs.validator {
+            let field = quote!{
+                /// Messages of the failed validations, by field.
+                #skip_instance
+                pub errors: ::std::option::Option<::std::collections::BTreeMap<::std::string::String, ::std::vec::Vec<::std::string::String>>>,
+            };
+            (Some(field), Some(quote!{ errors: self.validation_errors(), }))
+        } else {
+            (None, None)
+        };
+
+        methods.push(quote!{
+            /// The RFC 7807 problem details for this error.
+            pub fn to_problem_details(&self) -> #details_ident {
+                let (problem_type, title, status) = match self {
+                    #(#branches)*
+                };
+                #details_ident {
+                    r#type: problem_type,
+                    title,
+                    status,
+                    detail: ::std::string::ToString::to_string(self),
+                    instance: ::std::option::Option::None,
+                    #errors_value
+                }
+            }
+        });
+
+        quote!{
+            #[doc = #doc]
+            #[derive(::std::fmt::Debug, ::std::clone::Clone, ::std::cmp::PartialEq, ::std::cmp::Eq)]
+            #serde
+            #vis struct #details_ident {
+                /// URI identifying the problem type.
+                pub r#type: &'static str,
+                /// Short summary of the problem type.
+                pub title: &'static str,
+                /// HTTP status code.
+                pub status: u16,
+                /// Explanation specific to this occurrence of the problem.
+                pub detail: ::std::string::String,
+                /// URI identifying this occurrence of the problem.
+                #skip_instance
+                pub instance: ::std::option::Option<::std::string::String>,
+                #errors_field
+            }
+        }
+    });
+
+    // JSON-RPC error codes, defaulting to the reserved internal error code
+    let jsonrpc_impl = enum_attrs.jsonrpc.as_ref().map(|jsonrpc_ident| {
+        let branches = error_variants.iter().map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let code = var.jsonrpc_code.unwrap_or(-32603);
+            quote!{ #(#cfgs)* Self::#curvar { .. } => #code, }
+        });
+        let doc = format!("JSON-RPC 2.0 error object describing a [`{}`].", error_ident);
+        let mut from_generics = generics.clone();
+        from_generics.make_where_clause().predicates.push(syn::parse_quote!{ #error_ident #ty_generics: ::std::error::Error });
+        let from_where = &from_generics.where_clause;
+        let serde = match (enum_attrs.serde, enum_attrs.schemars) {
+            (true, true) => Some(quote!{ #[derive(::serde::Serialize, ::schemars::JsonSchema)] }),
+            (true, false) => Some(quote!{ #[derive(::serde::Serialize)] }),
+            _ => None,
+        };
+        let skip_data = if enum_attrs.serde {
+            Some(quote!{ #[serde(skip_serializing_if = "::std::option::Option::is_none")] })
+        } else {
+            None
+        };
+
+        methods.push(quote!{
+            /// The JSON-RPC error code of this error.
+            pub fn jsonrpc_code(&self) -> i64 {
+                match self {
+                    #(#branches)*
+                }
+            }
+
+            /// The JSON-RPC error object for this error, with the source of
+            ///  the error, if any, as data.
+            pub fn to_jsonrpc_error(&self) -> #jsonrpc_ident {
+                #jsonrpc_ident {
+                    code: self.jsonrpc_code(),
+                    message: ::std::string::ToString::to_string(self),
+                    data: ::std::error::Error::source(self).map(::std::string::ToString::to_string),
+                }
+            }
+        });
+
+        quote!{
+            #[doc = #doc]
+            #[derive(::std::fmt::Debug, ::std::clone::Clone, ::std::cmp::PartialEq, ::std::cmp::Eq)]
+            #serde
+            #vis struct #jsonrpc_ident {
+                /// Error code.
+                pub code: i64,
+                /// Error message.
+                pub message: ::std::string::String,
+                /// Additional information about the error.
+                #skip_data
+                pub data: ::std::option::Option<::std::string::String>,
+            }
+
+            impl #impl_generics ::std::convert::From<#error_ident #ty_generics> for #jsonrpc_ident #from_where {
+                fn from(e: #error_ident #ty_generics) -> Self {
+                    e.to_jsonrpc_error()
+                }
+            }
+        }
+    });
+
+    // GraphQL errors get the variant, and the code and status when given
+    let async_graphql_impl = if enum_attrs.async_graphql {
+        let code = if error_variants.iter().any(|var| var.code.is_some()) {
+            Some(quote!{
+                if let ::std::option::Option::Some(code) = self.code() {
+                    extensions.set("code", code);
+                }
+            })
+        } else if numeric_codes.is_some() {
+            Some(quote!{ extensions.set("code", self.code()); })
+        } else {
+            None
+        };
+        let status = if error_variants.iter().any(|var| var.status.is_some()) {
+            Some(quote!{
+                let status: u16 = match self {
+                    #(#status_branches)*
+                };
+                extensions.set("status", status);
+            })
+        } else {
+            None
+        };
+        Some(quote!{
+            impl #impl_generics ::async_graphql::ErrorExtensions for #error_ident #ty_generics #error_where {
+                fn extend(&self) -> ::async_graphql::Error {
+                    let mut extensions = ::async_graphql::ErrorExtensionValues::default();
+                    extensions.set("variant", self.variant_name());
+                    #code
+                    #status
+                    let mut error = ::async_graphql::Error::new(::std::string::ToString::to_string(self));
+                    error.extensions = ::std::option::Option::Some(extensions);
+                    error
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    // Errors serialize as their variant, code, message and chain of sources
+    let serde_impl = if enum_attrs.serde {
+        let mut serde_generics = error_generics.clone();
+        serde_generics.make_where_clause().predicates.push(syn::parse_quote!{ Self: 'static });
+        let serde_where = &serde_generics.where_clause;
+        let name = error_ident.unraw().to_string();
+        let has_code = error_variants.iter().any(|var| var.code.is_some()) || numeric_codes.is_some();
+        let field_count = if has_code { 4usize } else { 3usize };
+        let code = if numeric_codes.is_some() {
+            Some(quote!{ state.serialize_field("code", &self.code())?; })
+        } else if has_code {
+            Some(quote!{
+                match self.code() {
+                    ::std::option::Option::Some(code) => state.serialize_field("code", code)?,
+                    ::std::option::Option::None => state.skip_field("code")?,
+                }
+            })
+        } else {
+            None
+        };
+        // Deserializable errors also include the fields of their variant
+        let fields = if enum_attrs.deserialize {
+            let branches = error_variants.iter().filter(|var| var.deserialize && !var.hidden).map(|var| {
+                let curvar = &var.variant.ident;
+                let cfgs = &var.cfgs;
+                // Captured fields are not serialized
+                let bindings = (0..var.variant.fields.len()).map(|i| {
+                    if is_captured(var, i) {
+                        quote!{ _ }
+                    } else {
+                        let param = field_binding(i);
+                        quote!{ #param }
+                    }
+                });
+                let params = payload_fields(var).into_iter().map(field_binding);
+                match var.variant.fields {
+                    syn::Fields::Unit => quote!{
+                        #(#cfgs)*
+                        Self::#curvar => state.serialize_field("fields", &())?,
+                    },
+                    _ => quote!{
+                        #(#cfgs)*
+                        Self::#curvar(#(#bindings),*) => state.serialize_field("fields", &(#(#params,)*))?,
+                    },
+                }
+            });
+            Some(quote!{
+                #[allow(unreachable_patterns)]
+                match self {
+                    #(#branches)*
+                    _ => state.skip_field("fields")?,
+                }
+            })
+        } else {
+            None
+        };
+        let field_count = if enum_attrs.deserialize { field_count + 1 } else { field_count };
+        // Validation errors include the messages of the fields that failed
+        let validation = if enum_attrs.validator {
+            Some(quote!{
+                match self.validation_errors() {
+                    ::std::option::Option::Some(fields) => state.serialize_field("validation", &fields)?,
+                    ::std::option::Option::None => state.skip_field("validation")?,
+                }
+            })
+        } else {
+            None
+        };
+        let field_count = if enum_attrs.validator { field_count + 1 } else { field_count };
+        Some(quote!{
+            impl #impl_generics ::serde::Serialize for #error_ident #ty_generics #serde_where {
+                fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+                    use ::serde::ser::SerializeStruct;
+                    let mut state = serializer.serialize_struct(#name, #field_count)?;
+                    state.serialize_field("kind", self.variant_name())?;
+                    #code
+                    state.serialize_field("message", &::std::string::ToString::to_string(self))?;
+                    let source: ::std::vec::Vec<::std::string::String> = self.chain().skip(1).map(::std::string::ToString::to_string).collect();
+                    state.serialize_field("source", &source)?;
+                    #fields
+                    #validation
+                    state.end()
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    let deserialize_impl = if enum_attrs.deserialize {
+        if !generics.params.is_empty() {
+            errors.extend(TokenStream::from(syn::Error::new_spanned(&generics, "Deserialize is not supported for generic enums").to_compile_error()));
+        }
+
+        let catch_alls: Vec<_> = error_variants.iter().filter(|var| var.catch_all).collect();
+        if catch_alls.len() > 1 {
+            errors.extend(TokenStream::from(syn::Error::new_spanned(catch_alls[1].variant, "Only one variant can be marked as catch_all").to_compile_error()));
+        }
+        // Errors of other variants are deserialized into the catch-all
+        //  variant (or the whatever variant) from their message.
+        let catch_all = if let Some(var) = catch_alls.first() {
+            let curvar = &var.variant.ident;
+            quote!{
+                let _ = kind;
+                ::std::result::Result::Ok(#error_ident::#curvar(::std::convert::From::from(message)))
+            }
+        } else if error_variants.iter().any(|var| var.whatever) {
+            quote!{
+                let _ = kind;
+                ::std::result::Result::Ok(#error_ident::whatever(message))
+            }
+        } else {
+            quote!{ ::std::result::Result::Err(::serde::de::Error::custom(::std::format_args!("unknown error kind {}: {}", kind, message))) }
+        };
+
+        let name = error_ident.unraw().to_string();
+        // Known kinds need their fields, rather than being taken for errors
+        //  of other kinds
+        let known_kinds = error_variants.iter().filter(|var| var.deserialize && !var.hidden).map(|var| {
+            let cfgs = &var.cfgs;
+            let kind = var.variant.ident.unraw().to_string();
+            quote!{
+                #(#cfgs)*
+                #kind => { return ::std::result::Result::Err(::serde::de::Error::missing_field("fields")); }
+            }
+        });
+        let branches = error_variants.iter().filter(|var| var.deserialize && !var.hidden).map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let kind = curvar.unraw().to_string();
+            let payload = payload_fields(var);
+            let types = payload.iter().map(|i| &var.variant.fields.iter().nth(*i).unwrap().ty);
+            let params: Vec<_> = payload.iter().copied().map(field_binding).collect();
+            let args = (0..var.variant.fields.len()).map(|i| {
+                if is_captured(var, i) {
+                    field_value(var, i)
+                } else {
+                    let param = field_binding(i);
+                    quote!{ #param }
+                }
+            });
+            let value = match var.variant.fields {
+                syn::Fields::Unit => quote!{ #error_ident::#curvar },
+                _ => quote!{ #error_ident::#curvar(#(#args),*) },
+            };
+            quote!{
+                #(#cfgs)*
+                ::std::option::Option::Some(#kind) => {
+                    let (#(#params,)*): (#(#types,)*) = map.next_value()?;
+                    result = ::std::option::Option::Some(#value);
+                }
+            }
+        });
+
+        Some(quote!{
+            impl<'de> ::serde::Deserialize<'de> for #error_ident {
+                fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> ::std::result::Result<Self, D::Error> {
+                    struct AutoErrorVisitor;
+
+                    impl<'de> ::serde::de::Visitor<'de> for AutoErrorVisitor {
+                        type Value = #error_ident;
+
+                        fn expecting(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                            f.write_str(::std::concat!("struct ", #name))
+                        }
+
+                        fn visit_map<A: ::serde::de::MapAccess<'de>>(self, mut map: A) -> ::std::result::Result<#error_ident, A::Error> {
+                            let mut kind: ::std::option::Option<::std::string::String> = ::std::option::Option::None;
+                            let mut message: ::std::option::Option<::std::string::String> = ::std::option::Option::None;
+                            let mut result: ::std::option::Option<#error_ident> = ::std::option::Option::None;
+                            while let ::std::option::Option::Some(key) = map.next_key::<::std::string::String>()? {
+                                match key.as_str() {
+                                    "kind" => kind = ::std::option::Option::Some(map.next_value()?),
+                                    "message" => message = ::std::option::Option::Some(map.next_value()?),
+                                    "fields" => match kind.as_deref() {
+                                        #(#branches)*
+                                        ::std::option::Option::Some(_) => { map.next_value::<::serde::de::IgnoredAny>()?; }
+                                        ::std::option::Option::None => { return ::std::result::Result::Err(::serde::de::Error::custom("kind should come before fields")); }
+                                    },
+                                    _ => { map.next_value::<::serde::de::IgnoredAny>()?; }
+                                }
+                            }
+                            if let ::std::option::Option::Some(result) = result {
+                                return ::std::result::Result::Ok(result);
+                            }
+                            let kind = kind.ok_or_else(|| ::serde::de::Error::missing_field("kind"))?;
+                            match kind.as_str() {
+                                #(#known_kinds)*
+                                _ => {}
+                            }
+                            let message = message.ok_or_else(|| ::serde::de::Error::missing_field("message"))?;
+                            #catch_all
+                        }
+                    }
+
+                    deserializer.deserialize_struct(#name, &["kind", "code", "message", "source", "fields"], AutoErrorVisitor)
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    // The schema describes the serialized form of the error
+    let schemars_impl = if enum_attrs.schemars {
+        if !generics.params.is_empty() {
+            errors.extend(TokenStream::from(syn::Error::new_spanned(&generics, "JsonSchema is not supported for generic enums").to_compile_error()));
+        }
+
+        let name = error_ident.unraw().to_string();
+        let kinds = error_variants.iter().filter(|var| !var.hidden).map(|var| var.variant.ident.unraw().to_string());
+        let has_code = error_variants.iter().any(|var| var.code.is_some()) || numeric_codes.is_some();
+        let code = if numeric_codes.is_some() {
+            Some(quote!{ "code": { "type": "integer" }, })
+        } else if has_code {
+            Some(quote!{ "code": { "type": "string" }, })
+        } else {
+            None
+        };
+        let code_required = if has_code {
+            Some(quote!{ "code", })
+        } else {
+            None
+        };
+        let fields = if enum_attrs.deserialize {
+            Some(quote!{ "fields": {}, })
+        } else {
+            None
+        };
+        Some(quote!{
+            impl ::schemars::JsonSchema for #error_ident {
+                fn schema_name() -> ::std::borrow::Cow<'static, str> {
+                    ::std::borrow::Cow::Borrowed(#name)
+                }
+
+                fn json_schema(_generator: &mut ::schemars::SchemaGenerator) -> ::schemars::Schema {
+                    ::schemars::json_schema!({
+                        "type": "object",
+                        "properties": {
+                            "kind": { "type": "string", "enum": [#(#kinds),*] },
+                            #code
+                            "message": { "type": "string" },
+                            "source": { "type": "array", "items": { "type": "string" } },
+                            #fields
+                        },
+                        "required": ["kind", #code_required "message", "source"],
+                    })
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    // Arbitrary errors pick a variant, and then fill in its fields, except
+    //  those captured where the error is created
+    let arbitrary_impl = if enum_attrs.arbitrary {
+        if !generics.params.is_empty() {
+            errors.extend(TokenStream::from(syn::Error::new_spanned(&generics, "Arbitrary is not supported for generic enums").to_compile_error()));
+        }
+
+        let constructors = error_variants.iter().map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let values = (0..var.variant.fields.len()).map(|i| {
+                if is_captured(var, i) {
+                    field_value(var, i)
+                } else {
+                    quote!{ ::arbitrary::Arbitrary::arbitrary(u)? }
+                }
+            });
+            let value = match var.variant.fields {
+                syn::Fields::Unit => quote!{ Self::#curvar },
+                _ => quote!{ Self::#curvar(#(#values),*) },
+            };
+            quote!{
+                #(#cfgs)*
+                |u| ::std::result::Result::Ok(#value),
+            }
+        });
+        Some(quote!{
+            impl<'a> ::arbitrary::Arbitrary<'a> for #error_ident {
+                fn arbitrary(u: &mut ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<Self> {
+                    let constructors: &[fn(&mut ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<#error_ident>] = &[
+                        #(#constructors)*
+                    ];
+                    let index = u.choose_index(constructors.len())?;
+                    constructors[index](u)
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    let catalog_impl = enum_attrs.catalog.as_ref().map(|info_ident| {
+        let entries = error_variants.iter().filter(|var| !var.hidden).map(|var| {
+            let name = var.variant.ident.unraw().to_string();
+            let code = match &var.code {
+                Some(code) => quote!{ ::std::option::Option::Some(#code) },
+                None => quote!{ ::std::option::Option::None },
+            };
+            let format_str = &var.format_str;
+            let doc = doc_text(&var.variant.attrs);
+            let cfgs = &var.cfgs;
+            quote!{
+                #(#cfgs)*
+                #info_ident {
+                    name: #name,
+                    code: #code,
+                    format_str: #format_str,
+                    doc: #doc,
+                },
+            }
+        });
+        let doc = format!("Description of a single [`{}`] variant, as listed in its `CATALOG`.", error_ident);
+
+        methods.push(quote!{
+            /// Descriptions of all variants of this error type.
+            pub const CATALOG: &'static [#info_ident] = &[
+                #(#entries)*
+            ];
+        });
+
+        quote!{
+            #[doc = #doc]
+            #[derive(::std::fmt::Debug, ::std::clone::Clone, ::std::marker::Copy, ::std::cmp::PartialEq, ::std::cmp::Eq)]
+            #vis struct #info_ident {
+                /// Name of the variant.
+                pub name: &'static str,
+                /// Code of the variant, if any.
+                pub code: ::std::option::Option<&'static str>,
+                /// Format string used to display the variant.
+                pub format_str: &'static str,
+                /// Doc comment of the variant.
+                pub doc: &'static str,
+            }
+        }
+    });
+
+    if enum_attrs.accessors {
+        for var in error_variants.iter().filter(|var| !var.hidden) {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let name = to_snake_case(curvar);
+            let is_fn = format_ident!("is_{}", name);
+            let is_doc = format!("Whether this is a [`Self::{}`] error.", curvar);
+            let deprecated = deprecation(var, &error_ident);
+            methods.push(quote!{
+                #(#cfgs)*
+                #[doc = #is_doc]
+                #deprecated
+                pub fn #is_fn(&self) -> bool {
+                    ::std::matches!(self, Self::#curvar { .. })
+                }
+            });
+
+            // Captured fields are left out of the contents
+            let payload = payload_fields(var);
+            if payload.is_empty() {
+                continue;
+            }
+
+            let as_fn = format_ident!("as_{}", name);
+            let as_doc = format!("The contents of this error if it is a [`Self::{}`] error.", curvar);
+            let into_fn = format_ident!("into_{}", name);
+            let into_doc = format!("Convert into the contents of this error if it is a [`Self::{}`] error, returning the error itself otherwise.", curvar);
+            let types: Vec<_> = payload.iter().map(|i| &var.variant.fields.iter().nth(*i).unwrap().ty).collect();
+            let params: Vec<_> = payload.iter().copied().map(field_binding).collect();
+            let bindings: Vec<_> = (0..var.variant.fields.len()).map(|i| {
+                if is_captured(var, i) {
+                    quote!{ _ }
+                } else {
+                    let param = field_binding(i);
+                    quote!{ #param }
+                }
+            }).collect();
+            let (ref_type, owned_type, value) = if types.len() == 1 {
+                let ty = types[0];
+                let param = &params[0];
+                (quote!{ &#ty }, quote!{ #ty }, quote!{ #param })
+            } else {
+                (quote!{ (#(&#types),*) }, quote!{ (#(#types),*) }, quote!{ (#(#params),*) })
+            };
+            methods.push(quote!{
+                #(#cfgs)*
+                #[doc = #as_doc]
+                #deprecated
+                pub fn #as_fn(&self) -> ::std::option::Option<#ref_type> {
+                    match self {
+                        Self::#curvar(#(#bindings),*) => ::std::option::Option::Some(#value),
+                        #[allow(unreachable_patterns)]
+                        _ => ::std::option::Option::None,
+                    }
+                }
+
+                #(#cfgs)*
+                #[doc = #into_doc]
+                #deprecated
+                pub fn #into_fn(self) -> ::std::result::Result<#owned_type, Self> {
+                    match self {
+                        Self::#curvar(#(#bindings),*) => ::std::result::Result::Ok(#value),
+                        #[allow(unreachable_patterns)]
+                        e => ::std::result::Result::Err(e),
+                    }
+                }
+            });
+        }
+    }
+
+    // Variants with many fields are constructed through a builder instead,
+    //  which has a setter for every field and only builds once all are set
+    let has_builder = |var: &ErrorVariant| enum_attrs.builders && payload_fields(var).len() >= 3;
+    let track_caller = if enum_attrs.track_caller {
+        Some(quote!{ #[track_caller] })
+    } else {
+        None
+    };
+    let mut builders_impl = vec![];
+    if enum_attrs.builders && !generics.params.is_empty() {
+        errors.extend(TokenStream::from(syn::Error::new_spanned(&generics, "Builders are not supported for generic enums").to_compile_error()));
+    }
+    for var in error_variants.iter().filter(|var| !var.hidden && has_builder(var)) {
+        let curvar = &var.variant.ident;
+        let cfgs = &var.cfgs;
+        let constructor = to_snake_ident(curvar);
+        let builder = format_ident!("{}Builder", curvar);
+        let payload = payload_fields(var);
+        let types: Vec<_> = payload.iter().map(|i| &var.variant.fields.iter().nth(*i).unwrap().ty).collect();
+        let type_params: Vec<_> = (0..payload.len()).map(|n| format_ident!("F{}", n)).collect();
+        // Setters are named after the names given to the fields as
+        //  message arguments
+        let mut setters = vec![];
+        for (n, i) in payload.iter().enumerate() {
+            let name = match var.arg_names.iter().find(|(j, _)| j == i) {
+                Some((_, name)) => name.clone(),
+                None => format!("arg{}", i),
+            };
+            let is_ident = name.chars().next().map_or(false, |c| c.is_alphabetic() || c == '_') && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+            if !is_ident {
+                let field = var.variant.fields.iter().nth(*i).unwrap();
+                errors.extend(TokenStream::from(syn::Error::new_spanned(field, "Builder setters need field names that are identifiers").to_compile_error()));
+                continue;
+            }
+            let setter = to_snake_ident(&syn::Ident::new(&name, curvar.span()));
+            let ty = types[n];
+            let unset: Vec<_> = type_params.iter().enumerate().filter(|(m, _)| *m != n).map(|(_, param)| param).collect();
+            let result_params = type_params.iter().enumerate().map(|(m, param)| {
+                if m == n {
+                    quote!{ (#ty,) }
+                } else {
+                    quote!{ #param }
+                }
+            });
+            let values = (0..payload.len()).map(|m| {
+                if m == n {
+                    quote!{ (::std::convert::Into::into(#setter),) }
+                } else {
+                    let index = syn::Index::from(m);
+                    quote!{ self.#index }
+                }
+            });
+            let params = type_params.iter().enumerate().map(|(m, param)| {
+                if m == n {
+                    quote!{ () }
+                } else {
+                    quote!{ #param }
+                }
+            });
+            let doc = format!("Set the {} field.", name);
+            setters.push(quote!{
+                #(#cfgs)*
+                #[allow(deprecated)]
+                impl<#(#unset),*> #builder<#(#params),*> {
+                    #[doc = #doc]
+                    pub fn #setter(self, #setter: impl ::std::convert::Into<#ty>) -> #builder<#(#result_params),*> {
+                        #builder(#(#values),*)
+                    }
+                }
+            });
+        }
+        let args = (0..var.variant.fields.len()).map(|i| {
+            if is_captured(var, i) {
+                field_value(var, i)
+            } else {
+                let index = syn::Index::from(payload.iter().position(|j| *j == i).unwrap());
+                quote!{ self.#index.0 }
+            }
+        });
+        let body = on_created(var, quote!{ #error_ident::#curvar(#(#args),*) });
+        let var_track_caller = track_caller.clone().or_else(|| location_track_caller(var));
+        let doc = format!("Builder for [`{}::{}`] errors, created by [`{}::{}`].", error_ident, curvar, error_ident, constructor.unraw());
+        let unset = type_params.iter().map(|_| quote!{ () });
+        let deprecated = deprecation(var, &error_ident);
+        builders_impl.push(quote!{
+            #(#cfgs)*
+            #[doc = #doc]
+            #deprecated
+            #[derive(::std::fmt::Debug)]
+            #[must_use]
+            #vis struct #builder<#(#type_params = ()),*>(#(#type_params),*);
+
+            #(#setters)*
+
+            #(#cfgs)*
+            #[allow(deprecated)]
+            impl #builder<#((#types,)),*> {
+                /// Build the error, once all fields are set.
+                #var_track_caller
+                pub fn build(self) -> #error_ident {
+                    #body
+                }
+            }
+        });
+        let doc = format!("Start building a [`Self::{}`] error.", curvar);
+        methods.push(quote!{
+            #(#cfgs)*
+            #[doc = #doc]
+            #deprecated
+            pub fn #constructor() -> #builder {
+                #builder(#(#unset),*)
+            }
+        });
+    }
+
+    if enum_attrs.constructors {
+        for var in error_variants.iter().filter(|var| !var.hidden && !has_builder(var)) {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let constructor = to_snake_ident(curvar);
+            let doc = format!("Construct a [`Self::{}`] error.", curvar);
+            // Captured fields are filled in rather than taken as parameter
+            let payload = payload_fields(var);
+            let types = payload.iter().map(|i| &var.variant.fields.iter().nth(*i).unwrap().ty);
+            let params: Vec<_> = payload.iter().copied().map(field_binding).collect();
+            let args = (0..var.variant.fields.len()).map(|i| {
+                if is_captured(var, i) {
+                    field_value(var, i)
+                } else {
+                    let param = field_binding(i);
+                    quote!{ ::std::convert::Into::into(#param) }
+                }
+            });
+            let body = match var.variant.fields {
+                syn::Fields::Unit => quote!{ Self::#curvar },
+                _ => quote!{ Self::#curvar(#(#args),*) },
+            };
+            let body = on_created(var, body);
+            let track_caller = track_caller.clone().or_else(|| location_track_caller(var));
+            let deprecated = deprecation(var, &error_ident);
+            methods.push(quote!{
+                #(#cfgs)*
+                #[doc = #doc]
+                #deprecated
+                #track_caller
+                pub fn #constructor(#(#params: impl ::std::convert::Into<#types>),*) -> Self {
+                    #body
+                }
+            });
+        }
+    }
+
+    // Generated tests construct every variant whose fields can be made up,
+    //  checking its message when that is known, and whether it has a source
+    let tests_impl = if enum_attrs.generate_tests {
+        if !generics.params.is_empty() {
+            errors.extend(TokenStream::from(syn::Error::new_spanned(&generics, "Generated tests are not supported for generic enums").to_compile_error()));
+        }
+        let tests = error_variants.iter().filter_map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let payload = payload_fields(var);
+            let mut displays = vec![];
+            let mut values = vec![];
+            for (i, field) in var.variant.fields.iter().enumerate() {
+                if is_captured(var, i) {
+                    values.push(field_value(var, i));
+                    continue;
+                }
+                let n = payload.iter().position(|j| *j == i).unwrap();
+                let (value, display) = placeholder_value(&field.ty, n)?;
+                values.push(value);
+                displays.push(display);
+            }
+            let value = match var.variant.fields {
+                syn::Fields::Unit => quote!{ super::#error_ident::#curvar },
+                _ => quote!{ super::#error_ident::#curvar(#(#values),*) },
+            };
+
+            let message = if var.hidden {
+                Some("unknown error".to_string())
+            } else if enum_attrs.localized || !is_plain_format_str(&var.format_str) || appended_source(var).is_some() {
+                None
+            } else {
+                expected_message(&var.format_str, &displays)
+            };
+            // Messages that can't be predicted, like localized ones, are
+            //  still displayed to check that formatting doesn't fail
+            let message = match message {
+                Some(message) => quote!{ ::std::assert_eq!(::std::string::ToString::to_string(&error), #message); },
+                None => quote!{ let _ = ::std::string::ToString::to_string(&error); },
+            };
+            let source = if var.err {
+                quote!{ ::std::assert!(::std::error::Error::source(&error).is_some()); }
+            } else {
+                quote!{ ::std::assert!(::std::error::Error::source(&error).is_none()); }
+            };
+
+            let name = format_ident!("{}_message_and_source", to_snake_case(curvar));
+            Some(quote!{
+                #(#cfgs)*
+                #[test]
+                pub(super) fn #name() {
+                    let error = #value;
+                    #message
+                    #source
+                }
+            })
+        });
+        let module = format_ident!("{}_autoerror_tests", to_snake_case(&error_ident));
+        Some(quote!{
+            #[cfg(test)]
+            #[allow(deprecated)]
+            mod #module {
+                #(#tests)*
+            }
+        })
+    } else {
+        None
+    };
+
+    // Conversions into the target error type map every variant, either by
+    //  passing its payload to the given variant or function, or through an
+    //  expression using the error itself. With into_variants, variants map
+    //  to the variant of the same name by default.
+    let into_impl = match &enum_attrs.into {
+        Some(target) => {
+            if !generics.params.is_empty() {
+                errors.extend(TokenStream::from(syn::Error::new_spanned(&generics, "Conversions into other error types are not supported for generic enums").to_compile_error()));
+            }
+            for var in error_variants.iter().filter(|var| var.into.is_none() && !enum_attrs.into_variants) {
+                errors.extend(TokenStream::from(syn::Error::new_spanned(var.variant, "Missing into, all variants need one when the enum converts into another error type").to_compile_error()));
+            }
+
+            let branches = error_variants.iter().map(|var| {
+                let curvar = &var.variant.ident;
+                let cfgs = &var.cfgs;
+                let into = match &var.into {
+                    Some(into) => into.clone(),
+                    None => syn::parse_quote!{ <#target>::#curvar },
+                };
+                let payload = payload_fields(var);
+                match into {
+                    syn::Expr::Path(path) if payload.is_empty() => quote!{
+                        #(#cfgs)*
+                        #error_ident::#curvar { .. } => #path,
+                    },
+                    syn::Expr::Path(path) => {
+                        let bindings = (0..var.variant.fields.len()).map(|i| {
+                            if payload.contains(&i) {
+                                field_binding(i).to_token_stream()
+                            } else {
+                                quote!{ _ }
+                            }
+                        });
+                        let params = payload.iter().copied().map(field_binding);
+                        quote!{
+                            #(#cfgs)*
+                            #error_ident::#curvar(#(#bindings),*) => #path(#(#params),*),
+                        }
+                    }
+                    expr => quote!{
+                        #(#cfgs)*
+                        #error_ident::#curvar { .. } => #expr,
+                    },
+                }
+            });
+            Some(quote!{
+                impl ::std::convert::From<#error_ident> for #target {
+                    fn from(error: #error_ident) -> Self {
+                        match error {
+                            #(#branches)*
+                        }
+                    }
+                }
+            })
+        }
+        None => {
+            if let Some(into) = error_variants.iter().find_map(|var| var.into.as_ref()) {
+                errors.extend(TokenStream::from(syn::Error::new_spanned(into, "The into conversion needs the target type given on the enum, as in #[auto_error(into = \"ApiError\")]").to_compile_error()));
+            }
+            None
+        }
+    };
+
+    // Error codes need to be given for all visible variants, and be unique
+    if error_variants.iter().any(|var| var.code.is_some()) {
+        let mut codes: Vec<(String, &syn::Ident)> = vec![];
+        for var in error_variants.iter() {
+            let code = match &var.code {
+                Some(code) => code,
+                None if var.hidden => continue,
+                None => {
+                    errors.extend(TokenStream::from(syn::Error::new_spanned(var.variant, "Missing code, all variants need a code when any of them has one").to_compile_error()));
+                    continue;
+                },
+            };
+            if let Some((_, other)) = codes.iter().find(|(c, _)| *c == code.value()) {
+                errors.extend(TokenStream::from(syn::Error::new_spanned(code, format!("Duplicate code, also used by {}", other)).to_compile_error()));
+            }
+            codes.push((code.value(), &var.variant.ident));
+        }
+
+        let branches = error_variants.iter().filter(|var| var.code.is_some()).map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let code = var.code.as_ref().unwrap();
+            quote!{ #(#cfgs)* Self::#curvar { .. } => ::std::option::Option::Some(#code), }
+        });
+        let fallback = if error_variants.iter().any(|var| var.code.is_none()) {
+            Some(quote!{ _ => ::std::option::Option::None, })
+        } else {
+            None
+        };
+        methods.push(quote!{
+            /// The stable code identifying this kind of error, which only
+            ///  hidden variants are without.
+            pub fn code(&self) -> ::std::option::Option<&'static str> {
+                match self {
+                    #(#branches)*
+                    #fallback
+                }
+            }
+        });
+    }
+
+    // Process exit codes, defaulting to the general failure code 1
+    if enum_attrs.termination.is_some() || error_variants.iter().any(|var| var.exit_code.is_some()) {
+        let branches = error_variants.iter().map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let exit_code = var.exit_code.unwrap_or(1);
+            quote!{ #(#cfgs)* Self::#curvar { .. } => #exit_code, }
+        });
+        methods.push(quote!{
+            /// The process exit code for this error.
+            pub fn exit_code(&self) -> ::std::process::ExitCode {
+                let exit_code: u8 = match self {
+                    #(#branches)*
+                };
+                ::std::process::ExitCode::from(exit_code)
+            }
+        });
+    }
+
+    // Raw OS error codes, in both directions. As variants are constructed
+    //  from just an error code, they can't have payload fields, and the same
+    //  code can't be used twice. Codes given as constants are compared by
+    //  name only.
+    let mut errnos: Vec<(String, &syn::Ident)> = vec![];
+    for var in error_variants.iter().filter(|var| var.errno.is_some()) {
+        let errno = var.errno.as_ref().unwrap();
+        if let Some(i) = payload_fields(var).first() {
+            let field = var.variant.fields.iter().nth(*i).unwrap();
+            errors.extend(TokenStream::from(syn::Error::new_spanned(field, "Variants with an errno can't have payload fields, as they are constructed from just the error code").to_compile_error()));
+        }
+        let value = match errno {
+            syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(v), .. }) => v.base10_digits().to_string(),
+            _ => errno.to_token_stream().to_string(),
+        };
+        if let Some((_, other)) = errnos.iter().find(|(e, _)| *e == value) {
+            errors.extend(TokenStream::from(syn::Error::new_spanned(errno, format!("Duplicate errno, also used by {}", other)).to_compile_error()));
+        }
+        errnos.push((value, &var.variant.ident));
+    }
+    if error_variants.iter().any(|var| var.errno.is_some()) {
+        let branches = error_variants.iter().filter(|var| var.errno.is_some()).map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let errno = var.errno.as_ref().unwrap();
+            quote!{ #(#cfgs)* Self::#curvar { .. } => ::std::option::Option::Some(#errno), }
+        });
+        let fallback = if error_variants.iter().any(|var| var.errno.is_none()) {
+            Some(quote!{ _ => ::std::option::Option::None, })
+        } else {
+            None
+        };
+        let track_caller = if error_variants.iter().any(|var| var.errno.is_some() && var.location_field.is_some()) {
+            Some(quote!{ #[track_caller] })
+        } else {
+            None
+        };
+        let from_branches = error_variants.iter().filter(|var| var.errno.is_some()).map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let errno = var.errno.as_ref().unwrap();
+            let value = if var.variant.fields.is_empty() {
+                quote!{ Self::#curvar }
+            } else {
+                let args = (0..var.variant.fields.len()).map(|i| field_value(var, i));
+                quote!{ Self::#curvar(#(#args),*) }
+            };
+            quote!{
+                #(#cfgs)*
+                if errno == #errno {
+                    return ::std::option::Option::Some(#value);
+                }
+            }
+        });
+        methods.push(quote!{
+            /// The raw OS error code corresponding to this error, if any.
+            pub fn raw_os_error(&self) -> ::std::option::Option<i32> {
+                match self {
+                    #(#branches)*
+                    #fallback
+                }
+            }
+
+            /// The error corresponding to the given raw OS error code, if any.
+            #track_caller
+            pub fn from_raw_os_error(errno: i32) -> ::std::option::Option<Self> {
+                #(#from_branches)*
+                ::std::option::Option::None
+            }
+
+            /// The error corresponding to the raw OS error code of the given
+            ///  I/O error, if any.
+            #track_caller
+            pub fn from_io_error(e: &::std::io::Error) -> ::std::option::Option<Self> {
+                match e.raw_os_error() {
+                    ::std::option::Option::Some(errno) => Self::from_raw_os_error(errno),
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+        });
+    }
+
+    // Retryability, with variants wrapping the error type itself asking the
+    //  wrapped error unless marked explicitly
+    if error_variants.iter().any(|var| var.retryable.is_some()) {
+        let branches = error_variants.iter().map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            match (var.retryable, var.source_field) {
+                (Some(retryable), _) => quote!{ #(#cfgs)* Self::#curvar { .. } => #retryable, },
+                (None, Some(source_field)) if is_self_type(&var.variant.fields.iter().nth(source_field).unwrap().ty, &error_ident) => {
+                    let fields = (0..var.variant.fields.len()).map(|i| {
+                        if i == source_field {
+                            quote!{ inner }
+                        } else {
+                            quote!{ _ }
+                        }
+                    });
+                    quote!{ #(#cfgs)* Self::#curvar(#(#fields),*) => inner.is_retryable(), }
+                }
+                (None, _) => quote!{ #(#cfgs)* Self::#curvar { .. } => false, },
+            }
+        });
+        methods.push(quote!{
+            /// Whether the operation that failed with this error may succeed
+            ///  when retried.
+            pub fn is_retryable(&self) -> bool {
+                match self {
+                    #(#branches)*
+                }
+            }
+        });
+    }
+
+    // Severity levels, defaulting to error
+    let severity_impl = if error_variants.iter().any(|var| var.severity.is_some()) {
+        let severity_ident = format_ident!("{}Severity", error_ident);
+        let branches = error_variants.iter().map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let level = var.severity.clone().unwrap_or_else(|| format_ident!("Error"));
+            quote!{ #(#cfgs)* Self::#curvar { .. } => #severity_ident::#level, }
+        });
+        let levels: Vec<_> = SEVERITIES.iter().map(|(_, level, _)| format_ident!("{}", level)).collect();
+        let names = SEVERITIES.iter().map(|(name, _, _)| name);
+        let level_docs = SEVERITIES.iter().map(|(_, _, doc)| doc);
+        let doc = format!("The severity of a [`{}`], ordered from least to most severe.", error_ident);
+
+        methods.push(quote!{
+            /// The severity of this error.
+            pub fn severity(&self) -> #severity_ident {
+                match self {
+                    #(#branches)*
+                }
+            }
+        });
+
+        Some(quote!{
+            #[doc = #doc]
+            #[derive(::std::fmt::Debug, ::std::clone::Clone, ::std::marker::Copy, ::std::cmp::PartialEq, ::std::cmp::Eq, ::std::cmp::PartialOrd, ::std::cmp::Ord, ::std::hash::Hash)]
+            #vis enum #severity_ident {
+                #(#[doc = #level_docs] #levels,)*
+            }
+
+            impl #severity_ident {
+                /// The name of this severity, as used in the severity attribute.
+                pub fn as_str(&self) -> &'static str {
+                    match self {
+                        #(Self::#levels => #names,)*
+                    }
+                }
+            }
+
+            impl ::std::fmt::Display for #severity_ident {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    f.write_str(self.as_str())
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    // Log levels, defaulting to error
+    if enum_attrs.log {
+        let branches = error_variants.iter().map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let level = var.log_level.clone().unwrap_or_else(|| format_ident!("Error"));
+            quote!{ #(#cfgs)* Self::#curvar { .. } => ::log::Level::#level, }
+        });
+        methods.push(quote!{
+            /// The level at which this error should be logged.
+            pub fn log_level(&self) -> ::log::Level {
+                match self {
+                    #(#branches)*
+                }
+            }
+        });
+    }
+
+    // Tracing events use the log level of the variant, as the level of an
+    //  event needs to be known at compile time
+    if enum_attrs.tracing {
+        let code = if error_variants.iter().any(|var| var.code.is_some()) || numeric_codes.is_some() {
+            Some(quote!{ code = self.code(), })
+        } else {
+            None
+        };
+        let branches = error_variants.iter().map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let level = format_ident!("{}", var.log_level.as_ref().map_or("ERROR".to_string(), |level| level.to_string().to_uppercase()));
+            let name = curvar.unraw().to_string();
+            quote!{ #(#cfgs)* Self::#curvar { .. } => ::tracing::event!(::tracing::Level::#level, kind = #name, #code "{}", self), }
+        });
+        methods.push(quote!{
+            /// Emit a tracing event for this error, with the variant name as
+            ///  kind, the code when given, and the error message.
+            pub fn record(&self) {
+                match self {
+                    #(#branches)*
+                }
+            }
+
+            /// This error as a value that can be recorded in tracing fields,
+            ///  as in `tracing::warn!(error = e.as_value(), "retrying")`.
+            pub fn as_value(&self) -> &(dyn ::std::error::Error + 'static)
+            where
+                Self: 'static,
+            {
+                self
+            }
+        });
+    }
+
+    // Errors are recorded on the current OpenTelemetry span following the
+    //  semantic conventions for exceptions, marking the span as failed
+    if enum_attrs.otel {
+        let code = if error_variants.iter().any(|var| var.code.is_some()) {
+            Some(quote!{
+                if let ::std::option::Option::Some(code) = self.code() {
+                    attributes.push(::opentelemetry::KeyValue::new("code", code));
+                }
+            })
+        } else if numeric_codes.is_some() {
+            Some(quote!{ attributes.push(::opentelemetry::KeyValue::new("code", self.code() as i64)); })
+        } else {
+            None
+        };
+        methods.push(quote!{
+            /// Record this error as an exception on the current OpenTelemetry
+            ///  span, with the variant name as type, the error message and the
+            ///  code when given, and set the status of the span to error.
+            pub fn record_exception(&self) {
+                ::opentelemetry::trace::get_active_span(|span| {
+                    let message = ::std::string::ToString::to_string(self);
+                    let mut attributes = ::std::vec![
+                        ::opentelemetry::KeyValue::new("exception.type", self.variant_name()),
+                        ::opentelemetry::KeyValue::new("exception.message", ::std::clone::Clone::clone(&message)),
+                    ];
+                    #code
+                    span.add_event("exception", attributes);
+                    span.set_status(::opentelemetry::trace::Status::error(message));
+                })
+            }
+        });
+    }
+
+    if enum_attrs.sentry {
+        let branches = error_variants.iter().map(|var| {
+            let curvar = &var.variant.ident;
+            let cfgs = &var.cfgs;
+            let capture = sentry_capture(var, quote!{ self });
+            quote!{ #(#cfgs)* Self::#curvar { .. } => #capture, }
+        });
+        methods.push(quote!{
+            /// Report this error and its chain of sources to Sentry, grouped by
+            ///  the code of the variant, or its name when it has no code.
+            pub fn capture(&self) -> ::sentry::types::Uuid {
+                match self {
+                    #(#branches)*
+                }
+            }
+        });
+    }
+
+    let termination_impl = enum_attrs.termination.as_ref().map(|termination_ident| {
+        let print_help = if has_help {
+            Some(quote!{
+                if let ::std::option::Option::Some(help) = e.help() {
+                    ::std::eprintln!("help: {}", help);
+                }
+            })
+        } else {
+            None
+        };
+        let doc = format!("Result of a program failing with a [`{}`], to be returned from `main`.", error_ident);
+        let mut termination_generics = generics.clone();
+        let termination_where = termination_generics.make_where_clause();
+        termination_where.predicates.push(syn::parse_quote!{ #error_ident #ty_generics: ::std::fmt::Debug + ::std::fmt::Display + 'static });
+        termination_where.predicates.extend(source_predicates.iter().cloned());
+        let termination_where = &termination_generics.where_clause;
+        quote!{
+            #[doc = #doc]
+            #[derive(::std::fmt::Debug)]
+            #vis struct #termination_ident #impl_generics (pub ::std::result::Result<(), #error_ident #ty_generics>) #where_clause;
+
+            impl #impl_generics ::std::process::Termination for #termination_ident #ty_generics #termination_where {
+                fn report(self) -> ::std::process::ExitCode {
+                    match self.0 {
+                        ::std::result::Result::Ok(()) => ::std::process::ExitCode::SUCCESS,
+                        ::std::result::Result::Err(e) => {
+                            ::std::eprintln!("error: {}", e);
+                            for source in e.chain().skip(1) {
+                                ::std::eprintln!("caused by: {}", source);
+                            }
+                            #print_help
+                            e.exit_code()
+                        }
+                    }
+                }
+            }
+
+            impl #impl_generics ::std::convert::From<::std::result::Result<(), #error_ident #ty_generics>> for #termination_ident #ty_generics #where_clause {
+                fn from(result: ::std::result::Result<(), #error_ident #ty_generics>) -> Self {
+                    Self(result)
+                }
+            }
+
+            impl #impl_generics ::std::convert::From<#error_ident #ty_generics> for #termination_ident #ty_generics #where_clause {
+                fn from(e: #error_ident #ty_generics) -> Self {
+                    Self(::std::result::Result::Err(e))
+                }
+            }
+        }
+    });
+
+    let whatever_variants: Vec<_> = error_variants.iter().filter(|var| var.whatever).collect();
+    if whatever_variants.len() > 1 {
+        errors.extend(TokenStream::from(syn::Error::new_spanned(whatever_variants[1].variant, "Only one variant can be marked as whatever").to_compile_error()));
+    }
+    if let Some(var) = whatever_variants.first() {
+        let curvar = &var.variant.ident;
+        let cfgs = &var.cfgs;
+        let doc = format!("Construct a [`Self::{}`] error with the given message.", curvar);
+        let track_caller = location_track_caller(var);
+        // The message is the first field that isn't captured, followed by
+        //  the source, if any
+        let message_field = payload_fields(var)[0];
+        let construct = |source: proc_macro2::TokenStream| {
+            let args = (0..var.variant.fields.len()).map(|i| {
+                if i == message_field {
+                    quote!{ ::std::convert::Into::into(::std::string::ToString::to_string(&message)) }
+                } else if Some(i) == var.source_field {
+                    source.clone()
+                } else {
+                    field_value(var, i)
+                }
+            });
+            on_created(var, quote!{ Self::#curvar(#(#args),*) })
+        };
+        if let Some(source_field) = var.source_field {
+            let source_ty = &var.variant.fields.iter().nth(source_field).unwrap().ty;
+            let (source_inner, wrap) = match strip_option(source_ty) {
+                Some(inner) => (inner, quote!{ ::std::option::Option::Some }),
+                None => (source_ty, quote!{}),
+            };
+            let source_doc = format!("Construct a [`Self::{}`] error with the given message and source.", curvar);
+            let body = construct(quote!{ ::std::default::Default::default() });
+            let source_body = construct(quote!{ #wrap(::std::convert::Into::into(source)) });
+            methods.push(quote!{
+                #(#cfgs)*
+                #[doc = #doc]
+                #track_caller
+                pub fn whatever(message: impl ::std::fmt::Display) -> Self {
+                    #body
+                }
+
+                #(#cfgs)*
+                #[doc = #source_doc]
+                #track_caller
+                pub fn whatever_with_source(source: impl ::std::convert::Into<#source_inner>, message: impl ::std::fmt::Display) -> Self {
+                    #source_body
+                }
+            });
+        } else {
+            let body = construct(quote!{});
+            methods.push(quote!{
+                #(#cfgs)*
+                #[doc = #doc]
+                #track_caller
+                pub fn whatever(message: impl ::std::fmt::Display) -> Self {
+                    #body
+                }
+            });
+        }
+    }
+
+    if enum_attrs.display_chain {
+        let help = if has_help {
+            quote!{ self.help() }
+        } else {
+            quote!{ ::std::option::Option::None }
+        };
+        methods.push(quote!{
+            /// Display this error followed by its chain of sources, one per line,
+            ///  and its help text, if any.
+            pub fn display_chain(&self) -> impl ::std::fmt::Display + '_
+            where
+                Self: 'static,
+            {
+                struct DisplayChain<'a>(&'a (dyn ::std::error::Error + 'static), ::std::option::Option<&'static str>);
+
+                impl ::std::fmt::Display for DisplayChain<'_> {
+                    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                        ::std::write!(f, "error: {}", self.0)?;
+                        let mut source = self.0.source();
+                        while let ::std::option::Option::Some(e) = source {
+                            ::std::write!(f, "\ncaused by: {}", e)?;
+                            source = e.source();
+                        }
+                        if let ::std::option::Option::Some(help) = self.1 {
+                            ::std::write!(f, "\nhelp: {}", help)?;
+                        }
+                        ::std::result::Result::Ok(())
+                    }
+                }
+
+                DisplayChain(self, #help)
+            }
+        });
+    }
+
+    if !aggregate_branches.is_empty() {
+        methods.push(quote!{
+            /// Iterate over all the underlying errors of this error. For most variants
+            ///  this is the same as source, but aggregate variants return all their
+            ///  elements.
+            pub fn sources(&self) -> impl ::std::iter::Iterator<Item = &(dyn ::std::error::Error + 'static)> + '_ {
+                let result: ::std::boxed::Box<dyn ::std::iter::Iterator<Item = &(dyn ::std::error::Error + 'static)> + '_> = match self {
+                    #(#aggregate_branches)*
+                    _ => ::std::boxed::Box::new(::std::error::Error::source(self).into_iter()),
+                };
+                result
+            }
+        });
+    }
+
+    // Joined Vec fields are displayed through a single helper, shared by
+    //  all variants
+    let join_helper = if error_variants.iter().any(|var| (0..var.variant.fields.len()).any(|i| is_joined(var, i))) {
+        Some(quote!{
+            struct AutoErrorJoin<'a, T>(&'a [T], &'static str);
+
+            impl<T: ::std::fmt::Display> ::std::fmt::Display for AutoErrorJoin<'_, T> {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    for (i, item) in self.0.iter().enumerate() {
+                        if i != 0 {
+                            f.write_str(self.1)?;
+                        }
+                        ::std::fmt::Display::fmt(item, f)?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+
+            impl<T: ::std::fmt::Debug> ::std::fmt::Debug for AutoErrorJoin<'_, T> {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    ::std::fmt::Debug::fmt(self.0, f)
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    if !errors.is_empty() {
+        return errors;
+    }
+
+    // Named items are generated at item level, while the implementations
+    //  go in an anonymous const, isolating them from the surrounding module
+    TokenStream::from(quote! {
+        #kind_impl
+        #catalog_impl
+        #problem_details_impl
+        #jsonrpc_impl
+        #severity_impl
+        #termination_impl
+        #ffi_impl
+        #macros_impl
+        #result_alias_impl
+        #context_impl
+        #(#builders_impl)*
+        #context_ext_impl
+        #tests_impl
+
+        #[allow(deprecated)]
+        const _: () = {
+            #(#from_impls)*
+            #(#route_impls)*
+            #poison_impl
+            #panic_impl
+            #(#tuple_from_impls)*
+            #join_helper
+
+            impl #impl_generics ::std::fmt::Display for #error_ident #ty_generics #display_where {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    #display_validation
+                    #display_localized
+                    #display_table
+
+                    match self {
+                        #(#display_branches)*
+                        #display_fallback
+                    }
+                }
+            }
+
+            #debug_impl
+            #eq_impl
+            #hash_impl
+            #actix_impl
+            #warp_impl
+            #rocket_impl
+            #tonic_impl
+            #pyo3_impl
+            #napi_impl
+            #miette_impl
+            #log_kv_impl
+            #slog_impl
+            #valuable_impl
+            #i18n_impl
+            #async_graphql_impl
+            #serde_impl
+            #deserialize_impl
+            #schemars_impl
+            #arbitrary_impl
+            #into_impl
+
+            #[allow(dead_code, deprecated)]
+            impl #impl_generics #error_ident #ty_generics #error_where {
+                #(#methods)*
+            }
+
+            impl #impl_generics ::std::error::Error for #error_ident #ty_generics #error_where {
+                fn source(&self) -> ::std::option::Option<&(dyn ::std::error::Error + 'static)> {
+                    #source_body
+                }
+            }
+        };
+    })
+}
+
+/// Attribute macro form of the AutoError derive, which can also modify the
+///  enum itself. The enum is given `#[derive(AutoError)]` unless it already
+///  derives it, and otherwise takes the same attributes as the derive.
+///
+/// The attribute takes the following parameters
+///  - clone makes the error type `Clone`, by wrapping the errors held by source
+///    fields in an `Arc` (replacing a `Box`, and looking through `Option` and
+///    `Vec`) and deriving `Clone`. The wrapped fields are marked with
+///    `#[auto_error(arc)]`, so the generated `From` implementations still take
+///    the error itself.
+///  - backtrace and location append a field marked with `#[auto_error(backtrace)]`
+///    or `#[auto_error(location)]` to every variant with fields, capturing the
+///    backtrace or location where the error was created. With clone, the
+///    backtrace is shared through an `Arc` as well.
+///  - instance_id takes a type, as in `instance_id = "ErrorId"`, and appends a
+///    field of that type marked with `#[auto_error(instance_id)]` to every variant
+///    with fields, generating a unique ID for every error created.
+///
+/// # Example
+///
+/// ```
+/// #[autoerror::autoerror(clone)]
+/// #[derive(Debug)]
+/// enum Error {
+///     #[auto_error(format_str="Document not found")]
+///     NotFound,
+///     IO(std::io::Error),
+/// }
+///
+/// let e = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+/// let _cached = e.clone();
+/// ```
+#[proc_macro_attribute]
+pub fn autoerror(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as syn::AttributeArgs);
+    let mut input = parse_macro_input!(input as DeriveInput);
+
+    let mut clone = false;
+    let mut backtrace = false;
+    let mut location = false;
+    let mut instance_id: Option<syn::Type> = None;
+    for arg in args.iter() {
+        match arg {
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("clone") => {
+                clone = true;
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("backtrace") => {
+                backtrace = true;
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("location") => {
+                location = true;
+            }
+            syn::NestedMeta::Meta(syn::Meta::NameValue(arg)) if arg.path.is_ident("instance_id") => {
+                instance_id = match &arg.lit {
+                    syn::Lit::Str(v) => match v.parse() {
+                        Ok(ty) => Some(ty),
+                        Err(e) => { return TokenStream::from(e.to_compile_error()); },
+                    },
+                    _ => { return TokenStream::from(syn::Error::new_spanned(&arg.lit, "Incorrect value for instance_id, expected type").to_compile_error()); },
+                };
+            }
+            _ => { return TokenStream::from(syn::Error::new_spanned(arg_name(arg), "Unknown parameter").to_compile_error()); },
+        }
+    }
+
+    let error_ident = input.ident.clone();
+    let enumdecl = if let syn::Data::Enum(e) = &mut input.data {
+        e
+    } else {
+        return TokenStream::from(syn::Error::new_spanned(&input.ident, "autoerror only supports enums").to_compile_error());
+    };
+
+    // Sources are shared through an Arc to make them cloneable. Variants
+    //  the derive can't handle are left as is, for it to report.
+    if clone {
+        let heuristic = parse_enum_attrs(&input.attrs, &error_ident).map(|enum_attrs| enum_attrs.heuristic).unwrap_or_default();
+        for variant in enumdecl.variants.iter_mut() {
+            let source_field = match parse_variant(variant, &error_ident, &heuristic) {
+                Ok(var) if var.err => var.source_field.filter(|i| !var.arc_fields.contains(i)),
+                _ => None,
+            };
+            let field = match source_field.and_then(|i| variant.fields.iter_mut().nth(i)) {
+                Some(field) if !is_self_type(&field.ty, &error_ident) => field,
+                _ => continue,
+            };
+            if let Some(ty) = share_type(&field.ty) {
+                field.ty = ty;
+                field.attrs.push(syn::parse_quote!{ #[auto_error(arc)] });
+            }
+        }
+    }
+
+    // Every variant with fields gets the requested captured fields appended,
+    //  unless it already has them
+    for variant in enumdecl.variants.iter_mut() {
+        let mut errors = TokenStream::new();
+        let field_attrs = parse_field_attrs(variant, &mut errors);
+        if !errors.is_empty() {
+            continue;
+        }
+        let fields = match &mut variant.fields {
+            syn::Fields::Unnamed(fields) if !fields.unnamed.is_empty() => fields,
+            _ => continue,
+        };
+        if backtrace && field_attrs.backtrace.is_none() {
+            let field: syn::FieldsUnnamed = if clone {
+                syn::parse_quote!{ (#[auto_error(backtrace)] ::std::sync::Arc<::std::backtrace::Backtrace>) }
+            } else {
+                syn::parse_quote!{ (#[auto_error(backtrace)] ::std::backtrace::Backtrace) }
+            };
+            fields.unnamed.extend(field.unnamed);
+        }
+        if location && field_attrs.location.is_none() {
+            let field: syn::FieldsUnnamed = syn::parse_quote!{ (#[auto_error(location)] &'static ::std::panic::Location<'static>) };
+            fields.unnamed.extend(field.unnamed);
+        }
+        if let Some(ty) = instance_id.as_ref().filter(|_| field_attrs.instance_id.is_none()) {
+            let field: syn::FieldsUnnamed = syn::parse_quote!{ (#[auto_error(instance_id)] #ty) };
+            fields.unnamed.extend(field.unnamed);
+        }
+    }
+
+    // The derives go first, as their helper attributes can't precede them
+    if clone && !derives(&input.attrs, "Clone") {
+        input.attrs.insert(0, syn::parse_quote!{ #[derive(::std::clone::Clone)] });
+    }
+    if !derives(&input.attrs, "AutoError") {
+        input.attrs.insert(0, syn::parse_quote!{ #[derive(::autoerror::AutoError)] });
+    }
+
+    TokenStream::from(input.into_token_stream())
+}
+
+// A variant in the error_enum! DSL: the variant with its fields, followed by
+//  an optional message and an optional list of auto_error parameters
+struct DslVariant {
+    attrs: Vec<syn::Attribute>,
+    ident: syn::Ident,
+    fields: syn::Fields,
+    message: Option<syn::LitStr>,
+    params: Vec<syn::NestedMeta>,
+}
+
+impl syn::parse::Parse for DslVariant {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let ident: syn::Ident = input.parse()?;
+        let fields = if input.peek(syn::token::Paren) {
+            syn::Fields::Unnamed(input.parse()?)
+        } else {
+            syn::Fields::Unit
+        };
+        let message = if input.peek(syn::Token![=>]) {
+            input.parse::<syn::Token![=>]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let mut params = Vec::new();
+        if input.peek(syn::token::Bracket) {
+            let content;
+            syn::bracketed!(content in input);
+            let nested = content.parse_terminated::<_, syn::Token![,]>(syn::NestedMeta::parse)?;
+            params.extend(nested);
+        }
+        Ok(DslVariant { attrs, ident, fields, message, params })
+    }
+}
+
+// The enum declared through the error_enum! DSL
+struct DslEnum {
+    attrs: Vec<syn::Attribute>,
+    vis: syn::Visibility,
+    ident: syn::Ident,
+    generics: syn::Generics,
+    variants: syn::punctuated::Punctuated<DslVariant, syn::Token![,]>,
+}
+
+impl syn::parse::Parse for DslEnum {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<syn::Token![enum]>()?;
+        let ident = input.parse()?;
+        let mut generics: syn::Generics = input.parse()?;
+        generics.where_clause = input.parse()?;
+        let content;
+        syn::braced!(content in input);
+        let variants = content.parse_terminated(DslVariant::parse)?;
+        Ok(DslEnum { attrs, vis, ident, generics, variants })
+    }
+}
+
+/// Terse declaration of an error enum, one line per variant, expanding to the
+///  enum deriving AutoError.
+///
+/// Each variant is written as its name and fields, optionally followed by
+///  `=> "message"` giving its format string and a list of further auto_error
+///  parameters in brackets. In that list, from is short for `make_from=true`.
+///  Attributes on the enum and its variants are kept, including auto_error
+///  attributes, which are merged with the parameters given in the list.
+///
+/// # Example
+///
+/// ```
+/// autoerror::error_enum! {
+///     #[derive(Debug)]
+///     pub enum Error {
+///         NotFound(String) => "User {} not found",
+///         IO(std::io::Error),
+///         Other(String) => "Other: {}" [from],
+///         Timeout => "Request timed out",
+///     }
+/// }
+///
+/// let e = Error::from("oops".to_string());
+/// assert_eq!(e.to_string(), "Other: oops");
+/// ```
+#[proc_macro]
+pub fn error_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DslEnum);
+
+    let variants = input.variants.into_iter().map(|variant| {
+        let mut params = Vec::new();
+        if let Some(message) = &variant.message {
+            params.push(quote!{ format_str = #message });
+        }
+        for param in variant.params.iter() {
+            match param {
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("from") => {
+                    params.push(quote!{ make_from = true });
+                }
+                _ => params.push(param.to_token_stream()),
+            }
+        }
+
+        // Parameters from the list are added to an existing auto_error
+        //  attribute, as the derive only takes one per variant
+        let mut attrs = variant.attrs;
+        if !params.is_empty() {
+            match attrs.iter_mut().find(|attr| attr.path.is_ident("auto_error")) {
+                Some(attr) => {
+                    let existing = match attr.parse_meta() {
+                        Ok(syn::Meta::List(list)) => list.nested,
+                        _ => { return Err(syn::Error::new_spanned(&attr, "Incorrect auto_error arguments")); },
+                    };
+                    *attr = syn::parse_quote!{ #[auto_error(#existing, #(#params),*)] };
+                }
+                None => attrs.push(syn::parse_quote!{ #[auto_error(#(#params),*)] }),
+            }
+        }
+
+        let ident = &variant.ident;
+        let fields = &variant.fields;
+        Ok(quote!{ #(#attrs)* #ident #fields })
+    }).collect::<syn::Result<Vec<_>>>();
+    let variants = match variants {
+        Ok(variants) => variants,
+        Err(e) => { return TokenStream::from(e.to_compile_error()); },
+    };
+
+    let attrs = &input.attrs;
+    let derive = if derives(attrs, "AutoError") {
+        None
+    } else {
+        Some(quote!{ #[derive(::autoerror::AutoError)] })
+    };
+    let vis = &input.vis;
+    let ident = &input.ident;
+    let generics = &input.generics;
+    let where_clause = &input.generics.where_clause;
+
+    TokenStream::from(quote!{
+        #derive
+        #(#attrs)*
+        #vis enum #ident #generics #where_clause {
+            #(#variants),*
         }
     })
 }
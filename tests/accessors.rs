@@ -0,0 +1,29 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(accessors)]
+enum Error {
+    #[auto_error(format_str = "not found")]
+    NotFound,
+    IO(std::io::Error),
+    #[auto_error(format_str = "{} at {}")]
+    ParseFailure(String, usize),
+    HTTPError(u16),
+}
+
+pub fn main() {
+    let e = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert!(e.is_io());
+    assert!(!e.is_not_found());
+    assert_eq!(e.as_io().unwrap().to_string(), "oops");
+    assert!(e.as_parse_failure().is_none());
+    let e = e.into_parse_failure().unwrap_err();
+    assert_eq!(e.into_io().unwrap().to_string(), "oops");
+
+    let e = Error::ParseFailure("unexpected".to_string(), 4);
+    assert_eq!(e.as_parse_failure(), Some((&"unexpected".to_string(), &4)));
+    assert_eq!(e.into_parse_failure().unwrap(), ("unexpected".to_string(), 4));
+
+    assert!(Error::NotFound.is_not_found());
+    assert_eq!(Error::HTTPError(404).into_http_error().unwrap(), 404);
+}
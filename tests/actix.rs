@@ -0,0 +1,24 @@
+use actix_web::body::MessageBody;
+use actix_web::http::StatusCode;
+use actix_web::ResponseError;
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(actix)]
+enum Error {
+    #[auto_error(status = 404, format_str = "no such user {}")]
+    UserNotFound(u32),
+    #[auto_error(body = "internal error")]
+    Io(std::io::Error),
+}
+
+pub fn main() {
+    let e = Error::UserNotFound(7);
+    assert_eq!(e.status_code(), StatusCode::NOT_FOUND);
+    assert_eq!(e.error_response().status(), StatusCode::NOT_FOUND);
+    assert_eq!(e.error_response().into_body().try_into_bytes().unwrap(), "no such user 7");
+
+    let e = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert_eq!(e.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(e.error_response().into_body().try_into_bytes().unwrap(), "internal error");
+}
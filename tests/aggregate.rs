@@ -0,0 +1,36 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(format_str = "batch failed: {}")]
+    Batch(Vec<std::io::Error>),
+    #[auto_error(separator = "; ")]
+    Messages(Vec<String>),
+    #[auto_error(format_str = "fields {:?}")]
+    Fields(Vec<String>),
+    Io(std::io::Error),
+}
+
+use std::error::Error as StdError;
+
+pub fn main() {
+    let e = Error::from(vec![
+        std::io::Error::new(std::io::ErrorKind::Other, "first"),
+        std::io::Error::new(std::io::ErrorKind::Other, "second"),
+    ]);
+    assert_eq!(format!("{}", e), "batch failed: first, second");
+    assert_eq!(format!("{}", e.source().unwrap()), "first");
+    let sources: Vec<_> = e.sources().map(|e| e.to_string()).collect();
+    assert_eq!(sources, ["first", "second"]);
+
+    let e = Error::Messages(vec!["a".to_string(), "b".to_string()]);
+    assert_eq!(format!("{}", e), "a; b");
+    assert!(e.source().is_none());
+    assert_eq!(e.sources().count(), 0);
+
+    let e = Error::Fields(vec!["a".to_string(), "b".to_string()]);
+    assert_eq!(format!("{}", e), "fields [\"a\", \"b\"]");
+
+    let e = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "single"));
+    assert_eq!(e.sources().count(), 1);
+}
@@ -0,0 +1,23 @@
+#![deny(warnings)]
+
+use autoerror::AutoError;
+use std::error::Error as _;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    Io(std::io::Error),
+    Fmt(std::fmt::Error),
+}
+
+#[derive(Debug, AutoError)]
+enum NoSources {
+    #[auto_error(format_str = "not found")]
+    NotFound,
+    Invalid(String),
+}
+
+pub fn main() {
+    assert!(Error::from(std::fmt::Error).source().is_some());
+    assert!(NoSources::NotFound.source().is_none());
+    assert!(NoSources::Invalid("x".to_string()).source().is_none());
+}
@@ -0,0 +1,19 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    Anyhow(anyhow::Error),
+    #[auto_error(err = "as_ref", make_from = false)]
+    Other(anyhow::Error),
+}
+
+use std::error::Error as StdError;
+
+pub fn main() {
+    let e = Error::from(anyhow::anyhow!("oops"));
+    assert_eq!(format!("{}", e), "oops");
+    assert_eq!(format!("{}", e.source().unwrap()), "oops");
+
+    let e = Error::Other(anyhow::Error::new(std::fmt::Error));
+    assert!(e.source().unwrap().is::<std::fmt::Error>());
+}
@@ -0,0 +1,39 @@
+use autoerror::AutoError;
+use arbitrary::{Arbitrary, Unstructured};
+
+#[derive(Debug, AutoError)]
+#[auto_error(arbitrary)]
+enum ParseError {
+    #[auto_error(format_str = "unexpected character {}")]
+    Unexpected(char),
+    #[auto_error(format_str = "unexpected end of input")]
+    Eof,
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(arbitrary)]
+enum Error {
+    #[auto_error(format_str = "no such user {}")]
+    UserNotFound(u32),
+    #[auto_error(format_str = "could not parse {}")]
+    Parse(String, #[auto_error(source)] ParseError),
+    #[auto_error(format_str = "timed out")]
+    Timeout,
+}
+
+pub fn main() {
+    let mut seen = [false; 3];
+    for seed in 0..=255u8 {
+        let data: Vec<u8> = (0..64).map(|i| seed.wrapping_mul(31).wrapping_add(i)).collect();
+        let mut u = Unstructured::new(&data);
+        let error = Error::arbitrary(&mut u).unwrap();
+        let index = match error {
+            Error::UserNotFound(_) => 0,
+            Error::Parse(_, _) => 1,
+            Error::Timeout => 2,
+        };
+        seen[index] = true;
+        assert!(!error.to_string().is_empty());
+    }
+    assert_eq!(seen, [true; 3]);
+}
@@ -0,0 +1,23 @@
+use async_graphql::ErrorExtensions;
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(async_graphql)]
+enum Error {
+    #[auto_error(code = "E0404", status = 404, format_str = "no such user {}")]
+    UserNotFound(u32),
+    #[auto_error(code = "E1042")]
+    Io(std::io::Error),
+}
+
+pub fn main() {
+    let error = Error::UserNotFound(7).extend();
+    assert_eq!(error.message, "no such user 7");
+    let extensions = error.extensions.unwrap();
+    assert_eq!(extensions.get("variant"), Some(&async_graphql::Value::from("UserNotFound")));
+    assert_eq!(extensions.get("code"), Some(&async_graphql::Value::from("E0404")));
+    assert_eq!(extensions.get("status"), Some(&async_graphql::Value::from(404)));
+
+    let error = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops")).extend();
+    assert_eq!(error.extensions.unwrap().get("status"), Some(&async_graphql::Value::from(500)));
+}
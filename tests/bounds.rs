@@ -0,0 +1,58 @@
+use autoerror::AutoError;
+
+use std::error::Error as StdError;
+use std::fmt;
+
+#[derive(Debug)]
+struct Token;
+
+#[derive(Debug, AutoError)]
+#[auto_error(no_bound(T))]
+enum RequestError<T> {
+    #[auto_error(format_str = "request timed out")]
+    Timeout(T),
+    #[auto_error(format_str = "invalid status {}")]
+    Status(u16),
+}
+
+trait Describe {
+    fn describe(&self) -> String;
+}
+
+impl Describe for Token {
+    fn describe(&self) -> String {
+        "token".to_string()
+    }
+}
+
+struct Described<T>(T);
+
+impl<T: Describe> fmt::Display for Described<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.describe())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Described<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(bound = "T: Describe + fmt::Debug")]
+enum LookupError<T> {
+    #[auto_error(format_str = "missing {}")]
+    Missing(Described<T>),
+}
+
+pub fn main() {
+    let e = RequestError::Timeout(Token);
+    assert_eq!(e.to_string(), "request timed out");
+    assert!(e.source().is_none());
+    assert_eq!(RequestError::<Token>::Status(503).to_string(), "invalid status 503");
+
+    let e = LookupError::Missing(Described(Token));
+    assert_eq!(e.to_string(), "missing token");
+    assert!(e.source().is_none());
+}
@@ -0,0 +1,37 @@
+use autoerror::AutoError;
+use std::error::Error as StdError;
+
+#[derive(Debug)]
+pub struct BigError {
+    details: [u64; 32],
+}
+
+impl std::fmt::Display for BigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "big error {}", self.details[0])
+    }
+}
+
+impl StdError for BigError {}
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(boxed, err = true)]
+    Big(Box<BigError>),
+    #[auto_error(format_str = "parse failed at {}")]
+    Parse(usize, #[auto_error(from, boxed)] Box<std::num::ParseIntError>),
+    #[auto_error(format_str = "timed out")]
+    Timeout,
+}
+
+pub fn main() {
+    assert!(std::mem::size_of::<Error>() < std::mem::size_of::<BigError>());
+
+    let big = Error::from(BigError { details: [7; 32] });
+    assert_eq!(big.to_string(), "big error 7");
+    assert!(big.source().unwrap().is::<BigError>());
+
+    let parse = Error::from("x".parse::<u8>().unwrap_err());
+    assert!(matches!(parse, Error::Parse(0, _)));
+    assert!(parse.source().unwrap().is::<std::num::ParseIntError>());
+}
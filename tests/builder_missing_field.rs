@@ -0,0 +1,16 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(builders)]
+enum Error {
+    #[auto_error(format_str = "{2} at line {0}, column {1}")]
+    ParseError(
+        #[auto_error(arg = "line")] u32,
+        #[auto_error(arg = "column")] u32,
+        #[auto_error(arg = "message")] String,
+    ),
+}
+
+fn main() {
+    let _ = Error::parse_error().line(12u32).message("missing comma").build();
+}
@@ -0,0 +1,36 @@
+use autoerror::AutoError;
+
+use std::panic::Location;
+
+#[derive(Debug, AutoError)]
+#[auto_error(builders, constructors)]
+enum Error {
+    #[auto_error(format_str = "{2} at line {0}, column {1}")]
+    ParseError(
+        #[auto_error(arg = "line")] u32,
+        #[auto_error(arg = "column")] u32,
+        #[auto_error(arg = "message")] String,
+        #[auto_error(location)] &'static Location<'static>,
+    ),
+    #[auto_error(format_str = "unexpected {}")]
+    Unexpected(String),
+    #[auto_error(format_str = "{} {} {}")]
+    Triple(u8, u8, u8),
+}
+
+pub fn main() {
+    let e = Error::parse_error().line(12u32).column(4u32).message("missing comma").build();
+    assert_eq!(e.to_string(), "missing comma at line 12, column 4");
+    assert_eq!(e.location().unwrap().line(), line!() - 2);
+
+    // Fields can be set in any order
+    let e = Error::parse_error().message("stray brace").column(1u32).line(3u32).build();
+    assert_eq!(e.to_string(), "stray brace at line 3, column 1");
+
+    let e = Error::triple().arg2(3).arg0(1).arg1(2).build();
+    assert_eq!(e.to_string(), "1 2 3");
+
+    // Variants with fewer fields keep their positional constructor
+    let e = Error::unexpected("token");
+    assert_eq!(e.to_string(), "unexpected token");
+}
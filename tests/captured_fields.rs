@@ -0,0 +1,62 @@
+use autoerror::AutoError;
+
+#[autoerror::autoerror(backtrace, location)]
+#[derive(Debug)]
+#[auto_error(constructors, accessors, context_selectors)]
+enum Error {
+    #[auto_error(format_str = "not found")]
+    NotFound,
+    Io(std::io::Error),
+    #[auto_error(format_str = "could not parse {}")]
+    Parse(String, #[auto_error(source)] std::num::ParseIntError),
+    #[auto_error(whatever)]
+    Other(String),
+}
+
+#[autoerror::autoerror(clone, backtrace)]
+#[derive(Debug)]
+enum CloneError {
+    Io(std::io::Error),
+}
+
+#[derive(Debug, AutoError)]
+enum ManualError {
+    #[auto_error(format_str = "{} failed")]
+    Failed(#[auto_error(location)] &'static std::panic::Location<'static>, String),
+    #[auto_error(format_str = "missing")]
+    Missing,
+}
+
+pub fn main() {
+    let line = line!() + 1;
+    let e = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert_eq!(e.to_string(), "oops");
+    assert_eq!(e.location().unwrap().line(), line);
+    assert!(e.backtrace().is_some());
+    assert!(std::error::Error::source(&e).is_some());
+    assert_eq!(e.as_io().unwrap().to_string(), "oops");
+
+    let line = line!() + 1;
+    let e = "x".parse::<u32>().context(ParseContext("x")).unwrap_err();
+    assert_eq!(e.to_string(), "could not parse x");
+    assert_eq!(e.location().unwrap().line(), line);
+
+    let line = line!() + 1;
+    let e = Error::whatever("something");
+    assert_eq!(e.to_string(), "something");
+    assert_eq!(e.location().unwrap().line(), line);
+
+    let e = Error::parse("y", "y".parse::<u32>().unwrap_err());
+    assert_eq!(e.as_parse().unwrap().0, "y");
+    assert!(Error::NotFound.location().is_none());
+    assert!(Error::NotFound.backtrace().is_none());
+
+    let e = CloneError::from(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert!(e.clone().backtrace().is_some());
+
+    let line = line!() + 1;
+    let e = ManualError::Failed(std::panic::Location::caller(), "upload".to_string());
+    assert_eq!(e.to_string(), "upload failed");
+    assert_eq!(e.location().unwrap().line(), line);
+    assert!(ManualError::Missing.location().is_none());
+}
@@ -0,0 +1,25 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(catalog)]
+enum Error {
+    /// The requested item does not exist.
+    #[auto_error(code = "E0404", format_str = "not found: {}")]
+    NotFound(String),
+    /// Reading or writing failed.
+    ///  Usually retryable.
+    #[auto_error(code = "E1042")]
+    Io(std::io::Error),
+}
+
+pub fn main() {
+    assert_eq!(Error::CATALOG.len(), 2);
+    assert_eq!(Error::CATALOG[0], ErrorInfo {
+        name: "NotFound",
+        code: Some("E0404"),
+        format_str: "not found: {}",
+        doc: "The requested item does not exist.",
+    });
+    assert_eq!(Error::CATALOG[1].name, "Io");
+    assert_eq!(Error::CATALOG[1].doc, "Reading or writing failed.\nUsually retryable.");
+}
@@ -0,0 +1,42 @@
+use autoerror::AutoError;
+
+#[derive(Debug, Default)]
+struct Trace(&'static str);
+
+impl std::fmt::Display for Trace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(accessors, constructors, context_selectors)]
+enum Error {
+    #[cfg_attr(all(), auto_error(format_str = "could not read {} ({})"))]
+    #[cfg_attr(not(all()), auto_error(format_str = "could not read {}"))]
+    Read(String, #[cfg(all())] Trace, #[cfg(any())] u32, #[auto_error(source)] std::io::Error),
+    #[cfg_attr(any(), auto_error(format_str = "invalid {} ({})"))]
+    #[cfg_attr(not(any()), auto_error(format_str = "invalid {}"))]
+    Invalid(String, #[cfg(any())] Trace),
+    Io(std::io::Error, #[cfg(all())] Trace),
+    Fmt(std::fmt::Error, #[cfg(any())] Trace),
+}
+
+pub fn main() {
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "oops");
+    let e = Error::Read("config".to_string(), Trace("at start"), io);
+    assert_eq!(e.to_string(), "could not read config (at start)");
+    assert!(std::error::Error::source(&e).is_some());
+    assert_eq!(e.as_read().unwrap().1.0, "at start");
+
+    let e = Error::invalid("key");
+    assert_eq!(e.to_string(), "invalid key");
+    assert_eq!(e.as_invalid(), Some(&"key".to_string()));
+
+    let e = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert!(e.is_io());
+    assert!(std::error::Error::source(&e).is_some());
+
+    let e = Error::from(std::fmt::Error);
+    assert!(e.is_fmt());
+}
@@ -0,0 +1,30 @@
+use autoerror::AutoError;
+
+#[cfg(any())]
+mod postgres {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(accessors, constructors, context_selectors, kind, catalog)]
+enum Error {
+    #[auto_error(code = "E0404", format_str = "not found")]
+    NotFound,
+    #[cfg(any())]
+    #[auto_error(code = "E5000", err = true)]
+    Postgres(postgres::Error),
+    #[cfg(all())]
+    #[auto_error(code = "E1042")]
+    Io(std::io::Error),
+}
+
+pub fn main() {
+    let e = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert!(e.is_io());
+    assert_eq!(e.code(), Some("E1042"));
+    assert_eq!(e.kind(), ErrorKind::Io);
+    assert!(std::error::Error::source(&e).is_some());
+    assert_eq!(Error::CATALOG.len(), 2);
+    assert_eq!(Error::not_found().to_string(), "not found");
+}
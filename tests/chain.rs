@@ -0,0 +1,24 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(format_str = "not found")]
+    NotFound,
+    #[auto_error(format_str = "{}: {}")]
+    Context(String, Box<Error>),
+    Io(std::io::Error),
+}
+
+pub fn main() {
+    let e = Error::Context(
+        "outer".to_string(),
+        Box::new(Error::Context("inner".to_string(), Box::new(Error::from(std::io::Error::new(std::io::ErrorKind::Other, "disk full"))))),
+    );
+    let messages: Vec<_> = e.chain().map(|e| e.to_string()).collect();
+    assert_eq!(messages, ["outer: inner: disk full", "inner: disk full", "disk full", "disk full"]);
+    assert!(e.root_cause().is::<std::io::Error>());
+
+    let e = Error::NotFound;
+    assert_eq!(e.chain().count(), 1);
+    assert!(e.root_cause().is::<Error>());
+}
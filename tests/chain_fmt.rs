@@ -0,0 +1,43 @@
+use autoerror::AutoError;
+use std::error::Error as StdError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(chain_fmt)]
+enum Error {
+    #[auto_error(format_str = "failed to save user {}")]
+    Save(String, std::io::Error),
+    #[auto_error(err = true, format_str = "failed to load")]
+    Load(Option<std::io::Error>),
+    #[auto_error(format_str = "failed to connect", chain_fmt = false)]
+    Connect(std::io::Error),
+    #[auto_error(format_str = "no such user {}")]
+    NotFound(String),
+}
+
+#[derive(Debug, AutoError)]
+enum ParseError {
+    #[auto_error(format_str = "invalid number", chain_fmt)]
+    Number(#[auto_error(source)] std::num::ParseIntError),
+    #[auto_error(format_str = "invalid float")]
+    Float(#[auto_error(source)] std::num::ParseFloatError),
+}
+
+fn io(message: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::Other, message)
+}
+
+pub fn main() {
+    let save = Error::Save("alice".to_string(), io("disk full"));
+    assert_eq!(save.to_string(), "failed to save user alice: disk full");
+    assert_eq!(save.source().unwrap().to_string(), "disk full");
+
+    assert_eq!(Error::Load(Some(io("missing"))).to_string(), "failed to load: missing");
+    assert_eq!(Error::Load(None).to_string(), "failed to load");
+    assert_eq!(Error::Connect(io("refused")).to_string(), "failed to connect");
+    assert_eq!(Error::NotFound("bob".to_string()).to_string(), "no such user bob");
+
+    let number = ParseError::Number("x".parse::<u8>().unwrap_err());
+    assert_eq!(number.to_string(), "invalid number: invalid digit found in string");
+    let float = ParseError::Float("x".parse::<f32>().unwrap_err());
+    assert_eq!(float.to_string(), "invalid float");
+}
@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+#[autoerror::autoerror(clone)]
+#[derive(Debug)]
+enum Error {
+    #[auto_error(format_str = "not found")]
+    NotFound,
+    Io(std::io::Error),
+    #[auto_error(format_str = "could not parse {}")]
+    Parse(String, #[auto_error(source)] Box<dyn std::error::Error + Send + Sync>),
+    #[auto_error(format_str = "request failed")]
+    Request(#[auto_error(source)] Option<std::io::Error>, u16),
+    #[auto_error(format_str = "already shared")]
+    Shared(Arc<std::io::Error>),
+}
+
+pub fn main() {
+    let e = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    let cached = e.clone();
+    assert_eq!(cached.to_string(), "oops");
+    match (&e, &cached) {
+        (Error::Io(a), Error::Io(b)) => assert!(Arc::ptr_eq(a, b)),
+        _ => panic!("expected Io errors"),
+    }
+
+    let e = Error::Parse("config".to_string(), Arc::from(Box::<dyn std::error::Error + Send + Sync>::from("bad")));
+    assert_eq!(e.clone().to_string(), "could not parse config");
+    assert_eq!(std::error::Error::source(&e).unwrap().to_string(), "bad");
+
+    let e = Error::Request(None, 500);
+    assert!(std::error::Error::source(&e.clone()).is_none());
+
+    let e = Error::from(Arc::new(std::io::Error::new(std::io::ErrorKind::Other, "shared")));
+    assert_eq!(std::error::Error::source(&e).unwrap().to_string(), "shared");
+    assert_eq!(Error::NotFound.clone().to_string(), "not found");
+}
@@ -0,0 +1,15 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(code = "E0404", format_str = "not found")]
+    NotFound,
+    #[auto_error(code = "E1042")]
+    Io(std::io::Error),
+}
+
+pub fn main() {
+    assert_eq!(Error::NotFound.code(), Some("E0404"));
+    let e = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert_eq!(e.code(), Some("E1042"));
+}
@@ -0,0 +1,26 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(compact)]
+enum Error {
+    #[auto_error(format_str = "Document not found")]
+    NotFound,
+    #[auto_error(format_str = "{1} of {0} in {{braces}}")]
+    Swapped(String, u32),
+    #[auto_error(format_str = "Took {:.1}s")]
+    Slow(f64),
+    #[auto_error(format_str = "Missing {}", separator = ", ")]
+    Missing(Vec<String>),
+    Io(std::io::Error),
+    Other(String),
+}
+
+pub fn main() {
+    assert_eq!(Error::NotFound.to_string(), "Document not found");
+    assert_eq!(Error::Swapped("page".to_string(), 3).to_string(), "3 of page in {braces}");
+    assert_eq!(Error::Slow(1.25).to_string(), "Took 1.2s");
+    assert_eq!(Error::Missing(vec!["a".to_string(), "b".to_string()]).to_string(), "Missing a, b");
+    let e = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert_eq!(e.to_string(), "oops");
+    assert_eq!(format!("{:>8}", Error::Other("padded".to_string())), "  padded");
+}
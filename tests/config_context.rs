@@ -0,0 +1,49 @@
+use autoerror::AutoError;
+
+use std::error::Error as StdError;
+use std::path::PathBuf;
+
+#[derive(Debug, AutoError)]
+enum ConfigError {
+    #[auto_error(format_str = "invalid port {}")]
+    InvalidPort(
+        u32,
+        #[auto_error(config_path)] PathBuf,
+        #[auto_error(config_key)] String,
+    ),
+    #[auto_error(format_str = "could not parse number", chain_fmt, err = true, make_from = true)]
+    Parse(
+        std::num::ParseIntError,
+        #[auto_error(config_path)] Option<PathBuf>,
+        #[auto_error(config_key)] Option<String>,
+    ),
+    #[auto_error(format_str = "could not read configuration")]
+    Read(std::io::Error, #[auto_error(config_path)] PathBuf),
+    #[auto_error(format_str = "no configuration found")]
+    Missing,
+}
+
+fn parse_port(value: &str) -> Result<u32, ConfigError> {
+    Ok(value.parse()?)
+}
+
+pub fn main() {
+    let e = ConfigError::InvalidPort(70000, PathBuf::new(), String::new());
+    assert_eq!(e.to_string(), "invalid port 70000");
+    let e = e.in_config("config.toml").at_key("server.port");
+    assert_eq!(e.to_string(), "invalid port 70000 in config.toml at key server.port");
+
+    let e = parse_port("eighty").unwrap_err();
+    assert_eq!(e.to_string(), "could not parse number: invalid digit found in string");
+    let e = e.at_key("server.port");
+    assert_eq!(e.to_string(), "could not parse number at key server.port: invalid digit found in string");
+    assert!(e.source().is_some());
+
+    let e = ConfigError::from(std::io::Error::new(std::io::ErrorKind::NotFound, "not found"))
+        .in_config(PathBuf::from("/etc/app/config.toml"))
+        .at_key("ignored");
+    assert_eq!(e.to_string(), "could not read configuration in /etc/app/config.toml");
+
+    let e = ConfigError::Missing.in_config("config.toml");
+    assert_eq!(e.to_string(), "no configuration found");
+}
@@ -0,0 +1,10 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    Read(std::io::Error),
+    #[auto_error(format_str = "write failed")]
+    Write(std::io::Error),
+}
+
+fn main() {}
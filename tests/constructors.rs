@@ -0,0 +1,18 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(constructors, track_caller)]
+enum Error {
+    #[auto_error(format_str = "{} not found")]
+    NotFound(String),
+    #[auto_error(format_str = "{} at line {}")]
+    Parse(String, u64),
+    #[auto_error(format_str = "timeout")]
+    Timeout,
+}
+
+pub fn main() {
+    assert_eq!(Error::not_found("user 5").to_string(), "user 5 not found");
+    assert_eq!(Error::parse("unexpected token", 3u32).to_string(), "unexpected token at line 3");
+    assert!(matches!(Error::timeout(), Error::Timeout));
+}
@@ -0,0 +1,53 @@
+use autoerror::AutoError;
+use std::error::Error as StdError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(context)]
+    Context(String, Box<dyn StdError + Send + Sync>),
+    #[auto_error(format_str = "invalid port {}")]
+    InvalidPort(u16),
+}
+
+mod lookup {
+    use autoerror::AutoError;
+
+    #[derive(Debug, AutoError)]
+    pub enum LookupError {
+        #[auto_error(context)]
+        Context(String, Option<std::io::Error>),
+    }
+
+    pub fn find(values: &[u32], value: u32) -> Result<usize, LookupError> {
+        values.iter().position(|v| *v == value).with_context(|| format!("finding {}", value))
+    }
+}
+
+fn read_config(path: &str) -> Result<String, Error> {
+    let contents = std::fs::read_to_string(path).context("loading config")?;
+    let port: u16 = contents.trim().parse().with_context(|| format!("parsing port in {}", path))?;
+    if port == 0 {
+        return Err(Error::InvalidPort(port));
+    }
+    Ok(contents)
+}
+
+pub fn main() {
+    let error = read_config("/nonexistent/config").unwrap_err();
+    assert_eq!(error.to_string(), "loading config");
+    assert!(error.source().unwrap().downcast_ref::<std::io::Error>().is_some());
+
+    let error: Result<u16, Error> = "x".parse::<u16>().context("parsing port");
+    let error = error.unwrap_err();
+    assert_eq!(error.to_string(), "parsing port");
+    assert!(error.source().unwrap().is::<std::num::ParseIntError>());
+
+    assert_eq!(lookup::find(&[1, 2, 3], 2).unwrap(), 1);
+    let error = lookup::find(&[1, 2, 3], 4).unwrap_err();
+    assert_eq!(error.to_string(), "finding 4");
+    assert!(error.source().is_none());
+
+    let io: Result<(), std::io::Error> = Err(std::io::Error::new(std::io::ErrorKind::Other, "disk"));
+    let error = io.context("saving").unwrap_err();
+    assert_eq!(error.source().unwrap().to_string(), "disk");
+}
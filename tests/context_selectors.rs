@@ -0,0 +1,42 @@
+mod error {
+    use autoerror::AutoError;
+
+    #[derive(Debug, AutoError)]
+    #[auto_error(context_selectors)]
+    pub enum Error {
+        #[auto_error(format_str = "could not read config {}: {}")]
+        ReadConfig(String, #[auto_error(source)] std::io::Error),
+        #[auto_error(format_str = "missing key {}")]
+        MissingKey(String),
+        #[auto_error(format_str = "empty")]
+        Empty,
+    }
+}
+
+use error::{Error, EmptyContext, ErrorOptionExt, ErrorResultExt, MissingKeyContext, ReadConfigContext};
+
+fn read(path: &str) -> Result<String, Error> {
+    let r: Result<String, std::io::Error> = Err(std::io::Error::new(std::io::ErrorKind::NotFound, "no such file"));
+    r.context(ReadConfigContext(path))
+}
+
+pub fn main() {
+    let e = read("app.toml").unwrap_err();
+    assert_eq!(e.to_string(), "could not read config app.toml: no such file");
+
+    let e = None::<u32>.context(MissingKeyContext("port")).unwrap_err();
+    assert_eq!(e.to_string(), "missing key port");
+
+    let e = MissingKeyContext("host").build();
+    assert_eq!(e.to_string(), "missing key host");
+    assert!(EmptyContext.fail::<()>().is_err());
+
+    let r: Result<String, std::io::Error> = Err(std::io::Error::new(std::io::ErrorKind::NotFound, "gone"));
+    let e = r.with_context(|| ReadConfigContext(format!("{}.toml", "db"))).unwrap_err();
+    assert_eq!(e.to_string(), "could not read config db.toml: gone");
+
+    let found = Some(8080).with_context(|| -> MissingKeyContext<String> { unreachable!() });
+    assert_eq!(found.unwrap(), 8080);
+    let e = None::<u32>.with_context(|| MissingKeyContext(String::from("user"))).unwrap_err();
+    assert_eq!(e.to_string(), "missing key user");
+}
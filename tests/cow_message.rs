@@ -0,0 +1,25 @@
+use autoerror::AutoError;
+use std::borrow::Cow;
+
+#[derive(Debug, AutoError)]
+pub enum Error {
+    Message(Cow<'static, str>),
+    #[auto_error(make_from = false)]
+    Detail(std::borrow::Cow<'static, str>),
+    Io(std::io::Error),
+}
+
+pub fn main() {
+    let e = Error::from("static message");
+    assert!(matches!(&e, Error::Message(Cow::Borrowed("static message"))));
+    assert_eq!(e.to_string(), "static message");
+
+    let e = Error::from(format!("owned {}", 1));
+    assert!(matches!(&e, Error::Message(Cow::Owned(message)) if message == "owned 1"));
+    assert_eq!(e.to_string(), "owned 1");
+
+    let e: Error = "converted".into();
+    assert_eq!(e.to_string(), "converted");
+
+    assert_eq!(Error::Detail(Cow::Borrowed("detail")).to_string(), "detail");
+}
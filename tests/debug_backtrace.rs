@@ -0,0 +1,18 @@
+use autoerror::AutoError;
+
+#[derive(AutoError)]
+#[auto_error(debug)]
+enum Error {
+    Io(std::io::Error, #[auto_error(backtrace)] std::backtrace::Backtrace),
+    #[auto_error(format_str = "invalid input")]
+    Invalid,
+}
+
+pub fn main() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "1");
+    let e = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "disk full"));
+    let debug = format!("{:?}", e);
+    assert!(debug.starts_with("disk full\n\nCaused by:\n    disk full\n\nStack backtrace:\n"));
+    assert!(debug.ends_with(&e.backtrace().unwrap().to_string()));
+    assert_eq!(format!("{:?}", Error::Invalid), "invalid input");
+}
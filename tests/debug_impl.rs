@@ -0,0 +1,23 @@
+use autoerror::AutoError;
+
+#[derive(AutoError)]
+#[auto_error(debug)]
+enum Error<T> {
+    #[auto_error(format_str = "{}: {}")]
+    Context(String, Box<Error<T>>),
+    Io(std::io::Error),
+    #[auto_error(format_str = "value {}")]
+    Value(T),
+}
+
+pub fn main() {
+    let e: Error<u8> = Error::Context(
+        "loading config".to_string(),
+        Box::new(Error::from(std::io::Error::new(std::io::ErrorKind::Other, "disk full"))),
+    );
+    assert_eq!(
+        format!("{:?}", e),
+        "loading config: disk full\n\nCaused by:\n    disk full\n    disk full",
+    );
+    assert_eq!(format!("{:?}", Error::Value(5)), "value 5");
+}
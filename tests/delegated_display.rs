@@ -0,0 +1,33 @@
+use autoerror::AutoError;
+use std::fmt;
+
+#[derive(Debug)]
+pub struct Pretty;
+
+impl fmt::Display for Pretty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("pretty\n  printed")
+        } else {
+            f.write_str("pretty")
+        }
+    }
+}
+
+#[derive(Debug, AutoError)]
+pub enum Error {
+    Pretty(Pretty),
+    Name(String),
+    #[auto_error(format_str = "{0}")]
+    Ratio(f64),
+    #[auto_error(format_str = "name: {}")]
+    Labeled(String),
+}
+
+pub fn main() {
+    assert_eq!(format!("{}", Error::Pretty(Pretty)), "pretty");
+    assert_eq!(format!("{:#}", Error::Pretty(Pretty)), "pretty\n  printed");
+    assert_eq!(format!("{:>6}", Error::Name("bob".to_string())), "   bob");
+    assert_eq!(format!("{:.2}", Error::Ratio(0.125)), "0.12");
+    assert_eq!(format!("{:>12}", Error::Labeled("bob".to_string())), "name: bob");
+}
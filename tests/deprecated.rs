@@ -0,0 +1,29 @@
+#![deny(warnings)]
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(accessors, constructors, builders, context_selectors, kind, eq, hash, macros)]
+enum Error {
+    #[deprecated(note = "use Parse instead")]
+    #[auto_error(format_str = "old parse {}")]
+    OldParse(String),
+    #[auto_error(format_str = "legacy {} {} {}", superseded_by = "Parse")]
+    Legacy(u8, u8, u8),
+    #[auto_error(format_str = "parse {}")]
+    Parse(String),
+}
+
+pub fn main() {
+    let e = Error::parse("x");
+    assert!(e.is_parse());
+    assert_eq!(e.kind(), ErrorKind::Parse);
+    assert_eq!(Error::KINDS.len(), 3);
+
+    #[allow(deprecated)]
+    {
+        let e = Error::old_parse("y");
+        assert!(e.is_old_parse());
+        let e = Error::legacy().arg0(1).arg1(2).arg2(3).build();
+        assert_eq!(e.to_string(), "legacy 1 2 3");
+    }
+}
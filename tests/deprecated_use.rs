@@ -0,0 +1,20 @@
+#![deny(deprecated)]
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(accessors, constructors)]
+enum Error {
+    #[deprecated(note = "use Parse instead")]
+    #[auto_error(format_str = "old parse {}")]
+    OldParse(String),
+    #[auto_error(format_str = "legacy {}", superseded_by = "Parse")]
+    Legacy(String),
+    #[auto_error(format_str = "parse {}")]
+    Parse(String),
+}
+
+fn main() {
+    let e = Error::old_parse("x");
+    let _ = e.is_old_parse();
+    let _ = Error::legacy("y");
+}
@@ -0,0 +1,53 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(deserialize)]
+enum Error {
+    #[auto_error(format_str = "no such user {}")]
+    UserNotFound(u32),
+    #[auto_error(format_str = "invalid {} at {}")]
+    Invalid(String, usize),
+    #[auto_error(format_str = "timed out")]
+    Timeout,
+    #[auto_error(deserialize = false)]
+    Io(std::io::Error),
+    #[auto_error(catch_all, format_str = "{}")]
+    Other(String),
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(deserialize)]
+enum WhateverError {
+    #[auto_error(format_str = "timed out")]
+    Timeout,
+    #[auto_error(whatever)]
+    Whatever(String, Option<Box<dyn std::error::Error + Send + Sync>>),
+}
+
+fn round_trip(e: &Error) -> Error {
+    serde_json::from_str(&serde_json::to_string(e).unwrap()).unwrap()
+}
+
+pub fn main() {
+    assert!(matches!(round_trip(&Error::UserNotFound(7)), Error::UserNotFound(7)));
+    assert!(matches!(round_trip(&Error::Invalid("key".to_string(), 3)), Error::Invalid(key, 3) if key == "key"));
+    assert!(matches!(round_trip(&Error::Timeout), Error::Timeout));
+    assert_eq!(
+        serde_json::to_string(&Error::UserNotFound(7)).unwrap(),
+        r#"{"kind":"UserNotFound","message":"no such user 7","source":[],"fields":[7]}"#
+    );
+
+    let e = round_trip(&Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops")));
+    assert!(matches!(e, Error::Other(message) if message == "oops"));
+
+    let e: WhateverError = serde_json::from_str(r#"{"kind":"Gone","message":"gone"}"#).unwrap();
+    assert_eq!(e.to_string(), "gone");
+    let e: WhateverError = serde_json::from_str(r#"{"kind":"Timeout","fields":null,"message":"timed out"}"#).unwrap();
+    assert!(matches!(e, WhateverError::Timeout));
+
+    assert!(serde_json::from_str::<Error>(r#"{"message":"oops"}"#).is_err());
+
+    let e = serde_json::from_str::<Error>(r#"{"kind":"UserNotFound","message":"no such user 7"}"#).unwrap_err();
+    assert!(e.to_string().starts_with("missing field `fields`"));
+    assert!(serde_json::from_str::<WhateverError>(r#"{"kind":"Timeout","message":"timed out"}"#).is_err());
+}
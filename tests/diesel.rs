@@ -0,0 +1,34 @@
+use autoerror::AutoError;
+
+use std::error::Error as StdError;
+
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+
+#[derive(Debug, AutoError)]
+enum UserError {
+    #[auto_error(diesel = "unique_violation", format_str = "user already exists")]
+    Conflict(diesel::result::Error),
+    #[auto_error(diesel = "not_found", format_str = "user not found")]
+    NotFound,
+    Database(diesel::result::Error),
+}
+
+fn violation(kind: DatabaseErrorKind) -> DieselError {
+    DieselError::DatabaseError(kind, Box::new("constraint violated".to_string()))
+}
+
+pub fn main() {
+    let e: UserError = violation(DatabaseErrorKind::UniqueViolation).into();
+    assert!(matches!(e, UserError::Conflict(_)));
+    assert_eq!(e.to_string(), "user already exists");
+    assert!(e.source().is_some());
+
+    let e: UserError = DieselError::NotFound.into();
+    assert!(matches!(e, UserError::NotFound));
+    assert!(e.source().is_none());
+
+    let e: UserError = violation(DatabaseErrorKind::ForeignKeyViolation).into();
+    assert!(matches!(e, UserError::Database(_)));
+    assert_eq!(e.to_string(), "constraint violated");
+    assert!(e.source().is_some());
+}
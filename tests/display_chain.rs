@@ -0,0 +1,20 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(display_chain)]
+enum Error {
+    #[auto_error(format_str = "{}: {}")]
+    Context(String, Box<Error>),
+    Io(std::io::Error),
+}
+
+pub fn main() {
+    let e = Error::Context(
+        "loading config".to_string(),
+        Box::new(Error::from(std::io::Error::new(std::io::ErrorKind::Other, "disk full"))),
+    );
+    assert_eq!(
+        e.display_chain().to_string(),
+        "error: loading config: disk full\ncaused by: disk full\ncaused by: disk full",
+    );
+}
@@ -0,0 +1,9 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    A(#[auto_error(from)] std::io::Error, #[auto_error(from)] std::fmt::Error),
+}
+
+pub fn main() {
+}
@@ -0,0 +1,12 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(code = "E0404", format_str = "not found")]
+    NotFound,
+    #[auto_error(code = "E0404")]
+    Io(std::io::Error),
+}
+
+pub fn main() {
+}
@@ -0,0 +1,27 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(format_str = "not found")]
+    NotFound,
+    Other(Box<dyn std::error::Error + Send + Sync + 'static>),
+}
+
+#[derive(Debug, AutoError)]
+enum LocalError {
+    #[auto_error(err = true, make_from = false)]
+    Other(Box<dyn std::error::Error>),
+}
+
+use std::error::Error as StdError;
+
+pub fn main() {
+    let inner: Box<dyn StdError + Send + Sync> = "something broke".into();
+    let e = Error::from(inner);
+    assert_eq!(format!("{}", e), "something broke");
+    assert_eq!(format!("{}", e.source().unwrap()), "something broke");
+    assert!(Error::NotFound.source().is_none());
+
+    let e = LocalError::Other(Box::new(std::fmt::Error));
+    assert!(e.source().unwrap().is::<std::fmt::Error>());
+}
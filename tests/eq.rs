@@ -0,0 +1,32 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(eq)]
+enum Error {
+    #[auto_error(format_str = "not found")]
+    NotFound,
+    Io(std::io::Error),
+    #[auto_error(format_str = "{} at {}")]
+    Parse(String, usize),
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(eq)]
+enum GenericError<T> {
+    #[auto_error(format_str = "{}")]
+    Value(T),
+    #[auto_error(format_str = "empty")]
+    Empty,
+}
+
+pub fn main() {
+    let e = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert_eq!(e, Error::Io(std::io::Error::new(std::io::ErrorKind::NotFound, "other")));
+    assert_ne!(e, Error::NotFound);
+    assert_eq!(Error::Parse("x".to_string(), 1), Error::Parse("y".to_string(), 2));
+    assert!(Error::NotFound.same_kind(&Error::NotFound));
+    assert!(!Error::NotFound.same_kind(&e));
+
+    assert_eq!(GenericError::Value(1.5), GenericError::Value(f64::NAN));
+    assert_ne!(GenericError::Value(1.5), GenericError::Empty);
+}
@@ -0,0 +1,28 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(errno = libc::ENOENT, format_str = "no such file")]
+    NotFound,
+    #[auto_error(errno = libc::EACCES, format_str = "permission denied")]
+    PermissionDenied,
+    #[auto_error(errno = 110, format_str = "timed out")]
+    TimedOut,
+    #[auto_error(format_str = "failed for {}")]
+    Failed(String),
+}
+
+pub fn main() {
+    assert_eq!(Error::NotFound.raw_os_error(), Some(libc::ENOENT));
+    assert_eq!(Error::PermissionDenied.raw_os_error(), Some(libc::EACCES));
+    assert_eq!(Error::TimedOut.raw_os_error(), Some(110));
+    assert_eq!(Error::Failed("x".to_string()).raw_os_error(), None);
+
+    assert!(matches!(Error::from_raw_os_error(libc::ENOENT), Some(Error::NotFound)));
+    assert!(matches!(Error::from_raw_os_error(110), Some(Error::TimedOut)));
+    assert!(Error::from_raw_os_error(libc::EINVAL).is_none());
+
+    let e = std::io::Error::from_raw_os_error(libc::EACCES);
+    assert!(matches!(Error::from_io_error(&e), Some(Error::PermissionDenied)));
+    assert!(Error::from_io_error(&std::io::Error::new(std::io::ErrorKind::Other, "oops")).is_none());
+}
@@ -0,0 +1,15 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(errno = libc::ENOENT, format_str = "no such file")]
+    NotFound,
+    #[auto_error(errno = libc::ENOENT, format_str = "missing")]
+    Missing,
+    #[auto_error(errno = 110, format_str = "timed out")]
+    TimedOut,
+    #[auto_error(errno = 110, format_str = "took too long")]
+    TooLong,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(errno = libc::EACCES, format_str = "permission denied for {}")]
+    PermissionDenied(std::path::PathBuf),
+}
+
+fn main() {}
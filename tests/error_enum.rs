@@ -0,0 +1,41 @@
+use std::fmt::Display;
+
+autoerror::error_enum! {
+    #[derive(Debug)]
+    #[auto_error(kind)]
+    pub enum Error {
+        /// The user could not be found
+        NotFound(String) => "User {} not found" [code = "E404", status = 404],
+        Io(std::io::Error) [code = "E500"],
+        Other(String) => "Other: {}" [from, code = "E400"],
+        #[auto_error(code = "E408")]
+        Timeout => "Request timed out",
+        Plain [code = "E000"],
+    }
+}
+
+autoerror::error_enum! {
+    #[derive(Debug, autoerror::AutoError)]
+    enum GenericError<T> where T: Display + std::fmt::Debug {
+        Value(T) => "Bad value {}",
+    }
+}
+
+pub fn main() {
+    let e = Error::NotFound("alice".to_string());
+    assert_eq!(e.to_string(), "User alice not found");
+    assert_eq!(e.code(), Some("E404"));
+
+    let e = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert_eq!(e.code(), Some("E500"));
+    assert_eq!(e.kind(), ErrorKind::Io);
+
+    let e = Error::from("oops".to_string());
+    assert_eq!(e.to_string(), "Other: oops");
+
+    assert_eq!(Error::Timeout.to_string(), "Request timed out");
+    assert_eq!(Error::Timeout.code(), Some("E408"));
+    assert_eq!(Error::Plain.to_string(), "");
+
+    assert_eq!(GenericError::Value(3).to_string(), "Bad value 3");
+}
@@ -0,0 +1,77 @@
+use autoerror::AutoError;
+use std::error::Error as StdError;
+use std::path::PathBuf;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(format_str = "could not read {:?}: {}")]
+    Read(PathBuf, std::io::Error),
+    #[auto_error(format_str = "could not parse line {}")]
+    Parse(usize, Box<dyn StdError + Send + Sync>),
+    #[auto_error(format_str = "{} is not {}")]
+    Mismatch(String, String),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Error {
+        Error::Read(PathBuf::new(), e)
+    }
+}
+
+pub fn main() {
+    let read = Error::Read(PathBuf::from("config.toml"), std::io::Error::new(std::io::ErrorKind::NotFound, "not found"));
+    assert_eq!(read.to_string(), "could not read \"config.toml\": not found");
+    assert_eq!(read.source().unwrap().to_string(), "not found");
+
+    let parse = Error::Parse(3, "bad number".into());
+    assert_eq!(parse.to_string(), "could not parse line 3");
+    assert_eq!(parse.source().unwrap().to_string(), "bad number");
+
+    let mismatch = Error::Mismatch("a".to_string(), "b".to_string());
+    assert!(mismatch.source().is_none());
+
+    let from = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "other"));
+    assert!(matches!(from, Error::Read(..)));
+
+    let number = NumberError::Invalid("x".to_string(), "x".parse::<u8>().unwrap_err());
+    assert_eq!(number.to_string(), "invalid number x");
+    assert!(number.source().unwrap().is::<std::num::ParseIntError>());
+
+    let invalid = FormError::Invalid("email".to_string(), ValidationError { rule: "required" });
+    assert_eq!(invalid.to_string(), "invalid field email");
+    assert!(invalid.source().is_none());
+    let invalid = PlainFormError::Invalid("email".to_string(), ValidationError { rule: "required" });
+    assert_eq!(invalid.to_string(), "invalid field email: required");
+    assert!(invalid.source().is_none());
+}
+
+// Named like an error, but not implementing Error
+#[derive(Debug)]
+pub struct ValidationError {
+    rule: &'static str,
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.rule)
+    }
+}
+
+#[derive(Debug, AutoError)]
+enum NumberError {
+    #[auto_error(format_str = "invalid number {}")]
+    Invalid(String, std::num::ParseIntError),
+}
+
+#[derive(Debug, AutoError)]
+enum FormError {
+    #[auto_error(err = false, format_str = "invalid field {}")]
+    Invalid(String, ValidationError),
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(heuristic = false)]
+enum PlainFormError {
+    #[auto_error(format_str = "invalid field {}: {}")]
+    Invalid(String, ValidationError),
+}
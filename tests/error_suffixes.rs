@@ -0,0 +1,57 @@
+use autoerror::AutoError;
+
+use std::error::Error as StdError;
+use std::fmt;
+
+#[derive(Debug)]
+struct DiskFault;
+
+impl fmt::Display for DiskFault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("disk fault")
+    }
+}
+
+impl StdError for DiskFault {}
+
+#[derive(Debug, AutoError)]
+#[auto_error(error_suffixes("Error", "Fault"))]
+enum StorageError {
+    Disk(DiskFault),
+    Parse(std::num::ParseIntError),
+}
+
+// Named like an error, but only a message
+#[derive(Debug)]
+struct Error(&'static str);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(heuristic = false)]
+enum ReportError {
+    Rejected(Error),
+    #[auto_error(format_str = "failed to read {0}")]
+    Read(String, #[auto_error(source)] std::io::Error),
+}
+
+pub fn main() {
+    let e: StorageError = DiskFault.into();
+    assert_eq!(e.to_string(), "disk fault");
+    assert!(e.source().is_some());
+
+    let e: StorageError = "x".parse::<u32>().unwrap_err().into();
+    assert!(e.source().is_some());
+
+    let e = ReportError::Rejected(Error("rejected"));
+    assert_eq!(e.to_string(), "rejected");
+    assert!(e.source().is_none());
+
+    let e = ReportError::Read("a.txt".to_string(), std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert_eq!(e.to_string(), "failed to read a.txt");
+    assert!(e.source().is_some());
+}
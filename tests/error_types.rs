@@ -0,0 +1,56 @@
+use autoerror::AutoError;
+
+use std::error::Error as StdError;
+use std::fmt;
+use std::path::PathBuf;
+
+mod db {
+    #[derive(Debug)]
+    pub struct Failure;
+
+    impl std::fmt::Display for Failure {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("connection lost")
+        }
+    }
+
+    impl std::error::Error for Failure {}
+}
+
+#[derive(Debug)]
+struct Rejection(u16);
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rejected with {}", self.0)
+    }
+}
+
+impl StdError for Rejection {}
+
+#[derive(Debug, AutoError)]
+#[auto_error(error_types(db::Failure, Rejection))]
+enum Error {
+    Database(db::Failure),
+    #[auto_error(format_str = "upload of {0:?} failed")]
+    Upload(PathBuf, Box<Rejection>),
+    #[auto_error(format_str = "invalid limit {}")]
+    Limit(u32),
+}
+
+fn query() -> Result<(), Error> {
+    Err(db::Failure)?;
+    Ok(())
+}
+
+pub fn main() {
+    let e = query().unwrap_err();
+    assert_eq!(e.to_string(), "connection lost");
+    assert!(e.source().is_some());
+
+    let e = Error::Upload(PathBuf::from("a.txt"), Box::new(Rejection(413)));
+    assert_eq!(e.to_string(), "upload of \"a.txt\" failed");
+    assert_eq!(e.source().unwrap().to_string(), "rejected with 413");
+
+    assert!(Error::Limit(3).source().is_none());
+}
@@ -0,0 +1,40 @@
+use autoerror::AutoError;
+use std::process::{ExitCode, Termination};
+
+#[derive(Debug, AutoError)]
+#[auto_error(termination)]
+enum Error {
+    #[auto_error(exit_code = 2, format_str = "invalid usage: {}")]
+    Usage(String),
+    #[auto_error(exit_code = 74, format_str = "could not read {}")]
+    Read(String, #[auto_error(source)] std::io::Error),
+    #[auto_error(format_str = "failed")]
+    Failed,
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(termination = "Exit")]
+enum GenericError<T> {
+    #[auto_error(exit_code = 3, format_str = "bad value {}")]
+    Bad(T),
+}
+
+fn run(fail: bool) -> Result<(), Error> {
+    if fail {
+        return Err(Error::Usage("--frobnicate".to_string()));
+    }
+    Ok(())
+}
+
+pub fn main() {
+    assert_eq!(Error::Usage("-x".to_string()).exit_code(), ExitCode::from(2));
+    let e = Error::Read("app.toml".to_string(), std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert_eq!(e.exit_code(), ExitCode::from(74));
+    assert_eq!(Error::Failed.exit_code(), ExitCode::FAILURE);
+
+    assert_eq!(ErrorTermination::from(run(false)).report(), ExitCode::SUCCESS);
+    assert_eq!(ErrorTermination::from(run(true)).report(), ExitCode::from(2));
+    assert_eq!(ErrorTermination::from(e).report(), ExitCode::from(74));
+
+    assert_eq!(Exit::from(GenericError::Bad(7)).report(), ExitCode::from(3));
+}
@@ -0,0 +1,9 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(exit_code = 0)]
+    Fine,
+}
+
+fn main() {}
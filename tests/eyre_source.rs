@@ -0,0 +1,14 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    Eyre(eyre::Report),
+}
+
+use std::error::Error as StdError;
+
+pub fn main() {
+    let e = Error::from(eyre::Report::new(std::fmt::Error));
+    assert_eq!(format!("{}", e), "an error occurred when formatting an argument");
+    assert!(e.source().unwrap().is::<std::fmt::Error>());
+}
@@ -0,0 +1,40 @@
+use autoerror::AutoError;
+use std::ffi::CStr;
+
+#[derive(Debug, AutoError)]
+#[auto_error(ffi, last_error = "mylib")]
+enum Error {
+    #[auto_error(format_str = "no such user {}")]
+    UserNotFound(u32),
+    #[auto_error(format_str = "could not read {}")]
+    Read(String, #[auto_error(source)] std::io::Error),
+    #[auto_error(format_str = "timed out")]
+    Timeout,
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(ffi = "GenericCode")]
+enum GenericError<T> {
+    #[auto_error(format_str = "bad value {}")]
+    Bad(T),
+}
+
+pub fn main() {
+    assert_eq!(Error::UserNotFound(7).ffi_code(), ErrorCode::UserNotFound);
+    assert_eq!(Error::Timeout.ffi_code() as i32, 3);
+    assert_eq!(std::mem::size_of::<ErrorCode>(), std::mem::size_of::<std::os::raw::c_int>());
+    assert_eq!(GenericError::Bad(1.5).ffi_code(), GenericCode::Bad);
+
+    assert_eq!(mylib_last_error_code(), 0);
+    assert!(mylib_error_message().is_null());
+
+    Error::UserNotFound(7).set_last_error();
+    assert_eq!(mylib_last_error_code(), ErrorCode::UserNotFound as i32);
+    let message = mylib_error_message();
+    assert_eq!(unsafe { CStr::from_ptr(message) }.to_str().unwrap(), "no such user 7");
+    unsafe { mylib_error_message_free(message) };
+
+    assert!(matches!(Error::take_last_error(), Some(Error::UserNotFound(7))));
+    assert!(Error::take_last_error().is_none());
+    assert_eq!(mylib_last_error_code(), 0);
+}
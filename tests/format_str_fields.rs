@@ -0,0 +1,19 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(format_str = "file {} at {}")]
+    NotFound(String),
+    #[auto_error(format_str = "{1} before {0} and {2}")]
+    Swapped(String, u32),
+    #[auto_error(format_str = "value {value}")]
+    Named(u32),
+    #[auto_error(format_str = "pi is {:.*}")]
+    Precision(f64),
+    #[auto_error(format_str = "unclosed {")]
+    Unclosed(u32),
+    #[auto_error(format_str = "{} of {:1$}")]
+    Fine(String, usize),
+}
+
+fn main() {}
@@ -0,0 +1,39 @@
+#![deny(warnings)]
+
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(format_table)]
+enum Error<T: std::fmt::Display + std::fmt::Debug> {
+    #[auto_error(format_str = "Document not found")]
+    NotFound,
+    #[auto_error(format_str = "{1} of {0} in {{braces}}")]
+    Swapped(String, u32),
+    #[auto_error(format_str = "Took {:.1}s")]
+    Slow(f64),
+    #[auto_error(format_str = "Missing {}", separator = ", ")]
+    Missing(Vec<String>),
+    Io(std::io::Error),
+    Value(T),
+    #[doc(hidden)]
+    #[allow(dead_code)]
+    Hidden(String),
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(format_table)]
+pub enum DelegatedError {
+    Name(String),
+}
+
+pub fn main() {
+    assert_eq!(Error::<u8>::NotFound.to_string(), "Document not found");
+    assert_eq!(Error::<u8>::Swapped("page".to_string(), 3).to_string(), "3 of page in {braces}");
+    assert_eq!(Error::<u8>::Slow(1.25).to_string(), "Took 1.2s");
+    assert_eq!(Error::<u8>::Missing(vec!["a".to_string(), "b".to_string()]).to_string(), "Missing a, b");
+    let e = Error::<u8>::from(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert_eq!(e.to_string(), "oops");
+    assert_eq!(Error::Value(42).to_string(), "42");
+    assert_eq!(Error::<u8>::Hidden("secret".to_string()).to_string(), "unknown error");
+    assert_eq!(format!("{:<6}|", DelegatedError::Name("bob".to_string())), "bob   |");
+}
@@ -0,0 +1,22 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(format_str = "{} (line {})")]
+    Parse(#[auto_error(from)] std::num::ParseIntError, usize),
+    #[auto_error(format_str = "{1}: {0}")]
+    Io(#[auto_error(from)] std::io::Error, String),
+}
+
+use std::error::Error as StdError;
+
+pub fn main() {
+    let a = Error::from("x".parse::<i32>().unwrap_err());
+    assert!(matches!(a, Error::Parse(_, 0)));
+    assert_eq!(format!("{}", a), "invalid digit found in string (line 0)");
+    assert!(a.source().unwrap().is::<std::num::ParseIntError>());
+
+    let b = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert!(matches!(&b, Error::Io(_, s) if s.is_empty()));
+    assert_eq!(format!("{}", b), ": oops");
+}
@@ -0,0 +1,43 @@
+use autoerror::AutoError;
+use std::any::Any;
+
+#[derive(Debug, AutoError)]
+enum TaskError {
+    #[auto_error(format_str = "task panicked: {}", from_panic)]
+    Panicked(String, Box<dyn Any + Send>),
+    #[auto_error(format_str = "task cancelled")]
+    Cancelled,
+}
+
+#[derive(Debug, AutoError)]
+enum MessageError {
+    #[auto_error(from_panic)]
+    Panicked(String),
+}
+
+fn run<F: FnOnce() + std::panic::UnwindSafe>(task: F) -> Result<(), TaskError> {
+    std::panic::catch_unwind(task)?;
+    Ok(())
+}
+
+pub fn main() {
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let error = run(|| panic!("static message")).unwrap_err();
+    assert_eq!(error.to_string(), "task panicked: static message");
+
+    let error = run(|| panic!("formatted {}", 42)).unwrap_err();
+    assert_eq!(error.to_string(), "task panicked: formatted 42");
+
+    let error = run(|| std::panic::panic_any(7u32)).unwrap_err();
+    assert_eq!(error.to_string(), "task panicked: Box<dyn Any>");
+    match error {
+        TaskError::Panicked(_, payload) => assert_eq!(payload.downcast_ref::<u32>(), Some(&7)),
+        TaskError::Cancelled => unreachable!(),
+    }
+    assert!(run(|| ()).is_ok());
+    assert_eq!(TaskError::Cancelled.to_string(), "task cancelled");
+
+    let error = MessageError::from(std::panic::catch_unwind(|| panic!("oops")).unwrap_err());
+    assert_eq!(error.to_string(), "oops");
+}
@@ -0,0 +1,49 @@
+use autoerror::AutoError;
+use std::sync::{Arc, Mutex, RwLock};
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(format_str = "lock poisoned", from_poison)]
+    Poisoned,
+    #[auto_error(format_str = "no such entry {}")]
+    NotFound(String),
+}
+
+#[derive(Debug, AutoError)]
+enum MessageError {
+    #[auto_error(format_str = "lock poisoned: {}", from_poison)]
+    Poisoned(String),
+}
+
+fn poisoned() -> Arc<Mutex<Vec<u32>>> {
+    let lock = Arc::new(Mutex::new(vec![]));
+    let clone = lock.clone();
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    let _ = std::thread::spawn(move || {
+        let _guard = clone.lock().unwrap();
+        panic!("poisoning the lock");
+    }).join();
+    std::panic::set_hook(hook);
+    lock
+}
+
+fn push(lock: &Mutex<Vec<u32>>, value: u32) -> Result<(), Error> {
+    lock.lock()?.push(value);
+    Ok(())
+}
+
+fn read(lock: &RwLock<u32>) -> Result<u32, MessageError> {
+    Ok(*lock.read()?)
+}
+
+pub fn main() {
+    let lock = poisoned();
+    assert!(matches!(push(&lock, 1), Err(Error::Poisoned)));
+    assert!(push(&Mutex::new(vec![]), 1).is_ok());
+    assert_eq!(Error::NotFound("x".to_string()).to_string(), "no such entry x");
+
+    assert_eq!(read(&RwLock::new(3)).unwrap(), 3);
+    let error = MessageError::from(lock.lock().unwrap_err());
+    assert_eq!(error.to_string(), "lock poisoned: poisoned lock: another task failed inside");
+}
@@ -0,0 +1,13 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(make_from = "tuple", format_str = "{} at line {}")]
+    Parse(String, usize),
+}
+
+pub fn main() {
+    let r: Result<(), (String, usize)> = Err(("unexpected token".to_string(), 3));
+    let e = r.map_err(Error::from).unwrap_err();
+    assert_eq!(format!("{}", e), "unexpected token at line 3");
+}
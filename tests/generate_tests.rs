@@ -0,0 +1,22 @@
+use autoerror::AutoError;
+use std::io;
+
+#[derive(Debug, AutoError)]
+#[auto_error(generate_tests)]
+enum Error {
+    Io(io::Error),
+    #[auto_error(format_str = "Missing key {} at line {}")]
+    MissingKey(String, usize),
+    #[auto_error(format_str = "Ratio {} out of {{range}}")]
+    Ratio(f64),
+    #[auto_error(format_str = "Flag {:?}")]
+    Flag(bool),
+    #[doc(hidden)]
+    Internal(Option<String>),
+    Unknown,
+}
+
+pub fn main() {
+    let e = Error::MissingKey("name".to_string(), 3);
+    assert_eq!(e.to_string(), "Missing key name at line 3");
+}
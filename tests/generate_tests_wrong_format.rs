@@ -0,0 +1,10 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(compact, generate_tests)]
+enum Error {
+    #[auto_error(format_str = "{1} missing")]
+    Missing(String),
+}
+
+fn main() {}
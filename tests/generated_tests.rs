@@ -0,0 +1,46 @@
+// Runs the tests generated by generate_tests, which the trybuild tests only
+//  compile without cfg(test)
+
+use autoerror::AutoError;
+use std::io;
+
+#[derive(Debug, AutoError)]
+#[auto_error(generate_tests)]
+enum Error {
+    Io(io::Error),
+    #[auto_error(format_str = "Missing key {} at line {}")]
+    MissingKey(String, usize),
+    #[auto_error(format_str = "Ratio {1} of {0} out of {{range}}")]
+    Ratio(f64, u8),
+    #[auto_error(format_str = "Flag {:?}")]
+    Flag(bool),
+    #[doc(hidden)]
+    Internal(Option<String>),
+    Unknown,
+}
+
+#[cfg(feature = "runtime")]
+#[derive(Debug, AutoError)]
+#[auto_error(compact, generate_tests)]
+enum CompactError {
+    #[auto_error(format_str = "{1} of {0} missing")]
+    Missing(String, u32),
+}
+
+#[test]
+fn generated_tests_are_callable() {
+    error_autoerror_tests::missing_key_message_and_source();
+    error_autoerror_tests::ratio_message_and_source();
+    error_autoerror_tests::internal_message_and_source();
+    let _ = (Error::MissingKey(String::new(), 0), Error::Ratio(0.0, 0));
+    if let Error::Internal(detail) = Error::Internal(None) {
+        assert_eq!(detail, None);
+    }
+}
+
+#[cfg(feature = "runtime")]
+#[test]
+fn generated_tests_display_compact_messages() {
+    compact_error_autoerror_tests::missing_message_and_source();
+    let _ = CompactError::Missing(String::new(), 0);
+}
@@ -0,0 +1,28 @@
+use autoerror::AutoError;
+
+use std::sync::mpsc;
+
+#[derive(Debug, AutoError)]
+enum Error<T> {
+    #[auto_error(err = true, make_from = true)]
+    Channel(mpsc::SendError<T>),
+    #[auto_error(format_str = "invalid value {:?}")]
+    Invalid(u32),
+    Io(std::io::Error),
+}
+
+use std::error::Error as StdError;
+
+pub fn main() {
+    let (tx, rx) = mpsc::channel::<u8>();
+    drop(rx);
+    let e: Error<u8> = tx.send(5).unwrap_err().into();
+    assert_eq!(format!("{}", e), "sending on a closed channel");
+    assert!(e.source().is_some());
+
+    let e = Error::<u8>::from(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert_eq!(format!("{}", e), "oops");
+
+    let e = Error::<String>::Invalid(3);
+    assert_eq!(format!("{}", e), "invalid value 3");
+}
@@ -0,0 +1,34 @@
+use autoerror::AutoError;
+use std::collections::HashSet;
+
+#[derive(Debug, AutoError)]
+#[auto_error(eq, hash)]
+enum Error {
+    #[auto_error(format_str = "not found", code = "E404")]
+    NotFound,
+    #[auto_error(code = "E500")]
+    Io(std::io::Error),
+    #[auto_error(format_str = "{} at {}", code = "E400")]
+    Parse(String, usize),
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(hash)]
+enum GenericError<T> {
+    #[auto_error(format_str = "{}")]
+    Value(T),
+}
+
+pub fn main() {
+    let mut seen = HashSet::new();
+    assert!(seen.insert(Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops"))));
+    assert!(!seen.insert(Error::from(std::io::Error::new(std::io::ErrorKind::NotFound, "again"))));
+    assert!(seen.insert(Error::Parse("x".to_string(), 1)));
+    assert!(!seen.insert(Error::Parse("y".to_string(), 2)));
+    assert!(seen.insert(Error::NotFound));
+    assert_eq!(seen.len(), 3);
+
+    use std::hash::{BuildHasher, RandomState};
+    let hasher = RandomState::new();
+    assert_eq!(hasher.hash_one(GenericError::Value(1.5)), hasher.hash_one(GenericError::Value(f64::NAN)));
+}
@@ -0,0 +1,19 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(heapless)]
+pub enum Error {
+    #[auto_error(format_str = "not found: {}")]
+    NotFound(u32),
+}
+
+pub fn main() {
+    let message: heapless::String<16> = Error::NotFound(42).to_heapless().unwrap();
+    assert_eq!(message.as_str(), "not found: 42");
+
+    assert!(Error::NotFound(42).to_heapless::<8>().is_err());
+
+    let mut message = heapless::String::<32>::new();
+    Error::NotFound(7).write_message(&mut message).unwrap();
+    assert_eq!(message.as_str(), "not found: 7");
+}
@@ -0,0 +1,20 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(display_chain)]
+enum Error {
+    #[auto_error(help = "try running with --force", format_str = "{} already exists")]
+    Exists(String),
+    #[auto_error(format_str = "could not read config: {}")]
+    Read(std::io::Error),
+}
+
+pub fn main() {
+    let e = Error::Exists("out.txt".to_string());
+    assert_eq!(e.help(), Some("try running with --force"));
+    assert_eq!(e.display_chain().to_string(), "error: out.txt already exists\nhelp: try running with --force");
+
+    let e = Error::Read(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert_eq!(e.help(), None);
+    assert_eq!(e.display_chain().to_string(), "error: could not read config: oops\ncaused by: oops");
+}
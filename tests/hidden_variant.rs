@@ -0,0 +1,23 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(accessors, constructors, kind, catalog)]
+#[non_exhaustive]
+enum Error {
+    #[auto_error(code = "E0404", format_str = "not found")]
+    NotFound,
+    #[auto_error(code = "E1042")]
+    Io(std::io::Error),
+    #[doc(hidden)]
+    __NonExhaustive,
+}
+
+pub fn main() {
+    assert_eq!(Error::__NonExhaustive.to_string(), "unknown error");
+    assert_eq!(Error::__NonExhaustive.code(), None);
+    assert_eq!(Error::not_found().code(), Some("E0404"));
+    assert!(Error::NotFound.is_not_found());
+    assert_eq!(Error::__NonExhaustive.kind(), ErrorKind::__NonExhaustive);
+    assert_eq!(Error::CATALOG.len(), 2);
+    assert_eq!(Error::KINDS, &[ErrorKind::NotFound, ErrorKind::Io]);
+}
@@ -0,0 +1,55 @@
+#![allow(dead_code, unused_macros)]
+
+use autoerror::AutoError;
+
+// Items shadowing the prelude and std, which the generated code should not
+//  pick up
+mod std {}
+struct Option;
+struct Some;
+struct None;
+struct Ok;
+struct Result;
+struct Box;
+struct AutoErrorJoin;
+
+macro_rules! write {
+    ($($t:tt)*) => { compile_error!("shadowed write!") };
+}
+
+macro_rules! format_args {
+    ($($t:tt)*) => { compile_error!("shadowed format_args!") };
+}
+
+macro_rules! eprintln {
+    ($($t:tt)*) => { compile_error!("shadowed eprintln!") };
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(display_chain, eq, hash, kind)]
+enum Error {
+    #[auto_error(format_str = "not found")]
+    NotFound,
+    Io(::std::io::Error),
+    Other(::std::string::String),
+}
+
+#[derive(Debug, AutoError)]
+enum AllSources {
+    Io(::std::io::Error),
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(termination)]
+enum Exit {
+    #[auto_error(format_str = "failed", help = "try again")]
+    Failed,
+}
+
+pub fn main() {
+    let e = Error::from(::std::io::Error::new(::std::io::ErrorKind::Other, "oops"));
+    assert!(::std::error::Error::source(&e).is_some());
+    assert_eq!(::std::string::ToString::to_string(&Error::NotFound), "not found");
+    assert_eq!(::std::string::ToString::to_string(&Error::NotFound.display_chain()), "error: not found");
+    assert_eq!(Error::NotFound.kind(), ErrorKind::NotFound);
+}
@@ -0,0 +1,27 @@
+#![allow(dead_code)]
+
+use autoerror::AutoError;
+
+// Items shadowing the prelude and std, which the generated serde attributes
+//  should not pick up
+mod std {}
+struct Option;
+struct Some;
+struct None;
+
+#[derive(Debug, AutoError)]
+#[auto_error(problem_details, jsonrpc, serde)]
+enum Error {
+    #[auto_error(code = "E0404", status = 404, jsonrpc_code = -32001, format_str = "not found")]
+    NotFound,
+    #[auto_error(code = "E0500", format_str = "other")]
+    Other,
+}
+
+pub fn main() {
+    let details = Error::NotFound.to_problem_details();
+    assert_eq!(
+        serde_json::to_string(&details).unwrap(),
+        r#"{"type":"about:blank","title":"Not found","status":404,"detail":"not found"}"#
+    );
+}
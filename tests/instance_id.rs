@@ -0,0 +1,60 @@
+use autoerror::AutoError;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorId(u64);
+
+impl ErrorId {
+    pub fn new() -> Self {
+        ErrorId(NEXT_ID.fetch_add(1, Ordering::SeqCst))
+    }
+}
+
+fn fixed_id() -> u64 {
+    7
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(constructors)]
+enum Error {
+    Io(std::io::Error, #[auto_error(instance_id)] ErrorId),
+    #[auto_error(format_str = "{} not found")]
+    NotFound(#[auto_error(instance_id)] ErrorId, String),
+    #[auto_error(format_str = "missing")]
+    Missing,
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(constructors)]
+enum FixedError {
+    #[auto_error(format_str = "failed")]
+    Failed(#[auto_error(instance_id = "fixed_id")] u64),
+}
+
+#[autoerror::autoerror(instance_id = "ErrorId")]
+#[derive(Debug)]
+enum InjectedError {
+    Io(std::io::Error),
+    #[auto_error(format_str = "bad input {}")]
+    Input(String),
+}
+
+pub fn main() {
+    let first = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    let second = Error::not_found("page");
+    assert_eq!(first.to_string(), "oops");
+    assert_eq!(second.to_string(), "page not found");
+    assert_ne!(first.instance_id(), second.instance_id());
+    assert!(first.instance_id().is_some());
+    assert!(Error::Missing.instance_id().is_none());
+
+    assert_eq!(FixedError::failed().instance_id(), Some(&7));
+
+    let e = InjectedError::from(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert_eq!(e.to_string(), "oops");
+    assert!(e.instance_id().is_some());
+    let e = InjectedError::Input("x".to_string(), ErrorId::new());
+    assert_eq!(e.to_string(), "bad input x");
+}
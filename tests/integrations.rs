@@ -0,0 +1,15 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(integrations(serde, tracing, metrics = "app_errors"))]
+pub enum Error {
+    #[auto_error(format_str = "not found", code = "E404")]
+    NotFound,
+}
+
+pub fn main() {
+    let json = serde_json::to_value(&Error::NotFound).unwrap();
+    assert_eq!(json["kind"], "NotFound");
+    assert_eq!(json["code"], "E404");
+    Error::NotFound.record();
+}
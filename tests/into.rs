@@ -0,0 +1,41 @@
+use autoerror::AutoError;
+
+#[derive(Debug, PartialEq)]
+enum ApiError {
+    NotFound(u32),
+    BadRequest(String),
+    Unavailable,
+    Internal(String),
+}
+
+impl ApiError {
+    fn bad_request(field: String, reason: String) -> ApiError {
+        ApiError::BadRequest(format!("{}: {}", field, reason))
+    }
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(into = "ApiError")]
+enum DomainError {
+    #[auto_error(format_str = "no user {}", into = "ApiError::NotFound")]
+    UserNotFound(u32),
+    #[auto_error(format_str = "invalid {}: {}", into = "ApiError::bad_request")]
+    Invalid(String, String),
+    #[auto_error(format_str = "maintenance", into = "ApiError::Unavailable")]
+    Maintenance,
+    #[auto_error(into = "ApiError::Internal(error.to_string())")]
+    Database(std::io::Error),
+}
+
+fn lookup(id: u32) -> Result<(), ApiError> {
+    Err(DomainError::UserNotFound(id))?;
+    Ok(())
+}
+
+pub fn main() {
+    assert_eq!(lookup(3), Err(ApiError::NotFound(3)));
+    assert_eq!(ApiError::from(DomainError::Invalid("name".to_string(), "empty".to_string())), ApiError::BadRequest("name: empty".to_string()));
+    assert_eq!(ApiError::from(DomainError::Maintenance), ApiError::Unavailable);
+    let database = DomainError::Database(std::io::Error::new(std::io::ErrorKind::Other, "connection lost"));
+    assert_eq!(ApiError::from(database), ApiError::Internal("connection lost".to_string()));
+}
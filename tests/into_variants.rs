@@ -0,0 +1,42 @@
+mod storage {
+    use autoerror::AutoError;
+
+    #[derive(Debug, AutoError)]
+    #[auto_error(into_variants = "crate::AppError")]
+    pub enum StorageError {
+        #[auto_error(format_str = "no such key {}")]
+        NotFound(String),
+        #[auto_error(format_str = "storage is full")]
+        Full,
+        #[auto_error(into = "crate::AppError::Io(error.to_string())")]
+        Disk(std::io::Error),
+    }
+}
+
+use autoerror::AutoError;
+use storage::StorageError;
+
+#[derive(Debug, PartialEq, AutoError)]
+enum AppError {
+    #[auto_error(format_str = "no such key {}")]
+    NotFound(String),
+    #[auto_error(format_str = "storage is full")]
+    Full,
+    #[auto_error(format_str = "I/O error: {}")]
+    Io(String),
+    #[auto_error(format_str = "unauthorized")]
+    Unauthorized,
+}
+
+fn get(key: &str) -> Result<(), AppError> {
+    Err(StorageError::NotFound(key.to_string()))?;
+    Ok(())
+}
+
+pub fn main() {
+    assert_eq!(get("user"), Err(AppError::NotFound("user".to_string())));
+    assert_eq!(AppError::from(StorageError::Full), AppError::Full);
+    let disk = StorageError::Disk(std::io::Error::new(std::io::ErrorKind::Other, "bad sector"));
+    assert_eq!(AppError::from(disk), AppError::Io("bad sector".to_string()));
+    assert_eq!(AppError::Unauthorized.to_string(), "unauthorized");
+}
@@ -0,0 +1,29 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(jsonrpc)]
+enum Error {
+    #[auto_error(jsonrpc_code = -32001, format_str = "no such document {}")]
+    DocumentNotFound(String),
+    #[auto_error(jsonrpc_code = 7, format_str = "could not read {}: {}")]
+    Read(String, #[auto_error(source)] std::io::Error),
+    #[auto_error(format_str = "internal failure")]
+    Internal,
+}
+
+pub fn main() {
+    let e = Error::DocumentNotFound("main.rs".to_string());
+    assert_eq!(e.jsonrpc_code(), -32001);
+    assert_eq!(ErrorJsonRpcError::from(e), ErrorJsonRpcError {
+        code: -32001,
+        message: "no such document main.rs".to_string(),
+        data: None,
+    });
+
+    let e = Error::Read("lib.rs".to_string(), std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    let rpc = e.to_jsonrpc_error();
+    assert_eq!(rpc.code, 7);
+    assert_eq!(rpc.data.as_deref(), Some("oops"));
+
+    assert_eq!(Error::Internal.jsonrpc_code(), -32603);
+}
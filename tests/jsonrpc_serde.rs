@@ -0,0 +1,16 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(jsonrpc, serde)]
+enum Error {
+    #[auto_error(jsonrpc_code = -32001, format_str = "no such user {}")]
+    UserNotFound(u32),
+}
+
+pub fn main() {
+    let rpc = Error::UserNotFound(7).to_jsonrpc_error();
+    assert_eq!(
+        serde_json::to_string(&rpc).unwrap(),
+        r#"{"code":-32001,"message":"no such user 7"}"#
+    );
+}
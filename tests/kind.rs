@@ -0,0 +1,30 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(kind)]
+pub enum Error {
+    #[auto_error(format_str = "not found")]
+    NotFound,
+    Io(std::io::Error),
+    #[auto_error(format_str = "{} at {}")]
+    Parse(String, usize),
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(kind = "Class")]
+enum GenericError<T> {
+    #[auto_error(format_str = "{}")]
+    Value(T),
+}
+
+pub fn main() {
+    let e = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert_eq!(e.kind(), ErrorKind::Io);
+    assert_eq!(ErrorKind::from(&Error::NotFound), ErrorKind::NotFound);
+    assert_eq!(Error::Parse("x".to_string(), 1).kind(), ErrorKind::Parse);
+
+    assert_eq!(Error::KINDS, &[ErrorKind::NotFound, ErrorKind::Io, ErrorKind::Parse]);
+
+    assert_eq!(GenericError::Value(5).kind(), Class::Value);
+    assert_eq!(GenericError::<u32>::KINDS, &[Class::Value]);
+}
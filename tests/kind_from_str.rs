@@ -0,0 +1,40 @@
+use autoerror::AutoError;
+use std::convert::TryFrom;
+
+#[derive(Debug, AutoError)]
+#[auto_error(kind)]
+pub enum Error {
+    #[auto_error(format_str = "not found", code = "E1042")]
+    NotFound,
+    #[auto_error(code = "E1043")]
+    Io(std::io::Error),
+    #[cfg(any())]
+    #[auto_error(format_str = "never compiled", code = "E1044")]
+    Disabled,
+    #[auto_error(format_str = "{} at {}", code = "E1045")]
+    r#Parse(String, usize),
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(kind = "Class")]
+enum PlainError {
+    #[auto_error(format_str = "invalid")]
+    Invalid,
+}
+
+pub fn main() {
+    assert_eq!("E1042".parse(), Ok(ErrorKind::NotFound));
+    assert_eq!("NotFound".parse(), Ok(ErrorKind::NotFound));
+    assert_eq!("Parse".parse(), Ok(ErrorKind::Parse));
+    assert_eq!("Disabled".parse::<ErrorKind>(), Err("Disabled".to_string()));
+    assert_eq!("E1044".parse::<ErrorKind>(), Err("E1044".to_string()));
+
+    assert_eq!(ErrorKind::try_from("E1043"), Ok(ErrorKind::Io));
+    assert_eq!(ErrorKind::try_from("io"), Err("io"));
+
+    let kind = Error::Parse("x".to_string(), 1).kind();
+    assert_eq!(ErrorKind::try_from(Error::Parse("x".to_string(), 1).code().unwrap()), Ok(kind));
+
+    assert_eq!("Invalid".parse(), Ok(Class::Invalid));
+    assert_eq!(PlainError::Invalid.kind(), Class::Invalid);
+}
@@ -0,0 +1,34 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(localized)]
+enum Error {
+    #[auto_error(msg_key = "errors.not-found", format_str = "user {} not found")]
+    NotFound(#[auto_error(arg = "user")] String),
+    #[auto_error(format_str = "access denied")]
+    Denied,
+}
+
+pub fn main() {
+    let e = Error::NotFound("alice".to_string());
+    assert_eq!(e.to_string(), "user alice not found");
+    assert_eq!(e.localized_message(), None);
+
+    Error::set_localizer(|key, args| match key {
+        "errors.not-found" => Some(format!("Gebruiker {} niet gevonden", args[0].1)),
+        _ => None,
+    });
+    assert_eq!(e.to_string(), "Gebruiker alice niet gevonden");
+    assert_eq!(Error::Denied.to_string(), "access denied");
+
+    // Localizers can display errors and register another localizer
+    Error::set_localizer(|key, _| {
+        if key == "denied" {
+            let nested = Error::NotFound("bob".to_string()).to_string();
+            Error::set_localizer(move |_, _| Some(format!("replaced after {}", nested)));
+        }
+        None
+    });
+    assert_eq!(Error::Denied.to_string(), "access denied");
+    assert_eq!(e.to_string(), "replaced after user bob not found");
+}
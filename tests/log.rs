@@ -0,0 +1,62 @@
+use autoerror::AutoError;
+use std::sync::Mutex;
+
+static RECORDS: Mutex<Vec<(log::Level, String)>> = Mutex::new(Vec::new());
+
+struct Logger;
+
+impl log::Log for Logger {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        RECORDS.lock().unwrap().push((record.level(), record.args().to_string()));
+    }
+
+    fn flush(&self) {}
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(emit_log, constructors)]
+enum Error {
+    #[auto_error(log = "warn", format_str = "no such user {}")]
+    UserNotFound(u32),
+    #[auto_error(format_str = "could not read: {}")]
+    Io(std::io::Error),
+    #[auto_error(whatever, log = "info")]
+    Other(String),
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(log)]
+enum QuietError {
+    #[auto_error(log = "debug", format_str = "cache miss")]
+    CacheMiss,
+    #[auto_error(format_str = "failed")]
+    Failed,
+}
+
+fn read() -> Result<(), Error> {
+    Err(std::io::Error::new(std::io::ErrorKind::Other, "oops"))?;
+    Ok(())
+}
+
+pub fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    assert_eq!(Error::UserNotFound(7).log_level(), log::Level::Warn);
+    assert_eq!(QuietError::CacheMiss.log_level(), log::Level::Debug);
+    assert_eq!(QuietError::Failed.log_level(), log::Level::Error);
+    assert!(RECORDS.lock().unwrap().is_empty());
+
+    let _ = Error::user_not_found(7u32);
+    let _ = read();
+    let _ = Error::whatever(format_args!("giving up after {} tries", 3));
+    assert_eq!(*RECORDS.lock().unwrap(), vec![
+        (log::Level::Warn, "no such user 7".to_string()),
+        (log::Level::Error, "could not read: oops".to_string()),
+        (log::Level::Info, "giving up after 3 tries".to_string()),
+    ]);
+}
@@ -0,0 +1,80 @@
+use autoerror::AutoError;
+use log::kv::{self, Key, Source, Value, VisitSource};
+use std::sync::Mutex;
+
+static RECORDS: Mutex<Vec<Vec<(String, String)>>> = Mutex::new(Vec::new());
+
+struct Collect(Vec<(String, String)>);
+
+impl<'kvs> VisitSource<'kvs> for Collect {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+        self.0.push((key.to_string(), value.to_string()));
+        Ok(())
+    }
+}
+
+fn pairs(source: &dyn Source) -> Vec<(String, String)> {
+    let mut collect = Collect(vec![]);
+    source.visit(&mut collect).unwrap();
+    collect.0
+}
+
+struct Logger;
+
+impl log::Log for Logger {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        RECORDS.lock().unwrap().push(pairs(record.key_values()));
+    }
+
+    fn flush(&self) {}
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(log_kv)]
+enum Error {
+    #[auto_error(format_str = "no such user {}", code = "E404", log = "warn")]
+    UserNotFound(u32),
+    #[auto_error(format_str = "could not read", code = "E500")]
+    Io(#[auto_error(source)] std::io::Error),
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(log_kv)]
+enum PlainError {
+    #[auto_error(format_str = "timed out")]
+    Timeout,
+}
+
+fn strings(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+}
+
+pub fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    assert_eq!(Error::UserNotFound(7).log_level(), log::Level::Warn);
+    assert_eq!(pairs(&Error::UserNotFound(7)), strings(&[
+        ("kind", "UserNotFound"),
+        ("code", "E404"),
+        ("message", "no such user 7"),
+    ]));
+    let e = Error::Io(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert_eq!(pairs(&e), strings(&[
+        ("kind", "Io"),
+        ("code", "E500"),
+        ("message", "could not read"),
+        ("source", "oops"),
+    ]));
+    assert_eq!(pairs(&PlainError::Timeout), strings(&[
+        ("kind", "Timeout"),
+        ("message", "timed out"),
+    ]));
+
+    log::error!(error = e; "request failed");
+    assert_eq!(RECORDS.lock().unwrap().pop().unwrap(), strings(&[("error", "could not read")]));
+}
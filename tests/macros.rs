@@ -0,0 +1,28 @@
+mod error {
+    use autoerror::AutoError;
+
+    #[derive(Debug, AutoError)]
+    #[auto_error(macros)]
+    pub enum ParseError {
+        #[auto_error(format_str = "empty input")]
+        Empty,
+        #[auto_error(format_str = "line too long: {}")]
+        TooLong(usize),
+    }
+}
+
+use error::{bail_parse_error, ensure_parse_error, ParseError};
+
+fn parse(line: &str) -> Result<usize, ParseError> {
+    if line.is_empty() {
+        bail_parse_error!(Empty);
+    }
+    ensure_parse_error!(line.len() < 10, TooLong(line.len()));
+    Ok(line.len())
+}
+
+pub fn main() {
+    assert!(matches!(parse(""), Err(ParseError::Empty)));
+    assert!(matches!(parse("a very long line"), Err(ParseError::TooLong(16))));
+    assert_eq!(parse("short").unwrap(), 5);
+}
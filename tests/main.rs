@@ -2,7 +2,165 @@
 fn tests() {
     let t = trybuild::TestCases::new();
     t.pass("tests/basic_compile.rs");
+    t.pass("tests/all_sources.rs");
+    t.pass("tests/hygiene.rs");
+    t.pass("tests/msrv.rs");
+    t.pass("tests/raw_identifiers.rs");
     t.pass("tests/test_generation.rs");
+    t.pass("tests/from_field.rs");
+    t.pass("tests/from_tuple.rs");
+    t.pass("tests/from_poison.rs");
+    t.pass("tests/from_panic.rs");
+    t.pass("tests/generic_enum.rs");
+    t.pass("tests/bounds.rs");
+    t.pass("tests/error_types.rs");
+    t.pass("tests/error_suffixes.rs");
+    t.pass("tests/config_context.rs");
+    t.pass("tests/builders.rs");
+    t.pass("tests/test_helpers.rs");
+    t.pass("tests/deprecated.rs");
+    t.pass("tests/pointer_source.rs");
+    t.pass("tests/boxed.rs");
+    t.pass("tests/cow_message.rs");
+    t.pass("tests/dyn_source.rs");
+    t.pass("tests/option_source.rs");
+    t.pass("tests/error_field_source.rs");
+    t.pass("tests/anyhow_source.rs");
+    t.pass("tests/recursive_source.rs");
+    t.pass("tests/aggregate.rs");
+    t.pass("tests/chain.rs");
+    t.pass("tests/display_chain.rs");
+    t.pass("tests/chain_fmt.rs");
+    t.pass("tests/delegated_display.rs");
+    t.pass("tests/no_alloc.rs");
+    t.pass("tests/debug_impl.rs");
+    t.pass("tests/debug_backtrace.rs");
+    t.pass("tests/kind.rs");
+    t.pass("tests/kind_from_str.rs");
+    t.pass("tests/eq.rs");
+    t.pass("tests/hash.rs");
+    t.pass("tests/accessors.rs");
+    t.pass("tests/constructors.rs");
+    t.pass("tests/context_selectors.rs");
+    t.pass("tests/context.rs");
+    t.pass("tests/whatever.rs");
+    t.pass("tests/macros.rs");
+    t.pass("tests/result_alias.rs");
+    t.pass("tests/into.rs");
+    t.pass("tests/into_variants.rs");
+    t.pass("tests/codes.rs");
+    t.pass("tests/numeric_codes.rs");
+    t.pass("tests/catalog.rs");
+    t.pass("tests/variant_name.rs");
+    t.pass("tests/hidden_variant.rs");
+    t.pass("tests/cfg_variant.rs");
+    t.pass("tests/cfg_field.rs");
+    t.pass("tests/problem_details.rs");
+    t.pass("tests/jsonrpc.rs");
+    t.pass("tests/exit_code.rs");
+    t.pass("tests/errno.rs");
+    t.pass("tests/ffi.rs");
+    t.pass("tests/retryable.rs");
+    t.pass("tests/severity.rs");
+    t.pass("tests/help.rs");
+    t.pass("tests/url.rs");
+    t.pass("tests/msg_key.rs");
+    t.pass("tests/localized.rs");
+    t.pass("tests/generate_tests.rs");
+    t.pass("tests/clone.rs");
+    t.pass("tests/captured_fields.rs");
+    t.pass("tests/timestamp.rs");
+    t.pass("tests/instance_id.rs");
+    t.pass("tests/error_enum.rs");
+    #[cfg(feature = "eyre")]
+    t.pass("tests/eyre_source.rs");
+    #[cfg(feature = "actix")]
+    t.pass("tests/actix.rs");
+    #[cfg(feature = "warp")]
+    t.pass("tests/warp.rs");
+    #[cfg(feature = "rocket")]
+    t.pass("tests/rocket.rs");
+    #[cfg(feature = "tonic")]
+    t.pass("tests/tonic.rs");
+    #[cfg(feature = "pyo3")]
+    t.pass("tests/pyo3.rs");
+    #[cfg(feature = "napi")]
+    t.pass("tests/napi.rs");
+    #[cfg(feature = "log")]
+    t.pass("tests/log.rs");
+    #[cfg(feature = "log")]
+    t.pass("tests/log_kv.rs");
+    #[cfg(feature = "slog")]
+    t.pass("tests/slog.rs");
+    #[cfg(feature = "valuable")]
+    t.pass("tests/valuable.rs");
+    #[cfg(feature = "tracing")]
+    t.pass("tests/tracing.rs");
+    #[cfg(feature = "otel")]
+    t.pass("tests/otel.rs");
+    #[cfg(feature = "metrics")]
+    t.pass("tests/metrics.rs");
+    #[cfg(feature = "sentry")]
+    t.pass("tests/sentry.rs");
+    #[cfg(feature = "miette")]
+    t.pass("tests/miette.rs");
+    #[cfg(feature = "runtime")]
+    t.pass("tests/compact.rs");
+    #[cfg(feature = "runtime")]
+    t.pass("tests/format_table.rs");
+    #[cfg(feature = "runtime")]
+    t.compile_fail("tests/generate_tests_wrong_format.rs");
+    #[cfg(feature = "async_graphql")]
+    t.pass("tests/async_graphql.rs");
+    #[cfg(feature = "serde")]
+    t.pass("tests/problem_details_serde.rs");
+    #[cfg(feature = "serde")]
+    t.pass("tests/jsonrpc_serde.rs");
+    #[cfg(feature = "serde")]
+    t.pass("tests/serde.rs");
+    #[cfg(feature = "serde")]
+    t.pass("tests/hygiene_serde.rs");
+    #[cfg(feature = "serde")]
+    t.pass("tests/deserialize.rs");
+    #[cfg(feature = "schemars")]
+    t.pass("tests/schemars.rs");
+    #[cfg(feature = "arbitrary")]
+    t.pass("tests/arbitrary.rs");
+    #[cfg(all(feature = "serde", feature = "tracing", feature = "metrics"))]
+    t.pass("tests/integrations.rs");
+    #[cfg(feature = "heapless")]
+    t.pass("tests/heapless.rs");
+    #[cfg(feature = "sqlx")]
+    t.pass("tests/sqlx.rs");
+    #[cfg(feature = "diesel")]
+    t.pass("tests/diesel.rs");
+    #[cfg(feature = "reqwest")]
+    t.pass("tests/reqwest.rs");
+    #[cfg(all(feature = "validator", feature = "serde"))]
+    t.pass("tests/validator.rs");
     t.compile_fail("tests/error_struct.rs");
     t.compile_fail("tests/double_auto_error.rs");
+    t.compile_fail("tests/double_from_field.rs");
+    t.compile_fail("tests/duplicate_code.rs");
+    t.compile_fail("tests/exit_code_zero.rs");
+    t.compile_fail("tests/unknown_severity.rs");
+    t.compile_fail("tests/multiple_errors.rs");
+    t.compile_fail("tests/unknown_parameter.rs");
+    t.compile_fail("tests/format_str_fields.rs");
+    t.compile_fail("tests/conflicting_from.rs");
+    t.compile_fail("tests/missing_into.rs");
+    t.compile_fail("tests/unmapped_variant.rs");
+    t.compile_fail("tests/localized_no_alloc.rs");
+    t.compile_fail("tests/unknown_integration.rs");
+    t.compile_fail("tests/msrv_backtrace.rs");
+    t.compile_fail("tests/msrv_too_old.rs");
+    t.compile_fail("tests/builder_missing_field.rs");
+    t.compile_fail("tests/deprecated_use.rs");
+    t.compile_fail("tests/superseded_by_unknown.rs");
+    t.compile_fail("tests/errno_fields.rs");
+    t.compile_fail("tests/errno_duplicate.rs");
+    #[cfg(feature = "sqlx")]
+    t.compile_fail("tests/sqlx_fallback.rs");
+    #[cfg(not(feature = "sentry"))]
+    t.compile_fail("tests/integration_feature.rs");
 }
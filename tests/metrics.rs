@@ -0,0 +1,71 @@
+use autoerror::AutoError;
+use metrics::{Counter, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit};
+use std::sync::Mutex;
+
+#[derive(Default)]
+struct Registrations(Mutex<Vec<String>>);
+
+impl Recorder for Registrations {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+        let labels: Vec<_> = key.labels().map(|label| format!("{}={}", label.key(), label.value())).collect();
+        self.0.lock().unwrap().push(format!("{} {}", key.name(), labels.join(",")));
+        Counter::noop()
+    }
+
+    fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+        Gauge::noop()
+    }
+
+    fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+        Histogram::noop()
+    }
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(metrics, constructors)]
+enum Error {
+    #[auto_error(code = "E0404", format_str = "no such user {}")]
+    UserNotFound(u32),
+    #[auto_error(code = "E0500", format_str = "could not read: {}")]
+    Io(std::io::Error),
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(metrics = "requests_failed_total")]
+enum RequestError {
+    #[auto_error(format_str = "could not format: {}")]
+    Format(std::fmt::Error),
+}
+
+fn read() -> Result<(), Error> {
+    Err(std::io::Error::new(std::io::ErrorKind::Other, "oops"))?;
+    Ok(())
+}
+
+fn format(fail: bool) -> Result<(), RequestError> {
+    if fail {
+        Err(std::fmt::Error)?;
+    }
+    Ok(())
+}
+
+pub fn main() {
+    let recorder = Registrations::default();
+    metrics::with_local_recorder(&recorder, || {
+        let _ = Error::user_not_found(7u32);
+        let _ = read();
+        let _ = format(true);
+        let _ = format(false);
+    });
+    assert_eq!(*recorder.0.lock().unwrap(), vec![
+        "error_total kind=UserNotFound,code=E0404".to_string(),
+        "error_total kind=Io,code=E0500".to_string(),
+        "requests_failed_total kind=Format".to_string(),
+    ]);
+}
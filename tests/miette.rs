@@ -0,0 +1,51 @@
+use autoerror::AutoError;
+use miette::{Diagnostic, NamedSource, Severity, SourceSpan};
+
+#[derive(Debug, AutoError)]
+#[auto_error(miette)]
+enum Error {
+    #[auto_error(code = "E0001", severity = "warn", help = "remove the trailing comma", url = "https://docs.example.com/errors/E0001", format_str = "unexpected {}")]
+    Unexpected(
+        String,
+        #[auto_error(source_code)] NamedSource<String>,
+        #[auto_error(label = "here")] SourceSpan,
+        #[auto_error(label)] SourceSpan,
+    ),
+    #[auto_error(code = "E0002", format_str = "could not read: {}")]
+    Io(std::io::Error),
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(miette)]
+#[repr(u16)]
+enum NumericError {
+    #[auto_error(format_str = "busy")]
+    Busy = 503,
+}
+
+pub fn main() {
+    let e = Error::Unexpected(
+        "comma".to_string(),
+        NamedSource::new("config.toml", "a = [1, 2,]".to_string()),
+        (9, 1).into(),
+        (4, 7).into(),
+    );
+    assert_eq!(Diagnostic::code(&e).unwrap().to_string(), "E0001");
+    assert_eq!(Diagnostic::severity(&e), Some(Severity::Warning));
+    assert_eq!(Diagnostic::help(&e).unwrap().to_string(), "remove the trailing comma");
+    assert_eq!(Diagnostic::url(&e).unwrap().to_string(), "https://docs.example.com/errors/E0001");
+    assert!(Diagnostic::source_code(&e).is_some());
+    let labels: Vec<_> = Diagnostic::labels(&e).unwrap().map(|label| (label.label().map(str::to_string), label.offset(), label.len())).collect();
+    assert_eq!(labels, vec![(Some("here".to_string()), 9, 1), (None, 4, 7)]);
+
+    let e = Error::Io(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert_eq!(Diagnostic::code(&e).unwrap().to_string(), "E0002");
+    assert_eq!(Diagnostic::severity(&e), Some(Severity::Error));
+    assert!(Diagnostic::help(&e).is_none());
+    assert!(Diagnostic::labels(&e).is_none());
+
+    assert_eq!(Diagnostic::code(&NumericError::Busy).unwrap().to_string(), "503");
+
+    let report: miette::Report = e.into();
+    assert_eq!(report.to_string(), "could not read: oops");
+}
@@ -0,0 +1,21 @@
+use autoerror::AutoError;
+
+enum ApiError {
+    NotFound(u32),
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(into = "ApiError")]
+enum Error {
+    #[auto_error(into = "ApiError::NotFound")]
+    UserNotFound(u32),
+    Timeout,
+}
+
+#[derive(Debug, AutoError)]
+enum OtherError {
+    #[auto_error(into = "ApiError::NotFound")]
+    UserNotFound(u32),
+}
+
+fn main() {}
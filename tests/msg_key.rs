@@ -0,0 +1,29 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(msg_key = "errors.not-found", format_str = "user {} not found in {}")]
+    NotFound(#[auto_error(arg = "user")] String, u32),
+    #[auto_error(format_str = "missing {}", separator = " and ")]
+    MissingFields(Vec<String>),
+    #[auto_error(format_str = "could not read config")]
+    Read(std::io::Error),
+    Unknown,
+}
+
+pub fn main() {
+    let e = Error::NotFound("alice".to_string(), 3);
+    assert_eq!(e.message_key(), "errors.not-found");
+    assert_eq!(e.message_args(), vec![("user", "alice".to_string()), ("arg1", "3".to_string())]);
+
+    let e = Error::MissingFields(vec!["name".to_string(), "email".to_string()]);
+    assert_eq!(e.message_key(), "missing-fields");
+    assert_eq!(e.message_args(), vec![("arg0", "name and email".to_string())]);
+
+    let e = Error::Read(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert_eq!(e.message_key(), "read");
+    assert_eq!(e.message_args(), vec![]);
+
+    assert_eq!(Error::Unknown.message_key(), "unknown");
+    assert_eq!(Error::Unknown.message_args(), vec![]);
+}
@@ -0,0 +1,23 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(msrv = "1.56", constructors)]
+pub enum Error {
+    #[auto_error(format_str = "{1} of {0} took {2:.1}s")]
+    Slow(String, u32, f64),
+    #[auto_error(format_str = "missing {}", separator = ", ")]
+    Missing(Vec<String>),
+    #[auto_error(format_str = "{:>1$}")]
+    Padded(String, usize),
+    Io(std::io::Error),
+    #[auto_error(format_str = "not found")]
+    NotFound,
+}
+
+pub fn main() {
+    assert_eq!(Error::Slow("page".to_string(), 3, 1.25).to_string(), "3 of page took 1.2s");
+    assert_eq!(Error::Missing(vec!["a".to_string(), "b".to_string()]).to_string(), "missing a, b");
+    assert_eq!(Error::Padded("x".to_string(), 3).to_string(), "  x");
+    assert_eq!(Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops")).to_string(), "oops");
+    assert_eq!(Error::not_found().to_string(), "not found");
+}
@@ -0,0 +1,9 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(msrv = "1.60")]
+pub enum Error {
+    Io(std::io::Error, #[auto_error(backtrace)] std::backtrace::Backtrace),
+}
+
+pub fn main() {}
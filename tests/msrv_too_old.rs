@@ -0,0 +1,10 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(msrv = "1.46")]
+enum Error {
+    #[auto_error(format_str = "not found")]
+    NotFound,
+}
+
+fn main() {}
@@ -0,0 +1,61 @@
+// Builds and runs tests/msrv.rs with the Rust version it declares as msrv,
+//  as the trybuild tests only build it with the current compiler. Skipped
+//  when that toolchain is not installed through rustup, unless
+//  AUTOERROR_REQUIRE_MSRV is set, as in CI.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+const MSRV: &str = "1.56";
+
+#[test]
+fn msrv_mode_builds_on_declared_version() {
+    let toolchain = std::env::var("AUTOERROR_MSRV_TOOLCHAIN").unwrap_or_else(|_| MSRV.to_string());
+    let installed = Command::new("rustup")
+        .args(["run", &toolchain, "rustc", "--version"])
+        .output()
+        .map_or(false, |output| output.status.success());
+    if !installed {
+        assert!(std::env::var_os("AUTOERROR_REQUIRE_MSRV").is_none(), "Rust {} is not installed", toolchain);
+        eprintln!("skipped, Rust {} is not installed", toolchain);
+        return;
+    }
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let source = fs::read_to_string(root.join("tests/msrv.rs")).unwrap();
+    assert!(source.contains(&format!("msrv = \"{}\"", MSRV)));
+    let crate_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("msrv");
+    fs::create_dir_all(crate_dir.join("src")).unwrap();
+    fs::write(crate_dir.join("src/main.rs"), source).unwrap();
+    fs::write(crate_dir.join("Cargo.toml"), format!(r#"[package]
+name = "autoerror-msrv"
+version = "0.0.0"
+edition = "2018"
+rust-version = "{}"
+
+[dependencies]
+autoerror = {{ path = {:?} }}
+
+[workspace]
+"#, MSRV, root)).unwrap();
+
+    // The lock file is resolved by the current cargo, falling back to
+    //  dependency versions that still support the msrv
+    let status = Command::new(env!("CARGO"))
+        .arg("generate-lockfile")
+        .env("CARGO_RESOLVER_INCOMPATIBLE_RUST_VERSIONS", "fallback")
+        .current_dir(&crate_dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "resolving dependencies for Rust {} failed", toolchain);
+    let status = Command::new("rustup")
+        .args(["run", &toolchain, "cargo", "run", "--quiet"])
+        .env_remove("RUSTC")
+        .env_remove("RUSTUP_TOOLCHAIN")
+        .env("CARGO_TARGET_DIR", crate_dir.join("target"))
+        .current_dir(&crate_dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "tests/msrv.rs failed on Rust {}", toolchain);
+}
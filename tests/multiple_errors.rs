@@ -0,0 +1,28 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(kind = 1)]
+enum Error {
+    #[auto_error(severity = "fatal", status = 42)]
+    Crashed,
+    Io(std::io::Error),
+    #[auto_error(exit_code = 0)]
+    Exited,
+    Parse(#[auto_error(label = 1)] String),
+    #[auto_error(log = "loud")]
+    Config(#[auto_error(arg = 2)] String),
+}
+
+#[derive(Debug, AutoError)]
+enum ApiError {
+    #[auto_error(status = 42)]
+    Crashed,
+    #[auto_error(whatever)]
+    Other(String),
+    #[auto_error(whatever)]
+    Unexpected(String),
+    #[auto_error(superseded_by = "Missing")]
+    Legacy,
+}
+
+fn main() {}
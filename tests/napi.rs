@@ -0,0 +1,27 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(napi)]
+enum Error {
+    #[auto_error(napi_status = "InvalidArg", format_str = "invalid argument {}")]
+    Invalid(String),
+    #[auto_error(napi_status = "Cancelled", format_str = "cancelled")]
+    Cancelled,
+    #[auto_error(format_str = "failed")]
+    Failed,
+}
+
+fn check(value: i32) -> napi::Result<i32> {
+    if value < 0 {
+        Err(Error::Invalid(value.to_string()))?;
+    }
+    Ok(value)
+}
+
+pub fn main() {
+    let e = check(-1).unwrap_err();
+    assert_eq!(e.status, napi::Status::InvalidArg);
+    assert_eq!(e.reason, "invalid argument -1");
+    assert_eq!(napi::Error::from(Error::Cancelled).status, napi::Status::Cancelled);
+    assert_eq!(napi::Error::from(Error::Failed).status, napi::Status::GenericFailure);
+}
@@ -0,0 +1,81 @@
+use autoerror::AutoError;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+struct Buffer {
+    data: [u8; 64],
+    len: usize,
+}
+
+impl Write for Buffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > self.data.len() {
+            return Err(fmt::Error);
+        }
+        self.data[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+impl Buffer {
+    fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.data[..self.len]).unwrap()
+    }
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(no_alloc)]
+pub enum Error {
+    #[auto_error(format_str = "not found")]
+    NotFound,
+    #[auto_error(format_str = "took {:.1}s")]
+    Slow(f64),
+    #[auto_error(format_str = "missing {}", separator = ", ")]
+    Missing(Vec<&'static str>),
+    #[auto_error(format_str = "failed to save", chain_fmt)]
+    Save(std::io::Error),
+    Message(&'static str),
+}
+
+pub fn main() {
+    let errors = [
+        (Error::NotFound, "not found"),
+        (Error::Slow(1.25), "took 1.2s"),
+        (Error::Missing(vec!["a", "b"]), "missing a, b"),
+        (Error::Save(std::io::ErrorKind::Other.into()), "failed to save: other error"),
+        (Error::Message("static message"), "static message"),
+    ];
+
+    for (error, expected) in errors.iter() {
+        let mut buffer = Buffer { data: [0; 64], len: 0 };
+        let before = ALLOCATIONS.load(Ordering::SeqCst);
+        error.write_message(&mut buffer).unwrap();
+        let after = ALLOCATIONS.load(Ordering::SeqCst);
+        assert_eq!(before, after, "{:?} allocated when displayed", error);
+        assert_eq!(buffer.as_str(), *expected);
+    }
+
+    let mut buffer = Buffer { data: [0; 64], len: 0 };
+    assert!(Error::Message("a message that is too long to fit into a buffer of sixty-four bytes").write_message(&mut buffer).is_err());
+}
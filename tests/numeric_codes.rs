@@ -0,0 +1,34 @@
+use autoerror::AutoError;
+use std::convert::TryFrom;
+
+const BASE: u32 = 1000;
+
+#[derive(Debug, AutoError)]
+#[auto_error(kind)]
+#[repr(u32)]
+enum Error {
+    #[auto_error(format_str = "no such user {}")]
+    UserNotFound(u32) = BASE + 4,
+    #[auto_error(format_str = "permission denied")]
+    PermissionDenied = BASE + 1,
+    #[auto_error(format_str = "quota exceeded for {}")]
+    QuotaExceeded(String),
+    #[cfg(any())]
+    #[auto_error(format_str = "never compiled")]
+    Disabled,
+    #[auto_error(format_str = "internal error")]
+    Internal = 2000,
+}
+
+pub fn main() {
+    assert_eq!(Error::UserNotFound(7).code(), 1004);
+    assert_eq!(Error::PermissionDenied.code(), 1001);
+    assert_eq!(Error::QuotaExceeded("alice".to_string()).code(), 1002);
+    assert_eq!(Error::Internal.code(), 2000);
+
+    assert_eq!(ErrorKind::PermissionDenied as u32, 1001);
+    assert_eq!(ErrorKind::try_from(1002), Ok(ErrorKind::QuotaExceeded));
+    assert_eq!(ErrorKind::try_from(2000), Ok(ErrorKind::Internal));
+    assert_eq!(ErrorKind::try_from(5), Err(5));
+    assert_eq!(ErrorKind::try_from(Error::UserNotFound(1).code()), Ok(Error::UserNotFound(1).kind()));
+}
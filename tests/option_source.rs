@@ -0,0 +1,22 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(err = true, format_str = "request failed (cause: {:?})")]
+    Request(Option<std::io::Error>),
+    #[auto_error(err = true, format_str = "boxed {:?}")]
+    Boxed(Option<Box<std::fmt::Error>>),
+}
+
+use std::error::Error as StdError;
+
+pub fn main() {
+    let e = Error::Request(None);
+    assert!(e.source().is_none());
+
+    let e = Error::Request(Some(std::io::Error::new(std::io::ErrorKind::Other, "oops")));
+    assert_eq!(format!("{}", e.source().unwrap()), "oops");
+
+    let e = Error::Boxed(Some(Box::new(std::fmt::Error)));
+    assert!(e.source().unwrap().is::<std::fmt::Error>());
+}
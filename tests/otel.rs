@@ -0,0 +1,56 @@
+use autoerror::AutoError;
+use opentelemetry::trace::{Status, TraceContextExt, Tracer, TracerProvider};
+use opentelemetry::{Context, KeyValue};
+use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+
+#[derive(Debug, AutoError)]
+#[auto_error(otel)]
+pub enum Error {
+    #[auto_error(format_str = "user {} not found", code = "E404")]
+    NotFound(u32),
+    #[auto_error(code = "E500")]
+    Io(std::io::Error),
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(otel)]
+pub enum PlainError {
+    #[auto_error(format_str = "timed out")]
+    Timeout,
+}
+
+pub fn main() {
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder().with_simple_exporter(exporter.clone()).build();
+    let tracer = provider.tracer("autoerror");
+
+    let span = tracer.start("lookup");
+    let cx = Context::current_with_span(span);
+    {
+        let _guard = cx.clone().attach();
+        Error::NotFound(7).record_exception();
+        PlainError::Timeout.record_exception();
+    }
+    cx.span().end();
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+    assert_eq!(span.status, Status::error("user 7 not found"));
+
+    let events: Vec<_> = span.events.iter().collect();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].name, "exception");
+    assert_eq!(events[0].attributes, vec![
+        KeyValue::new("exception.type", "NotFound"),
+        KeyValue::new("exception.message", "user 7 not found"),
+        KeyValue::new("code", "E404"),
+    ]);
+    assert_eq!(events[1].attributes, vec![
+        KeyValue::new("exception.type", "Timeout"),
+        KeyValue::new("exception.message", "timed out"),
+    ]);
+
+    // Without an active span, recording does nothing
+    Error::Io(std::io::ErrorKind::Other.into()).record_exception();
+}
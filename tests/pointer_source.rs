@@ -0,0 +1,26 @@
+use autoerror::AutoError;
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    Io(Box<std::io::Error>),
+    Fmt(Arc<std::fmt::Error>),
+    #[auto_error(make_from = false)]
+    Other(Rc<std::io::Error>),
+}
+
+use std::error::Error as StdError;
+
+pub fn main() {
+    let e = Error::from(Box::new(std::io::Error::new(std::io::ErrorKind::Other, "oops")));
+    assert_eq!(format!("{}", e), "oops");
+    assert!(e.source().unwrap().is::<std::io::Error>());
+
+    let e = Error::from(Arc::new(std::fmt::Error));
+    assert!(e.source().unwrap().is::<std::fmt::Error>());
+
+    let e = Error::Other(Rc::new(std::io::Error::new(std::io::ErrorKind::Other, "oops")));
+    assert!(e.source().unwrap().is::<std::io::Error>());
+}
@@ -0,0 +1,26 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(problem_details)]
+enum Error {
+    #[auto_error(status = 404, problem_type = "https://example.com/probs/user-not-found", format_str = "no such user {}")]
+    UserNotFound(u32),
+    #[auto_error(title = "Storage failure")]
+    Io(std::io::Error),
+}
+
+pub fn main() {
+    let details = Error::UserNotFound(7).to_problem_details();
+    assert_eq!(details, ErrorProblemDetails {
+        r#type: "https://example.com/probs/user-not-found",
+        title: "User not found",
+        status: 404,
+        detail: "no such user 7".to_string(),
+        instance: None,
+    });
+
+    let details = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops")).to_problem_details();
+    assert_eq!(details.r#type, "about:blank");
+    assert_eq!(details.title, "Storage failure");
+    assert_eq!(details.status, 500);
+}
@@ -0,0 +1,16 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(problem_details, serde)]
+enum Error {
+    #[auto_error(status = 404, format_str = "no such user {}")]
+    UserNotFound(u32),
+}
+
+pub fn main() {
+    let details = Error::UserNotFound(7).to_problem_details();
+    assert_eq!(
+        serde_json::to_string(&details).unwrap(),
+        r#"{"type":"about:blank","title":"User not found","status":404,"detail":"no such user 7"}"#
+    );
+}
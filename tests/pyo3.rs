@@ -0,0 +1,29 @@
+use autoerror::AutoError;
+use pyo3::exceptions::{PyFileNotFoundError, PyRuntimeError, PyValueError};
+use pyo3::{PyErr, Python};
+
+pyo3::create_exception!(mymodule, QuotaExceeded, pyo3::exceptions::PyException);
+
+#[derive(Debug, AutoError)]
+#[auto_error(pyo3)]
+enum Error {
+    #[auto_error(py_exc = "ValueError", format_str = "invalid value {}")]
+    Invalid(String),
+    #[auto_error(py_exc = "FileNotFoundError", format_str = "no such file")]
+    NotFound,
+    #[auto_error(py_exc = "crate::QuotaExceeded", format_str = "quota exceeded")]
+    Quota,
+    #[auto_error(format_str = "failed")]
+    Failed,
+}
+
+pub fn main() {
+    Python::attach(|py| {
+        let e = PyErr::from(Error::Invalid("x".to_string()));
+        assert!(e.is_instance_of::<PyValueError>(py));
+        assert_eq!(e.value(py).to_string(), "invalid value x");
+        assert!(PyErr::from(Error::NotFound).is_instance_of::<PyFileNotFoundError>(py));
+        assert!(PyErr::from(Error::Quota).is_instance_of::<QuotaExceeded>(py));
+        assert!(PyErr::from(Error::Failed).is_instance_of::<PyRuntimeError>(py));
+    });
+}
@@ -0,0 +1,37 @@
+#![allow(non_camel_case_types)]
+
+use autoerror::AutoError;
+
+#[derive(AutoError)]
+#[auto_error(display_chain, accessors, constructors, context_selectors, debug, kind, eq, hash, macros, result_alias = "Result", catalog, generate_tests)]
+enum Error {
+    #[auto_error(format_str = "bad type {}", code = "E1")]
+    r#type(String),
+    #[auto_error(format_str = "async failure {} {}", code = "E2")]
+    r#async(u32, String),
+    #[auto_error(format_str = "no match", code = "E3")]
+    r#match,
+    #[auto_error(code = "E4")]
+    f0(std::io::Error),
+}
+
+// Bindings of the generated code don't pick up constants of the same name
+#[allow(non_upper_case_globals, dead_code)]
+const f0: u32 = 7;
+
+fn check(x: u32) -> Result<()> {
+    ensure_error!(x < 10, r#async(x, "too large".to_string()));
+    Ok(())
+}
+
+pub fn main() {
+    let e = Error::r#type("x".to_string());
+    assert_eq!(e.to_string(), "bad type x");
+    assert!(e.is_type());
+    assert_eq!(e.kind(), ErrorKind::r#type);
+    assert_eq!(Error::r#async(1, "y".to_string()).to_string(), "async failure 1 y");
+    assert!(check(20).is_err());
+    assert_eq!(Error::CATALOG.len(), 4);
+    assert_eq!(Error::CATALOG[0].name, "type");
+    assert_eq!(Error::r#match.to_string(), "no match");
+}
@@ -0,0 +1,27 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(format_str = "not found")]
+    NotFound,
+    #[auto_error(format_str = "{}: {}")]
+    Wrapped(String, Box<Error>),
+    Nested(Box<Self>),
+    #[auto_error(format_str = "{} ({})")]
+    Io(String, #[auto_error(source)] std::io::Error),
+}
+
+use std::error::Error as StdError;
+
+pub fn main() {
+    let e = Error::Wrapped("loading config".to_string(), Box::new(Error::NotFound));
+    assert_eq!(format!("{}", e), "loading config: not found");
+    assert!(matches!(e.source().unwrap().downcast_ref::<Error>(), Some(Error::NotFound)));
+
+    let e = Error::Nested(Box::new(Error::NotFound));
+    assert!(e.source().unwrap().is::<Error>());
+
+    let e = Error::Io("reading".to_string(), std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert_eq!(format!("{}", e), "reading (oops)");
+    assert!(e.source().unwrap().is::<std::io::Error>());
+}
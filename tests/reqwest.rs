@@ -0,0 +1,53 @@
+use autoerror::AutoError;
+
+use std::error::Error as StdError;
+use std::net::TcpListener;
+use std::time::Duration;
+
+#[derive(Debug, AutoError)]
+enum FetchError {
+    #[auto_error(reqwest = "timeout", format_str = "request timed out")]
+    Timeout(reqwest::Error),
+    #[auto_error(reqwest = "connect", format_str = "could not connect")]
+    Connect,
+    #[auto_error(reqwest = "server_error", format_str = "server failed")]
+    Server(reqwest::Error),
+    #[auto_error(reqwest = "decode", format_str = "invalid response")]
+    Decode(reqwest::Error),
+    #[auto_error(reqwest = "builder", format_str = "invalid request")]
+    Builder(reqwest::Error),
+    Http(reqwest::Error),
+}
+
+fn response(status: u16, body: &'static str) -> reqwest::Response {
+    reqwest::Response::from(http::Response::builder().status(status).body(body).unwrap())
+}
+
+pub fn main() {
+    let e: FetchError = response(503, "").error_for_status().unwrap_err().into();
+    assert!(matches!(e, FetchError::Server(_)));
+    assert_eq!(e.to_string(), "server failed");
+    assert!(e.source().is_some());
+
+    let e: FetchError = response(404, "").error_for_status().unwrap_err().into();
+    assert!(matches!(e, FetchError::Http(_)));
+
+    let client = reqwest::Client::new();
+    let e: FetchError = client.get("not a url").build().unwrap_err().into();
+    assert!(matches!(e, FetchError::Builder(_)));
+
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+    let e: FetchError = runtime.block_on(response(200, "not json").json::<u32>()).unwrap_err().into();
+    assert!(matches!(e, FetchError::Decode(_)));
+
+    // Connections are accepted by the backlog, but never answered
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    let client = reqwest::Client::builder().timeout(Duration::from_secs(1)).build().unwrap();
+    let e: FetchError = runtime.block_on(async { client.get(&url).send().await }).unwrap_err().into();
+    assert!(matches!(e, FetchError::Timeout(_)));
+
+    // Port 0 can't be connected to, so no other listener can be reached
+    let e: FetchError = runtime.block_on(async { client.get("http://127.0.0.1:0/").send().await }).unwrap_err().into();
+    assert!(matches!(e, FetchError::Connect));
+}
@@ -0,0 +1,23 @@
+mod error {
+    use autoerror::AutoError;
+
+    #[derive(Debug, AutoError)]
+    #[auto_error(result_alias = "Result")]
+    pub enum Error {
+        #[auto_error(format_str = "not found")]
+        NotFound,
+    }
+}
+
+fn find() -> error::Result<u32> {
+    Err(error::Error::NotFound)
+}
+
+fn other() -> error::Result<u32, std::fmt::Error> {
+    Ok(5)
+}
+
+pub fn main() {
+    assert!(find().is_err());
+    assert_eq!(other().unwrap(), 5);
+}
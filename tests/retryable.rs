@@ -0,0 +1,24 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(retryable, format_str = "timed out")]
+    Timeout,
+    #[auto_error(transient = true, format_str = "service unavailable")]
+    Unavailable,
+    #[auto_error(format_str = "no such user {}")]
+    UserNotFound(u32),
+    #[auto_error(format_str = "{}: {}")]
+    Context(String, Box<Error>),
+    #[auto_error(transient = false, format_str = "fatal: {}")]
+    Fatal(Box<Error>),
+}
+
+pub fn main() {
+    assert!(Error::Timeout.is_retryable());
+    assert!(Error::Unavailable.is_retryable());
+    assert!(!Error::UserNotFound(7).is_retryable());
+    assert!(Error::Context("fetching user".to_string(), Box::new(Error::Timeout)).is_retryable());
+    assert!(!Error::Context("fetching user".to_string(), Box::new(Error::UserNotFound(7))).is_retryable());
+    assert!(!Error::Fatal(Box::new(Error::Timeout)).is_retryable());
+}
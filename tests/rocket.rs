@@ -0,0 +1,34 @@
+use autoerror::AutoError;
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+
+#[derive(Debug, AutoError)]
+#[auto_error(rocket)]
+enum Error {
+    #[auto_error(status = 404, format_str = "no such user {}")]
+    UserNotFound(u32),
+    #[auto_error(body = "internal error")]
+    Io(std::io::Error),
+}
+
+#[rocket::get("/user")]
+fn user() -> Result<&'static str, Error> {
+    Err(Error::UserNotFound(7))
+}
+
+#[rocket::get("/io")]
+fn io() -> Result<&'static str, Error> {
+    Err(Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops")))
+}
+
+pub fn main() {
+    let client = Client::tracked(rocket::build().mount("/", rocket::routes![user, io])).unwrap();
+
+    let response = client.get("/user").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(response.into_string().unwrap(), "no such user 7");
+
+    let response = client.get("/io").dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+    assert_eq!(response.into_string().unwrap(), "internal error");
+}
@@ -0,0 +1,21 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(problem_details, serde, schemars)]
+enum Error {
+    #[auto_error(code = "E0404", status = 404, format_str = "no such user {}")]
+    UserNotFound(u32),
+    #[auto_error(code = "E1042", format_str = "could not read {}")]
+    Read(String, #[auto_error(source)] std::io::Error),
+}
+
+pub fn main() {
+    let schema = serde_json::to_value(schemars::schema_for!(Error)).unwrap();
+    assert_eq!(schema["title"], "Error");
+    assert_eq!(schema["properties"]["kind"]["enum"], serde_json::json!(["UserNotFound", "Read"]));
+    assert_eq!(schema["properties"]["code"]["type"], "string");
+    assert_eq!(schema["required"], serde_json::json!(["kind", "code", "message", "source"]));
+
+    let schema = serde_json::to_value(schemars::schema_for!(ErrorProblemDetails)).unwrap();
+    assert_eq!(schema["properties"]["status"]["type"], "integer");
+}
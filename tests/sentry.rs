@@ -0,0 +1,42 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(sentry, constructors)]
+enum Error {
+    #[auto_error(code = "E0404", format_str = "no such user {}")]
+    UserNotFound(u32),
+    #[auto_error(report, code = "E0500", format_str = "could not read: {}")]
+    Io(std::io::Error),
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(sentry)]
+enum PlainError {
+    #[auto_error(format_str = "failed")]
+    Failed,
+}
+
+fn read() -> Result<(), Error> {
+    Err(std::io::Error::new(std::io::ErrorKind::Other, "oops"))?;
+    Ok(())
+}
+
+pub fn main() {
+    let events = sentry::test::with_captured_events(|| {
+        Error::user_not_found(7u32).capture();
+        PlainError::Failed.capture();
+        let _ = read();
+    });
+    assert_eq!(events.len(), 3);
+
+    let fingerprints: Vec<Vec<String>> = events.iter().map(|event| event.fingerprint.iter().map(|s| s.to_string()).collect()).collect();
+    assert_eq!(fingerprints, vec![
+        vec!["Error".to_string(), "E0404".to_string()],
+        vec!["PlainError".to_string(), "Failed".to_string()],
+        vec!["Error".to_string(), "E0500".to_string()],
+    ]);
+
+    let messages: Vec<Vec<String>> = events.iter().map(|event| event.exception.values.iter().map(|e| e.value.clone().unwrap()).collect()).collect();
+    assert_eq!(messages[0], vec!["no such user 7".to_string()]);
+    assert_eq!(messages[2], vec!["oops".to_string(), "could not read: oops".to_string()]);
+}
@@ -0,0 +1,50 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(serde)]
+enum Error {
+    #[auto_error(code = "E0404", format_str = "no such user {}")]
+    UserNotFound(u32),
+    #[auto_error(code = "E1042", format_str = "could not read {}: {}")]
+    Read(String, #[auto_error(source)] std::io::Error),
+    #[doc(hidden)]
+    __NonExhaustive,
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(serde)]
+#[repr(u16)]
+enum NumericError {
+    #[auto_error(format_str = "busy")]
+    Busy = 503,
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(serde)]
+enum PlainError {
+    #[auto_error(format_str = "failed")]
+    Failed,
+}
+
+pub fn main() {
+    let e = Error::Read("app.toml".to_string(), std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert_eq!(
+        serde_json::to_string(&e).unwrap(),
+        r#"{"kind":"Read","code":"E1042","message":"could not read app.toml: oops","source":["oops"]}"#
+    );
+
+    assert_eq!(
+        serde_json::to_string(&Error::__NonExhaustive).unwrap(),
+        r#"{"kind":"__NonExhaustive","message":"unknown error","source":[]}"#
+    );
+
+    assert_eq!(
+        serde_json::to_string(&NumericError::Busy).unwrap(),
+        r#"{"kind":"Busy","code":503,"message":"busy","source":[]}"#
+    );
+
+    assert_eq!(
+        serde_json::to_string(&PlainError::Failed).unwrap(),
+        r#"{"kind":"Failed","message":"failed","source":[]}"#
+    );
+}
@@ -0,0 +1,24 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(severity = "info", format_str = "no such user {}")]
+    UserNotFound(u32),
+    #[auto_error(severity = "warn", format_str = "slow response")]
+    Slow,
+    #[auto_error(severity = "critical", format_str = "database corrupted")]
+    Corrupted,
+    #[auto_error(format_str = "failed")]
+    Failed,
+}
+
+pub fn main() {
+    assert_eq!(Error::UserNotFound(7).severity(), ErrorSeverity::Info);
+    assert_eq!(Error::Slow.severity(), ErrorSeverity::Warn);
+    assert_eq!(Error::Corrupted.severity(), ErrorSeverity::Critical);
+    assert_eq!(Error::Failed.severity(), ErrorSeverity::Error);
+
+    assert!(Error::Corrupted.severity() > ErrorSeverity::Error);
+    assert!(Error::Slow.severity() >= ErrorSeverity::Warn);
+    assert_eq!(Error::Slow.severity().to_string(), "warn");
+}
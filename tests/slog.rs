@@ -0,0 +1,70 @@
+use autoerror::AutoError;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct Collect(Arc<Mutex<Vec<(String, String)>>>);
+
+impl slog::Serializer for Collect {
+    fn emit_arguments(&mut self, key: slog::Key, val: &fmt::Arguments) -> slog::Result {
+        self.0.lock().unwrap().push((key.to_string(), val.to_string()));
+        Ok(())
+    }
+}
+
+impl slog::Drain for Collect {
+    type Ok = ();
+    type Err = slog::Never;
+
+    fn log(&self, record: &slog::Record, values: &slog::OwnedKVList) -> Result<(), slog::Never> {
+        let mut serializer = self.clone();
+        slog::KV::serialize(&record.kv(), record, &mut serializer).unwrap();
+        slog::KV::serialize(values, record, &mut serializer).unwrap();
+        Ok(())
+    }
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(slog)]
+enum Error {
+    #[auto_error(format_str = "no such user {}", code = "E404")]
+    UserNotFound(u32),
+    #[auto_error(format_str = "could not read", code = "E500")]
+    Io(#[auto_error(source)] std::io::Error),
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(slog)]
+#[repr(u16)]
+enum NumericError {
+    #[auto_error(format_str = "timed out")]
+    Timeout = 408,
+}
+
+fn strings(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+}
+
+pub fn main() {
+    let drain = Collect::default();
+    let logger = slog::Logger::root(slog::Fuse(drain.clone()), slog::o!());
+
+    let e = Error::Io(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    slog::error!(logger, "request failed"; &e);
+    assert_eq!(std::mem::take(&mut *drain.0.lock().unwrap()), strings(&[
+        ("kind", "Io"),
+        ("code", "E500"),
+        ("message", "could not read"),
+        ("source", "oops"),
+    ]));
+
+    slog::error!(logger, "lookup failed"; "error" => &Error::UserNotFound(7));
+    assert_eq!(std::mem::take(&mut *drain.0.lock().unwrap()), strings(&[("error", "no such user 7")]));
+
+    slog::warn!(logger, "slow"; NumericError::Timeout);
+    assert_eq!(std::mem::take(&mut *drain.0.lock().unwrap()), strings(&[
+        ("kind", "Timeout"),
+        ("code", "408"),
+        ("message", "timed out"),
+    ]));
+}
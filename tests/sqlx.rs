@@ -0,0 +1,81 @@
+use autoerror::AutoError;
+
+use std::error::Error as StdError;
+use std::fmt;
+
+use sqlx::error::{DatabaseError, ErrorKind};
+
+#[derive(Debug)]
+struct ConstraintError(ErrorKind);
+
+impl fmt::Display for ConstraintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("constraint violated")
+    }
+}
+
+impl StdError for ConstraintError {}
+
+impl DatabaseError for ConstraintError {
+    fn message(&self) -> &str {
+        "constraint violated"
+    }
+
+    fn as_error(&self) -> &(dyn StdError + Send + Sync + 'static) {
+        self
+    }
+
+    fn as_error_mut(&mut self) -> &mut (dyn StdError + Send + Sync + 'static) {
+        self
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn StdError + Send + Sync + 'static> {
+        self
+    }
+
+    fn kind(&self) -> ErrorKind {
+        match self.0 {
+            ErrorKind::UniqueViolation => ErrorKind::UniqueViolation,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+#[derive(Debug, AutoError)]
+enum UserError {
+    #[auto_error(sqlx = "unique_violation", format_str = "user already exists")]
+    Conflict(sqlx::Error),
+    #[auto_error(sqlx = "row_not_found", format_str = "user not found")]
+    NotFound,
+    Database(sqlx::Error),
+}
+
+#[derive(Debug, AutoError)]
+enum OrderError {
+    #[auto_error(sqlx = "pool_timed_out", format_str = "database busy")]
+    Busy,
+    #[auto_error(sqlx = "other", format_str = "query for order {0} failed")]
+    Query(u32, #[auto_error(from)] sqlx::Error),
+}
+
+pub fn main() {
+    let e: UserError = sqlx::Error::Database(Box::new(ConstraintError(ErrorKind::UniqueViolation))).into();
+    assert!(matches!(e, UserError::Conflict(_)));
+    assert_eq!(e.to_string(), "user already exists");
+    assert!(e.source().is_some());
+
+    let e: UserError = sqlx::Error::RowNotFound.into();
+    assert!(matches!(e, UserError::NotFound));
+    assert!(e.source().is_none());
+
+    let e: UserError = sqlx::Error::Database(Box::new(ConstraintError(ErrorKind::Other))).into();
+    assert!(matches!(e, UserError::Database(_)));
+    let e: UserError = sqlx::Error::PoolTimedOut.into();
+    assert!(matches!(e, UserError::Database(_)));
+
+    let e: OrderError = sqlx::Error::PoolTimedOut.into();
+    assert!(matches!(e, OrderError::Busy));
+    let e: OrderError = sqlx::Error::RowNotFound.into();
+    assert!(matches!(e, OrderError::Query(0, _)));
+    assert_eq!(e.to_string(), "query for order 0 failed");
+}
@@ -0,0 +1,11 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum UserError {
+    #[auto_error(sqlx = "row_not_found", format_str = "user not found")]
+    NotFound,
+    #[auto_error(format_str = "invalid name")]
+    InvalidName,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(format_str = "legacy {}", superseded_by = "Pasre")]
+    Legacy(String),
+    #[auto_error(format_str = "parse {}")]
+    Parse(String),
+}
+
+fn main() {}
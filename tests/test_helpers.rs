@@ -0,0 +1,40 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum IoError {
+    #[auto_error(format_str = "disk full")]
+    DiskFull,
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(kind, test_helpers)]
+enum Error {
+    #[auto_error(format_str = "{} not found")]
+    NotFound(String),
+    #[auto_error(format_str = "write failed")]
+    Write(#[auto_error(source)] IoError),
+}
+
+fn panic_message(f: impl FnOnce() + std::panic::UnwindSafe) -> String {
+    let payload = std::panic::catch_unwind(f).unwrap_err();
+    payload.downcast_ref::<String>().cloned().unwrap()
+}
+
+pub fn main() {
+    let e = Error::NotFound("user".into());
+    e.expect_kind(ErrorKind::NotFound);
+    let e = Error::expect_err_kind(Err::<(), _>(e), ErrorKind::NotFound);
+    assert_eq!(e.to_string(), "user not found");
+
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let message = panic_message(|| {
+        Error::Write(IoError::DiskFull).expect_kind(ErrorKind::NotFound);
+    });
+    assert_eq!(message, "expected error of kind NotFound, got Write: write failed\n    caused by: disk full");
+
+    let message = panic_message(|| {
+        Error::expect_err_kind(Ok::<_, Error>(42), ErrorKind::NotFound);
+    });
+    assert_eq!(message, "expected error of kind NotFound, got Ok(42)");
+}
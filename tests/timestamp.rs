@@ -0,0 +1,47 @@
+use autoerror::AutoError;
+use std::time::SystemTime;
+
+#[derive(Debug, AutoError)]
+#[auto_error(constructors)]
+enum Error {
+    Io(std::io::Error, #[auto_error(timestamp)] SystemTime),
+    #[auto_error(format_str = "{} not found")]
+    NotFound(#[auto_error(timestamp)] SystemTime, String),
+    #[auto_error(format_str = "missing")]
+    Missing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tick(u64);
+
+impl Tick {
+    pub fn now() -> Self {
+        Tick(42)
+    }
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(constructors)]
+enum TickError {
+    #[auto_error(format_str = "timed out")]
+    Timeout(#[auto_error(timestamp)] Tick),
+}
+
+pub fn main() {
+    let before = SystemTime::now();
+    let e = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    let after = SystemTime::now();
+    assert_eq!(e.to_string(), "oops");
+    assert!(std::error::Error::source(&e).is_some());
+    let timestamp = *e.timestamp().unwrap();
+    assert!(before <= timestamp && timestamp <= after);
+
+    let e = Error::not_found("page");
+    assert_eq!(e.to_string(), "page not found");
+    assert!(e.timestamp().is_some());
+    assert!(Error::Missing.timestamp().is_none());
+
+    let e = TickError::timeout();
+    assert_eq!(e.to_string(), "timed out");
+    assert_eq!(e.timestamp(), Some(&Tick(42)));
+}
@@ -0,0 +1,18 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(tonic)]
+enum Error {
+    #[auto_error(grpc = "not_found", format_str = "no such user {}")]
+    UserNotFound(u32),
+    Io(std::io::Error),
+}
+
+pub fn main() {
+    let status = tonic::Status::from(Error::UserNotFound(7));
+    assert_eq!(status.code(), tonic::Code::NotFound);
+    assert_eq!(status.message(), "no such user 7");
+
+    let status: tonic::Status = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops")).into();
+    assert_eq!(status.code(), tonic::Code::Internal);
+}
@@ -0,0 +1,78 @@
+use autoerror::AutoError;
+use std::sync::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+static EVENTS: Mutex<Vec<(Level, Vec<String>)>> = Mutex::new(Vec::new());
+
+struct Collector;
+
+struct Fields(Vec<String>);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.push(format!("{}={:?}", field.name(), value));
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn std::error::Error + 'static)) {
+        self.0.push(format!("{}={}", field.name(), value));
+    }
+}
+
+impl Subscriber for Collector {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(vec![]);
+        event.record(&mut fields);
+        EVENTS.lock().unwrap().push((*event.metadata().level(), fields.0));
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(tracing)]
+enum Error {
+    #[auto_error(code = "E0404", log = "warn", format_str = "no such user {}")]
+    UserNotFound(u32),
+    #[auto_error(code = "E0500", format_str = "failed")]
+    Failed,
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(tracing)]
+enum PlainError {
+    #[auto_error(log = "info", format_str = "cache miss")]
+    CacheMiss,
+}
+
+pub fn main() {
+    tracing::subscriber::set_global_default(Collector).unwrap();
+
+    Error::UserNotFound(7).record();
+    Error::Failed.record();
+    PlainError::CacheMiss.record();
+    let e = Error::Failed;
+    tracing::info!(error = e.as_value(), "retrying");
+
+    assert_eq!(*EVENTS.lock().unwrap(), vec![
+        (Level::WARN, vec!["message=no such user 7".to_string(), "kind=\"UserNotFound\"".to_string(), "code=\"E0404\"".to_string()]),
+        (Level::ERROR, vec!["message=failed".to_string(), "kind=\"Failed\"".to_string(), "code=\"E0500\"".to_string()]),
+        (Level::INFO, vec!["message=cache miss".to_string(), "kind=\"CacheMiss\"".to_string()]),
+        (Level::INFO, vec!["message=retrying".to_string(), "error=failed".to_string()]),
+    ]);
+}
@@ -0,0 +1,10 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(integrations(axum))]
+pub enum Error {
+    #[auto_error(format_str = "not found")]
+    NotFound,
+}
+
+pub fn main() {}
@@ -0,0 +1,14 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(format_str = "crashed", colour = "red")]
+    Crashed,
+    #[auto_error(make_from = true)]
+    Pair(String, String),
+    #[auto_error(whatever)]
+    Other,
+    Named { message: String },
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum AppError {
+    #[auto_error(format_str = "no such key {}")]
+    NotFound(String),
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(into_variants = "AppError")]
+enum StorageError {
+    #[auto_error(format_str = "no such key {}")]
+    NotFound(String),
+    #[auto_error(format_str = "storage is full")]
+    Full,
+}
+
+fn main() {}
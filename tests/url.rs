@@ -0,0 +1,28 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(url = "https://docs.example.com/errors/{code}")]
+enum Error {
+    #[auto_error(code = "E0404", format_str = "no such user {}")]
+    UserNotFound(u32),
+    #[auto_error(code = "E1042", url = "https://runbooks.example.com/disk-full", format_str = "disk full")]
+    DiskFull,
+    #[doc(hidden)]
+    __NonExhaustive,
+}
+
+#[derive(Debug, AutoError)]
+enum PlainError {
+    #[auto_error(url = "https://docs.example.com/timeouts", format_str = "timed out")]
+    Timeout,
+    #[auto_error(format_str = "failed")]
+    Failed,
+}
+
+pub fn main() {
+    assert_eq!(Error::UserNotFound(7).url().as_deref(), Some("https://docs.example.com/errors/E0404"));
+    assert_eq!(Error::DiskFull.url().as_deref(), Some("https://runbooks.example.com/disk-full"));
+    assert_eq!(Error::__NonExhaustive.url(), None);
+    assert_eq!(PlainError::Timeout.url().as_deref(), Some("https://docs.example.com/timeouts"));
+    assert_eq!(PlainError::Failed.url(), None);
+}
@@ -0,0 +1,63 @@
+use autoerror::AutoError;
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use validator::{ValidationError, ValidationErrors, ValidationErrorsKind};
+
+#[derive(Debug, AutoError)]
+#[auto_error(validator, serde, problem_details)]
+enum SignupError {
+    #[auto_error(make_from = true, status = 422)]
+    Invalid(ValidationErrors),
+    #[auto_error(format_str = "invalid address: {}")]
+    Address(Box<ValidationErrors>),
+    #[auto_error(format_str = "user {} exists", status = 409)]
+    Exists(String),
+}
+
+fn signup_errors() -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+    errors.add("email", ValidationError::new("email").with_message(Cow::from("must be a valid email")));
+    errors.add("name", ValidationError::new("length"));
+    let mut tag = ValidationErrors::new();
+    tag.add("label", ValidationError::new("required"));
+    let mut tags = BTreeMap::new();
+    tags.insert(1, Box::new(tag));
+    errors.errors_mut().insert(Cow::from("tags"), ValidationErrorsKind::List(tags));
+    errors
+}
+
+fn signup() -> Result<(), SignupError> {
+    Err(signup_errors())?;
+    Ok(())
+}
+
+pub fn main() {
+    let e = signup().unwrap_err();
+    assert_eq!(e.to_string(), "email: must be a valid email; name: length; tags[1].label: required");
+
+    let fields = e.validation_errors().unwrap();
+    assert_eq!(fields["email"], vec!["must be a valid email".to_string()]);
+    assert_eq!(fields.len(), 3);
+
+    let json = serde_json::to_value(&e).unwrap();
+    assert_eq!(json["validation"]["tags[1].label"], serde_json::json!(["required"]));
+
+    let details = e.to_problem_details();
+    assert_eq!(details.status, 422);
+    assert_eq!(details.errors, Some(fields));
+    let json = serde_json::to_value(&details).unwrap();
+    assert_eq!(json["errors"]["name"], serde_json::json!(["length"]));
+
+    let mut street = ValidationErrors::new();
+    street.add("street", ValidationError::new("required"));
+    let e = SignupError::Address(Box::new(street));
+    assert_eq!(e.to_string(), "invalid address: street: required");
+
+    let e = SignupError::Exists("ferris".to_string());
+    assert!(e.validation_errors().is_none());
+    let json = serde_json::to_value(&e).unwrap();
+    assert!(json.get("validation").is_none());
+    assert!(serde_json::to_value(&e.to_problem_details()).unwrap().get("errors").is_none());
+}
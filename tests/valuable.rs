@@ -0,0 +1,66 @@
+use autoerror::AutoError;
+use valuable::{NamedValues, Valuable, Value, Visit};
+
+#[derive(Debug, AutoError)]
+#[auto_error(valuable)]
+enum Error {
+    #[auto_error(code = "E0404", format_str = "no such user {}")]
+    UserNotFound(u32),
+    #[doc(hidden)]
+    __NonExhaustive,
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(valuable)]
+#[repr(u16)]
+enum NumericError {
+    #[auto_error(format_str = "busy")]
+    Busy = 503,
+}
+
+#[derive(Debug, AutoError)]
+#[auto_error(valuable)]
+enum PlainError {
+    #[auto_error(format_str = "cache miss")]
+    CacheMiss,
+}
+
+struct Fields(Vec<String>);
+
+impl Visit for Fields {
+    fn visit_value(&mut self, value: Value<'_>) {
+        if let Value::Structable(value) = value {
+            self.0.push(value.definition().name().to_string());
+            value.visit(self);
+        }
+    }
+
+    fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+        for (field, value) in named_values {
+            self.0.push(format!("{}={:?}", field.name(), value));
+        }
+    }
+}
+
+fn fields(value: &impl Valuable) -> Vec<String> {
+    let mut fields = Fields(vec![]);
+    valuable::visit(value, &mut fields);
+    fields.0
+}
+
+pub fn main() {
+    assert_eq!(fields(&Error::UserNotFound(7)), [
+        "Error",
+        "kind=\"UserNotFound\"",
+        "code=\"E0404\"",
+        "message=\"no such user 7\"",
+    ]);
+    assert_eq!(fields(&Error::__NonExhaustive), [
+        "Error",
+        "kind=\"__NonExhaustive\"",
+        "code=()",
+        "message=\"unknown error\"",
+    ]);
+    assert_eq!(fields(&NumericError::Busy), ["NumericError", "kind=\"Busy\"", "code=503", "message=\"busy\""]);
+    assert_eq!(fields(&PlainError::CacheMiss), ["PlainError", "kind=\"CacheMiss\"", "message=\"cache miss\""]);
+}
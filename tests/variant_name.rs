@@ -0,0 +1,16 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    NotFound,
+    Io(std::io::Error),
+    #[auto_error(format_str = "invalid {} at {}")]
+    Invalid(String, usize),
+}
+
+pub fn main() {
+    assert_eq!(Error::NotFound.variant_name(), "NotFound");
+    let e = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert_eq!(e.variant_name(), "Io");
+    assert_eq!(Error::Invalid("key".to_string(), 3).variant_name(), "Invalid");
+}
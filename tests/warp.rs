@@ -0,0 +1,20 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+#[auto_error(warp)]
+enum Error {
+    #[auto_error(format_str = "no such user {}")]
+    UserNotFound(u32),
+    Io(std::io::Error),
+}
+
+pub fn main() {
+    let rejection = Error::UserNotFound(7).into_rejection();
+    match rejection.find::<Error>() {
+        Some(Error::UserNotFound(7)) => {}
+        _ => panic!("expected the error to be found in the rejection"),
+    }
+
+    let rejection = warp::reject::custom(Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oops")));
+    assert!(rejection.find::<Error>().unwrap().to_string() == "oops");
+}
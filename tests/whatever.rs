@@ -0,0 +1,30 @@
+use autoerror::AutoError;
+
+#[derive(Debug, AutoError)]
+enum Error {
+    #[auto_error(format_str = "not found")]
+    NotFound,
+    #[auto_error(whatever)]
+    Whatever(String, Option<Box<dyn std::error::Error + Send + Sync>>),
+}
+
+#[derive(Debug, AutoError)]
+enum SimpleError {
+    #[auto_error(whatever, format_str = "oops: {}")]
+    Message(String),
+}
+
+use std::error::Error as StdError;
+
+pub fn main() {
+    let e = Error::whatever(format_args!("bad value {}", 5));
+    assert_eq!(e.to_string(), "bad value 5");
+    assert!(e.source().is_none());
+
+    let e = Error::whatever_with_source(std::io::Error::new(std::io::ErrorKind::Other, "disk full"), "saving");
+    assert_eq!(e.to_string(), "saving");
+    assert_eq!(e.source().unwrap().to_string(), "disk full");
+    assert!(!matches!(e, Error::NotFound));
+
+    assert_eq!(SimpleError::whatever("prototype").to_string(), "oops: prototype");
+}